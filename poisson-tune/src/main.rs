@@ -0,0 +1,200 @@
+use clap::{builder::PossibleValuesParser, Arg, Command};
+
+use criterion::Criterion;
+
+use poisson::{
+    algorithm::{Bridson, Creator, DartThrowing, Ebeida},
+    Builder, Float, Tuning, Type, Vector,
+};
+
+use rand::distr::{Distribution, StandardUniform};
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use nalgebra::{Vector2, Vector3, Vector4};
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Factors the cells of the grid are scaled by that are tried for Bridson and dart throwing.
+const GRID_FACTORS: [f64; 4] = [0.5, 0.7, 1., 1.4];
+
+/// Amounts of samples Bridson tries around an active sample, which is k in the paper of Bridson.
+const BRIDSON_K: [usize; 4] = [10, 20, 30, 50];
+
+/// Amount of seeds the amount of samples generated with each setting is the median of.
+const SEEDS: u64 = 5;
+
+/// Time criterion warms up for before measuring each setting.
+const WARM_UP: Duration = Duration::from_millis(500);
+
+/// Time criterion measures each setting for.
+const MEASUREMENT: Duration = Duration::from_secs(2);
+
+/// Settings are recommended only if they generate at least this share of the samples of the default settings.
+const MIN_COVERAGE: f64 = 0.95;
+
+fn main() {
+    let app = Command::new("Poisson tune")
+        .author("delma")
+        .version("0.1.0")
+        .about("Benchmarks the settings of poisson library for given parameters on this machine")
+        .arg(
+            Arg::new("radius")
+                .short('r')
+                .value_name("RADIUS")
+                .help("Radius of the disks")
+                .required(true),
+        )
+        .arg(
+            Arg::new("dimension")
+                .short('d')
+                .value_name("DIMENSION")
+                .help("Dimension of the samples")
+                .value_parser(PossibleValuesParser::new(["2", "3", "4"])),
+        )
+        .arg(
+            Arg::new("type")
+                .short('t')
+                .value_name("TYPE")
+                .help("Type of the distribution")
+                .value_parser(PossibleValuesParser::new(["normal", "perioditic"])),
+        )
+        .arg(
+            Arg::new("runs")
+                .short('n')
+                .value_name("RUNS")
+                .help("Amount of samples criterion measures for each setting, at least 10"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .value_name("OUTPUT")
+                .help("TOML file the recommended settings are written to"),
+        );
+    let matches = app.get_matches();
+    let radius = matches
+        .get_one::<String>("radius")
+        .map(|r| r.parse::<f64>().expect("Radius should be number"))
+        .expect("Radius is required");
+    let dimension = matches
+        .get_one::<String>("dimension")
+        .map_or(2, |d| d.parse().expect("Dimension should be 2, 3 or 4"));
+    let poisson_type = match matches.get_one::<String>("type").map(String::as_str) {
+        Some("perioditic") => Type::Perioditic,
+        _ => Type::Normal,
+    };
+    let runs = matches
+        .get_one::<String>("runs")
+        .map_or(10, |n| n.parse().expect("Runs should be positive integer"));
+    let tuning = match dimension {
+        2 => tune::<Vector2<f64>>(radius, poisson_type, runs),
+        3 => tune::<Vector3<f64>>(radius, poisson_type, runs),
+        _ => tune::<Vector4<f64>>(radius, poisson_type, runs),
+    };
+    print!("\nRecommended settings:\n{}", tuning);
+    if let Some(path) = matches.get_one::<String>("output") {
+        fs::write(path, tuning.to_string()).expect("Writing the tuning failed");
+        println!("Written to {}", path);
+    }
+}
+
+/// Benchmarks every combination of the settings with criterion and returns the fastest one that covers the space
+/// well enough.
+fn tune<V>(radius: f64, poisson_type: Type, runs: usize) -> Tuning
+where
+    V: Vector<f64>,
+    StandardUniform: Distribution<V>,
+{
+    let mut bench = Bench::new(runs);
+    let builder = Builder::<f64, V>::with_disk_radius(radius, poisson_type);
+    let expected = count(&builder, Bridson);
+    let mut candidates = vec![];
+    candidates.push(("ebeida", 1., builder.attempts(), bench.measure("ebeida", &builder, Ebeida)));
+    for &factor in &GRID_FACTORS {
+        let scaled = builder.clone().with_cell_scale(factor);
+        let id = format!("dart-{}", factor);
+        candidates.push(("dart", factor, builder.attempts(), bench.measure(&id, &scaled, DartThrowing)));
+        for &k in &BRIDSON_K {
+            let tuned = scaled.clone().with_attempts(k);
+            let id = format!("bridson-{}-{}", factor, k);
+            candidates.push(("bridson", factor, k, bench.measure(&id, &tuned, Bridson)));
+        }
+    }
+    println!("{:<10}{:>13}{:>11}{:>14}{:>10}", "algorithm", "grid factor", "bridson k", "time", "samples");
+    let mut best: Option<(Duration, Tuning)> = None;
+    for (algorithm, grid_factor, bridson_k, (time, samples)) in candidates {
+        println!(
+            "{:<10}{:>13}{:>11}{:>14}{:>10}",
+            algorithm,
+            grid_factor,
+            bridson_k,
+            format!("{:.3?}", time),
+            samples
+        );
+        if (samples as f64) < MIN_COVERAGE * expected as f64 {
+            continue;
+        }
+        if best.as_ref().is_none_or(|(fastest, _)| time < *fastest) {
+            let algorithm = algorithm.to_owned();
+            best = Some((time, Tuning { algorithm, grid_factor, bridson_k }));
+        }
+    }
+    best.map_or_else(Tuning::default, |(_, tuning)| tuning)
+}
+
+/// Criterion set up for benchmarking the settings one by one.
+struct Bench {
+    criterion: Criterion,
+    dir: PathBuf,
+}
+
+impl Bench {
+    fn new(runs: usize) -> Self {
+        let dir = env::temp_dir().join("poisson-tune");
+        let criterion = Criterion::default()
+            .sample_size(runs.max(10))
+            .warm_up_time(WARM_UP)
+            .measurement_time(MEASUREMENT)
+            .output_directory(&dir)
+            .without_plots();
+        Bench { criterion, dir }
+    }
+
+    /// Returns the median time criterion measures for generating the distribution
+    /// and the median amount of samples generated.
+    fn measure<F, V, A>(&mut self, id: &str, builder: &Builder<F, V>, algo: A) -> (Duration, usize)
+    where
+        F: Float,
+        V: Vector<F>,
+        A: Creator<F, V>,
+    {
+        let generator = builder.clone().build(SmallRng::seed_from_u64(0), algo);
+        self.criterion.bench_function(id, |b| b.iter(|| generator.generate()));
+        // Criterion reports the estimates only as files in its output directory, so they are read back from there.
+        let path = self.dir.join(id).join("new").join("estimates.json");
+        let estimates = fs::read_to_string(&path).expect("Criterion should have written the estimates");
+        let estimates: serde_json::Value =
+            serde_json::from_str(&estimates).expect("Criterion should have written the estimates as JSON");
+        let nanos = estimates["median"]["point_estimate"]
+            .as_f64()
+            .expect("Estimates should have the median");
+        (Duration::from_nanos(nanos as u64), count(builder, algo))
+    }
+}
+
+/// Returns the median amount of samples generated.
+fn count<F, V, A>(builder: &Builder<F, V>, algo: A) -> usize
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    let mut counts = (0..SEEDS)
+        .map(|seed| builder.clone().build(SmallRng::seed_from_u64(seed), algo).generate().len())
+        .collect::<Vec<_>>();
+    counts.sort();
+    counts[counts.len() / 2]
+}
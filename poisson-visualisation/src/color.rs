@@ -0,0 +1,112 @@
+//! Colors and palettes for the disks and the background.
+
+use image::Rgba;
+
+use rand::Rng;
+
+use std::str::FromStr;
+
+/// Stops of the viridis colormap from dark to light.
+const VIRIDIS: [[u8; 3]; 10] = [
+    [0x44, 0x01, 0x54],
+    [0x48, 0x28, 0x78],
+    [0x3e, 0x49, 0x89],
+    [0x31, 0x68, 0x8e],
+    [0x26, 0x82, 0x8e],
+    [0x1f, 0x9e, 0x89],
+    [0x35, 0xb7, 0x79],
+    [0x6e, 0xce, 0x58],
+    [0xb5, 0xde, 0x2b],
+    [0xfd, 0xe7, 0x25],
+];
+
+/// Stops of the magma colormap from dark to light.
+const MAGMA: [[u8; 3]; 10] = [
+    [0x00, 0x00, 0x04],
+    [0x18, 0x0f, 0x3d],
+    [0x44, 0x0f, 0x76],
+    [0x72, 0x1f, 0x81],
+    [0x9e, 0x2f, 0x7f],
+    [0xcd, 0x40, 0x71],
+    [0xf1, 0x60, 0x5d],
+    [0xfd, 0x96, 0x68],
+    [0xfe, 0xca, 0x8d],
+    [0xfc, 0xfd, 0xbf],
+];
+
+/// Colors the colorful style picks from.
+#[derive(PartialEq, Debug, Clone)]
+pub enum Palette {
+    /// Any color along the viridis colormap.
+    Viridis,
+    /// Any color along the magma colormap.
+    Magma,
+    /// One of the listed colors.
+    Colors(Vec<Rgba<u8>>),
+}
+
+impl Palette {
+    /// Picks random color of the palette.
+    pub fn pick<R: Rng>(&self, rng: &mut R) -> Rgba<u8> {
+        match *self {
+            Palette::Viridis => gradient(&VIRIDIS, rng.random()),
+            Palette::Magma => gradient(&MAGMA, rng.random()),
+            Palette::Colors(ref colors) => colors[rng.random_range(0..colors.len())],
+        }
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    /// Parses either name of a colormap or comma separated list of hex colors.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "viridis" => Ok(Palette::Viridis),
+            "magma" => Ok(Palette::Magma),
+            _ => s
+                .split(',')
+                .map(parse_color)
+                .collect::<Result<_, _>>()
+                .map(Palette::Colors)
+                .map_err(|_| {
+                    format!(
+                        "Invalid palette, expected viridis, magma or hex list: {}",
+                        s
+                    )
+                }),
+        }
+    }
+}
+
+/// Interpolates linearly between the stops at `t` in [0, 1].
+fn gradient(stops: &[[u8; 3]], t: f32) -> Rgba<u8> {
+    let position = t.clamp(0., 1.) * (stops.len() - 1) as f32;
+    let index = (position as usize).min(stops.len() - 2);
+    let fraction = position - index as f32;
+    let (from, to) = (stops[index], stops[index + 1]);
+    let mix =
+        |n: usize| (from[n] as f32 + (to[n] as f32 - from[n] as f32) * fraction).round() as u8;
+    Rgba([mix(0), mix(1), mix(2), 255])
+}
+
+/// Parses opaque color from hex of form `#rrggbb` where the `#` is optional.
+pub fn parse_color(s: &str) -> Result<Rgba<u8>, String> {
+    let invalid = || format!("Invalid color, expected #rrggbb: {}", s);
+    let hex = s.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+    let channel = |n: usize| u8::from_str_radix(&hex[2 * n..2 * n + 2], 16).map_err(|_| invalid());
+    Ok(Rgba([channel(0)?, channel(1)?, channel(2)?, 255]))
+}
+
+/// Parses background that is either `transparent` or opaque hex color.
+pub fn parse_background(s: &str) -> Result<Rgba<u8>, String> {
+    if s.eq_ignore_ascii_case("transparent") {
+        Ok(Rgba([0, 0, 0, 0]))
+    } else {
+        parse_color(s)
+    }
+}
@@ -0,0 +1,78 @@
+//! Externally provided point sets rendered for validation.
+
+use nalgebra::Vector2;
+
+use image::{ImageBuffer, Rgba};
+
+use std::fs;
+
+/// Loads points from CSV file with `x,y` on each line in [0, 1) coordinates.
+/// Empty lines, lines starting with `#` and a header line that doesn't parse are skipped.
+pub fn load(path: &str) -> Result<Vec<Vector2<f32>>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    let mut points = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || format!("Invalid point on line {} of {}: {}", i + 1, path, line);
+        let (x, y) = line.split_once(',').ok_or_else(invalid)?;
+        match (x.trim().parse(), y.trim().parse()) {
+            (Ok(x), Ok(y)) => points.push(Vector2::new(x, y)),
+            _ if i == 0 => continue,
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(points)
+}
+
+/// Returns the index pairs of points that are closer than the diameter to each other.
+pub fn violations(points: &[Vector2<f32>], radius: f32) -> Vec<(usize, usize)> {
+    let diameter = 2. * radius;
+    let mut order = (0..points.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| {
+        points[a]
+            .x
+            .partial_cmp(&points[b].x)
+            .expect("Points should not be NaN.")
+    });
+    let mut pairs = vec![];
+    for (i, &a) in order.iter().enumerate() {
+        // Points are sorted along x so the search can stop once they are farther along it than the diameter.
+        for &b in &order[i + 1..] {
+            if points[b].x - points[a].x >= diameter {
+                break;
+            }
+            if (points[b] - points[a]).norm() < diameter {
+                pairs.push((a.min(b), a.max(b)));
+            }
+        }
+    }
+    pairs.sort();
+    pairs
+}
+
+/// Draws red line between the points of each violating pair.
+pub fn draw_violations(
+    image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    points: &[Vector2<f32>],
+    pairs: &[(usize, usize)],
+) {
+    let (width, height) = image.dimensions();
+    let to_pixel =
+        |p: Vector2<f32>| Vector2::new(p.x * width as f32, height as f32 - p.y * height as f32);
+    for &(a, b) in pairs {
+        let (from, to) = (to_pixel(points[a]), to_pixel(points[b]));
+        let steps = (to - from).amax().ceil().max(1.) as u32;
+        for s in 0..=steps {
+            let p = from + (to - from) * (s as f32 / steps as f32);
+            let (x, y) = (p.x as i32, p.y as i32);
+            if x < 0 || x >= width as i32 || y < 0 || y >= height as i32 {
+                // Outside of the picture
+                continue;
+            }
+            image[(x as u32, y as u32)] = Rgba([255, 0, 0, 255]);
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Text labels drawn next to the points.
+//!
+//! Labels only need digits and a few punctuation marks, so they are drawn with a tiny built-in bitmap font
+//! instead of loading a font file.
+
+use image::{ImageBuffer, Rgba};
+
+use nalgebra::Vector2;
+
+use std::str::FromStr;
+
+/// Width of a glyph in font pixels.
+const GLYPH_WIDTH: i32 = 3;
+/// Height of a glyph in font pixels.
+const GLYPH_HEIGHT: i32 = 5;
+/// Size of a font pixel in image pixels.
+const SCALE: i32 = 2;
+/// Color of the labels.
+const LABEL_COLOR: Rgba<u8> = Rgba([255, 220, 0, 255]);
+
+/// What is written next to each point.
+#[derive(PartialEq, Debug, Clone, Copy, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub enum Labels {
+    /// Index of the point in the order it was generated or read in.
+    Index,
+    /// Coordinates of the point.
+    Coords,
+}
+
+impl FromStr for Labels {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "index" => Ok(Labels::Index),
+            "coords" => Ok(Labels::Coords),
+            _ => Err(format!("Invalid labels: {}", s)),
+        }
+    }
+}
+
+impl TryFrom<String> for Labels {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Labels::from_str(&s)
+    }
+}
+
+impl Labels {
+    fn text(self, index: usize, p: &Vector2<f32>) -> String {
+        match self {
+            Labels::Index => index.to_string(),
+            Labels::Coords => format!("({:.3}, {:.3})", p.x, p.y),
+        }
+    }
+}
+
+/// Returns the rows of the glyph from top to bottom with the leftmost pixel in the highest bit.
+/// Characters without a glyph are left blank.
+fn glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '(' => [0b001, 0b010, 0b010, 0b010, 0b001],
+        ')' => [0b100, 0b010, 0b010, 0b010, 0b100],
+        _ => [0; 5],
+    }
+}
+
+/// Writes the text with its top left corner at the pixel, clipping it at the edges of the image.
+fn draw_text(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, text: &str, x: i32, y: i32) {
+    let (width, height) = image.dimensions();
+    for (i, c) in text.chars().enumerate() {
+        let left = x + i as i32 * (GLYPH_WIDTH + 1) * SCALE;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for column in 0..GLYPH_WIDTH {
+                if bits & (1 << (GLYPH_WIDTH - 1 - column)) == 0 {
+                    continue;
+                }
+                for dx in 0..SCALE {
+                    for dy in 0..SCALE {
+                        let xx = left + column * SCALE + dx;
+                        let yy = y + row as i32 * SCALE + dy;
+                        if xx < 0 || xx >= width as i32 || yy < 0 || yy >= height as i32 {
+                            // Outside of the picture
+                            continue;
+                        }
+                        image[(xx as u32, yy as u32)] = LABEL_COLOR;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Writes the label of each point to the right of its disk.
+pub fn draw_labels(
+    image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    labels: Labels,
+    points: &[Vector2<f32>],
+    radius: f32,
+) {
+    let (width, height) = image.dimensions();
+    for (i, p) in points.iter().enumerate() {
+        let x = (p.x * width as f32 + radius * width as f32) as i32 + SCALE;
+        // Image rows grow downwards so the label is centered on the flipped position of the point.
+        let y = height as i32 - (p.y * height as f32) as i32 - GLYPH_HEIGHT * SCALE / 2;
+        draw_text(image, &labels.text(i, p), x, y);
+    }
+}
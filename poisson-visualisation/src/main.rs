@@ -1,28 +1,45 @@
-use clap::{builder::PossibleValuesParser, Arg, ArgMatches, Command};
+use clap::{builder::PossibleValuesParser, Arg, ArgAction, ArgMatches, Command};
 
 use poisson::{
-    algorithm::{Bridson, Ebeida},
-    Builder, Type,
+    algorithm::{Bridson, Creator, DartThrowing, Ebeida},
+    field::ToneMapping,
+    Builder, OccupancyImage, Type,
 };
 
 use rand::rngs::SmallRng;
 use rand::{rng, seq::SliceRandom, Rng, SeedableRng};
 
+use rayon::prelude::*;
+
 use nalgebra::Vector2;
 
-use image::{ImageBuffer, Rgb};
+use image::{imageops, DynamicImage, ImageBuffer, Rgba};
 
 use lab::Lab;
 
 use fnv::FnvHasher;
 
+use std::fs;
 use std::hash::Hasher;
+use std::path::Path;
 use std::str::FromStr;
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+use color::Palette;
+use label::Labels;
+
+mod color;
+mod input;
+mod label;
+mod scene;
+mod stats;
+mod stipple;
+
+#[derive(PartialEq, Debug, Clone, Copy, serde::Deserialize)]
+#[serde(try_from = "String")]
 pub enum Algo {
     Ebeida,
     Bridson,
+    DartThrowing,
 }
 
 impl FromStr for Algo {
@@ -32,18 +49,28 @@ impl FromStr for Algo {
         match s.to_lowercase().as_str() {
             "ebeida" => Ok(Algo::Ebeida),
             "bridson" => Ok(Algo::Bridson),
+            "dart" => Ok(Algo::DartThrowing),
             _ => Err(format!("Invalid algorithm: {}", s)),
         }
     }
 }
 
-#[derive(PartialEq, Debug, Clone, Copy)]
+#[derive(PartialEq, Debug, Clone, Copy, serde::Deserialize)]
+#[serde(try_from = "String")]
 pub enum Style {
     Plain,
     Colorful,
     Dot,
 }
 
+impl TryFrom<String> for Algo {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Algo::from_str(&s)
+    }
+}
+
 impl FromStr for Style {
     type Err = String;
 
@@ -57,11 +84,56 @@ impl FromStr for Style {
     }
 }
 
+impl TryFrom<String> for Style {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Style::from_str(&s)
+    }
+}
+
 fn main() {
+    // Arguments shared with the stipple subcommand.
+    let width = Arg::new("width")
+        .short('w')
+        .value_name("WIDTH")
+        .help("Width of the generated image");
+    let height = Arg::new("height")
+        .short('h')
+        .value_name("HEIGHT")
+        .help("Height of the generated image");
+    let style = Arg::new("style")
+        .short('s')
+        .value_name("STYLE")
+        .help("Style for the disks")
+        .value_parser(PossibleValuesParser::new(["plain", "colorful", "dot"]));
+    let point_color = Arg::new("point-color")
+        .long("point-color")
+        .value_name("COLOR")
+        .help("Hex color of the disks in the plain and dot styles")
+        .value_parser(|s: &str| color::parse_color(s));
+    let background = Arg::new("background")
+        .long("background")
+        .value_name("BACKGROUND")
+        .help("Hex color of the background or transparent for RGBA image")
+        .value_parser(|s: &str| color::parse_background(s));
+    let algo = Arg::new("algo")
+        .short('a')
+        .help("Algorithm that's used to generate image")
+        .value_name("ALGO")
+        .value_parser(PossibleValuesParser::new(["ebeida", "bridson", "dart"]));
     let app = Command::new("Poisson visualisation")
         .author("delma")
         .version("0.1.0")
         .about("Visualisation for poisson library")
+        // -h is taken by height so help is only available as --help.
+        .disable_help_flag(true)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .help("Print help")
+                .action(ArgAction::Help),
+        )
         .arg(
             Arg::new("OUTPUT")
                 .help("Output file that's generated")
@@ -76,88 +148,684 @@ fn main() {
                 .help("Radius of the disks"),
         )
         .arg(
-            Arg::new("width")
-                .short('w')
-                .value_name("WIDTH")
-                .help("Width of the generated image"),
+            Arg::new("samples")
+                .long("samples")
+                .visible_alias("count")
+                .value_name("SAMPLES")
+                .help("Approximate amount of points that the radius is derived from and printed, instead of -r")
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all(["radius", "sweep"]),
+        )
+        .arg(width.clone())
+        .arg(height.clone())
+        .arg(style.clone())
+        .arg(
+            Arg::new("relative")
+                .long("relative")
+                .value_name("RELATIVE")
+                .help("Radius relative to the largest one that fits the amount of points, 0.8 by default")
+                .requires("samples"),
+        )
+        .arg(
+            Arg::new("palette")
+                .long("palette")
+                .value_name("PALETTE")
+                .help("Colors of the colorful style: viridis, magma or comma separated hex colors")
+                .value_parser(|s: &str| Palette::from_str(s)),
         )
+        .arg(point_color.clone())
+        .arg(background.clone())
         .arg(
-            Arg::new("height")
-                .short('h')
-                .value_name("HEIGHT")
-                .help("Height of the generated image"),
+            Arg::new("labels")
+                .long("labels")
+                .value_name("LABELS")
+                .help("Writes the index or the coordinates of each point next to it")
+                .value_parser(PossibleValuesParser::new(["index", "coords"])),
         )
+        .arg(algo.clone())
         .arg(
-            Arg::new("style")
-                .short('s')
-                .value_name("STYLE")
-                .help("Style for the disks")
-                .value_parser(PossibleValuesParser::new(["plain", "colorful", "dot"])),
+            Arg::new("show-exclusion")
+                .long("show-exclusion")
+                .help("Draws the exclusion zone of radius 2r around each point")
+                .action(ArgAction::SetTrue),
         )
         .arg(
-            Arg::new("algo")
-                .short('a')
-                .help("Algorithm that's used to generate image")
-                .value_name("ALGO")
-                .value_parser(PossibleValuesParser::new(["ebeida", "bridson"])),
+            Arg::new("sweep")
+                .long("sweep")
+                .value_name("PARAM=START..END:STEPS")
+                .help("Renders contact sheet over values of a parameter, e.g. radius=0.01..0.1:10")
+                .value_parser(|s: &str| Sweep::from_str(s)),
+        )
+        .arg(
+            Arg::new("sweep-seeds")
+                .long("sweep-seeds")
+                .value_name("SEEDS")
+                .help("Amount of seeds rendered as rows of the sweep")
+                .requires("sweep"),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .value_name("SCENE")
+                .help("Renders layers described in TOML file composited into one image")
+                .conflicts_with("sweep"),
+        )
+        .arg(
+            Arg::new("stats")
+                .long("stats")
+                .help("Writes nearest neighbour histogram and summary statistics next to the image")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["sweep", "config"]),
+        )
+        .arg(
+            Arg::new("input")
+                .long("input")
+                .value_name("POINTS")
+                .help("Renders points from CSV file with their exclusion zones and marks pairs closer than 2r")
+                .conflicts_with_all(["sweep", "config"]),
+        )
+        .arg(
+            Arg::new("debug-grid")
+                .long("debug-grid")
+                .help("Shades the cells of the acceleration grid by how many points they hold under the points")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["config", "input"]),
+        )
+        .arg(
+            Arg::new("show-tiling")
+                .long("show-tiling")
+                .help("Generates perioditic distribution and renders it repeated 3×3 with the borders of the tiles")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["config", "input", "stats"]),
+        )
+        .arg(
+            Arg::new("ensemble")
+                .long("ensemble")
+                .value_name("COUNT")
+                .help("Renders independent distributions side by side, generated in parallel with --threads")
+                .value_parser(clap::value_parser!(usize))
+                .conflicts_with_all([
+                    "sweep",
+                    "config",
+                    "input",
+                    "stats",
+                    "debug-grid",
+                    "show-tiling",
+                ]),
+        )
+        .arg(
+            Arg::new("threads")
+                .long("threads")
+                .value_name("THREADS")
+                .help("Amount of threads distributions are generated with, all cores by default")
+                .value_parser(clap::value_parser!(usize)),
+        )
+        .subcommand_negates_reqs(true)
+        .args_conflicts_with_subcommands(true)
+        .subcommand(
+            Command::new("stipple")
+                .about("Stipples the photo with dots that are denser where it's darker, as black on white by default")
+                .disable_help_flag(true)
+                .arg(
+                    Arg::new("help")
+                        .long("help")
+                        .help("Print help")
+                        .action(ArgAction::Help),
+                )
+                .arg(
+                    Arg::new("PHOTO")
+                        .help("Photo that's stippled")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("OUTPUT")
+                        .help("Output file that's generated")
+                        .required(true)
+                        .index(2),
+                )
+                .arg(Arg::new("SEED").help("Seed for the generation").index(3))
+                .arg(
+                    Arg::new("radius")
+                        .short('r')
+                        .value_name("MIN..MAX")
+                        .help("Radii of the disks where the photo is black and where it's white, 0.005..0.02 by default")
+                        .value_parser(|s: &str| stipple::parse_radii(s)),
+                )
+                .arg(
+                    Arg::new("tone")
+                        .long("tone")
+                        .value_name("TONE")
+                        .help("Mapping of the brightness of the photo: linear, equalized or gamma:<power>")
+                        .value_parser(|s: &str| stipple::parse_tone(s)),
+                )
+                .arg(width)
+                .arg(height)
+                .arg(style)
+                .arg(point_color)
+                .arg(background)
+                .arg(algo),
         );
     visualise(app.get_matches());
 }
 
+/// Parameter that can be swept over.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum Param {
+    Radius,
+}
+
+/// Sweep over values of a parameter.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Sweep {
+    param: Param,
+    start: f32,
+    end: f32,
+    steps: usize,
+}
+
+impl Sweep {
+    fn value(&self, step: usize) -> f32 {
+        if self.steps < 2 {
+            return self.start;
+        }
+        self.start + (self.end - self.start) * step as f32 / (self.steps - 1) as f32
+    }
+}
+
+impl FromStr for Sweep {
+    type Err = String;
+
+    /// Parses sweeps of form `param=start..end:steps`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid sweep, expected param=start..end:steps: {}", s);
+        let (param, range) = s.split_once('=').ok_or_else(invalid)?;
+        let (range, steps) = range.split_once(':').ok_or_else(invalid)?;
+        let (start, end) = range.split_once("..").ok_or_else(invalid)?;
+        let param = match param.to_lowercase().as_str() {
+            "radius" => Param::Radius,
+            _ => return Err(format!("Invalid sweep parameter: {}", param)),
+        };
+        let steps = steps.parse().map_err(|_| invalid())?;
+        if steps == 0 {
+            return Err(invalid());
+        }
+        Ok(Sweep {
+            param,
+            start: start.parse().map_err(|_| invalid())?,
+            end: end.parse().map_err(|_| invalid())?,
+            steps,
+        })
+    }
+}
+
+/// Settings for rendering single distribution.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Settings {
+    width: u32,
+    height: u32,
+    radius: f32,
+    algo: Algo,
+    style: Style,
+    show_exclusion: bool,
+    debug_grid: bool,
+    show_tiling: bool,
+    labels: Option<Labels>,
+    color: Option<Rgba<u8>>,
+    palette: Option<Palette>,
+    background: Rgba<u8>,
+}
+
+impl Settings {
+    fn with(&self, param: Param, value: f32) -> Settings {
+        let mut settings = self.clone();
+        match param {
+            Param::Radius => settings.radius = value,
+        }
+        settings
+    }
+}
+
 fn visualise(m: ArgMatches) {
-    let width: u32 = m
-        .get_one::<String>("width")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(1024);
-    let height: u32 = m
-        .get_one::<String>("height")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(1024);
-    let radius: f32 = m
-        .get_one::<String>("radius")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0.02);
-    let algo = m
-        .get_one::<String>("algo")
-        .and_then(|s| Algo::from_str(s).ok())
-        .unwrap_or(Algo::Ebeida);
-    let style = m
-        .get_one::<String>("style")
-        .and_then(|s| Style::from_str(s).ok())
-        .unwrap_or(Style::Plain);
-    let name = m.get_one::<String>("OUTPUT").expect("OUTPUT argument is required");
+    // Independent distributions are generated in parallel and merged in order,
+    // so the image is the same with any amount of threads.
+    let threads = m.get_one::<usize>("threads").copied();
+    if let Some(threads) = threads {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .expect("Failed to create thread pool");
+    }
+    if let Some(("stipple", m)) = m.subcommand() {
+        return stipple(m);
+    }
+    let settings = Settings {
+        width: m
+            .get_one::<String>("width")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1024),
+        height: m
+            .get_one::<String>("height")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1024),
+        radius: match m.get_one::<usize>("samples") {
+            Some(&samples) => {
+                let relative = m
+                    .get_one::<String>("relative")
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(0.8);
+                let poisson_type = if m.get_flag("show-tiling") {
+                    Type::Perioditic
+                } else {
+                    Type::Normal
+                };
+                let radius =
+                    Builder::<_, Vector2<f32>>::with_samples(samples, relative, poisson_type)
+                        .radius();
+                println!("Radius {} for {} points", radius, samples);
+                radius
+            }
+            None => m
+                .get_one::<String>("radius")
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.02),
+        },
+        algo: m
+            .get_one::<String>("algo")
+            .and_then(|s| Algo::from_str(s).ok())
+            .unwrap_or(Algo::Ebeida),
+        style: m
+            .get_one::<String>("style")
+            .and_then(|s| Style::from_str(s).ok())
+            .unwrap_or(Style::Plain),
+        show_exclusion: m.get_flag("show-exclusion"),
+        debug_grid: m.get_flag("debug-grid"),
+        show_tiling: m.get_flag("show-tiling"),
+        labels: m
+            .get_one::<String>("labels")
+            .and_then(|s| Labels::from_str(s).ok()),
+        color: m.get_one::<Rgba<u8>>("point-color").copied(),
+        palette: m.get_one::<Palette>("palette").cloned(),
+        background: m
+            .get_one::<Rgba<u8>>("background")
+            .copied()
+            .unwrap_or(Rgba([0, 0, 0, 255])),
+    };
+    let name = m
+        .get_one::<String>("OUTPUT")
+        .expect("OUTPUT argument is required");
+    let master_rng = m
+        .get_one::<String>("SEED")
+        .map(|s| seeded_rng(s))
+        .unwrap_or_else(|| SmallRng::from_rng(&mut rng()));
+
+    let image = if let Some(path) = m.get_one::<String>("config") {
+        let scene = scene::Scene::load(path).unwrap_or_else(|e| panic!("{}", e));
+        scene.render(&settings, master_rng)
+    } else if let Some(sweep) = m.get_one::<Sweep>("sweep") {
+        let seeds = m
+            .get_one::<String>("sweep-seeds")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1);
+        render_sweep(&settings, *sweep, seeds, master_rng)
+    } else if let Some(&count) = m.get_one::<usize>("ensemble") {
+        render_ensemble(&settings, count, threads.is_some(), master_rng)
+    } else if let Some(path) = m.get_one::<String>("input") {
+        let points = input::load(path).unwrap_or_else(|e| panic!("{}", e));
+        let settings = Settings {
+            show_exclusion: true,
+            ..settings
+        };
+        let mut image =
+            ImageBuffer::from_pixel(settings.width, settings.height, settings.background);
+        draw(&mut image, &settings, &points, master_rng);
+        let pairs = input::violations(&points, settings.radius);
+        input::draw_violations(&mut image, &points, &pairs);
+        println!("{} pairs closer than {}", pairs.len(), 2. * settings.radius);
+        for &(a, b) in &pairs {
+            let (a, b) = (points[a], points[b]);
+            println!("({}, {}) ({}, {})", a.x, a.y, b.x, b.y);
+        }
+        if m.get_flag("stats") {
+            write_stats(Path::new(name), &settings, &points);
+        }
+        image
+    } else if m.get_flag("stats") {
+        let mut image =
+            ImageBuffer::from_pixel(settings.width, settings.height, settings.background);
+        if settings.debug_grid {
+            draw_grid(&mut image, &occupancy(&settings, master_rng.clone()));
+        }
+        let points = generate(&settings, master_rng.clone());
+        draw(&mut image, &settings, &points, master_rng);
+        write_stats(Path::new(name), &settings, &points);
+        image
+    } else {
+        render(&settings, master_rng)
+    };
+    save(image, name);
+}
+
+/// Stipples the photo given to the stipple subcommand.
+fn stipple(m: &ArgMatches) {
+    let (min_radius, radius) = m
+        .get_one::<(f32, f32)>("radius")
+        .copied()
+        .unwrap_or((0.005, 0.02));
+    let settings = Settings {
+        width: m
+            .get_one::<String>("width")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1024),
+        height: m
+            .get_one::<String>("height")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(1024),
+        radius,
+        algo: m
+            .get_one::<String>("algo")
+            .and_then(|s| Algo::from_str(s).ok())
+            .unwrap_or(Algo::Ebeida),
+        style: m
+            .get_one::<String>("style")
+            .and_then(|s| Style::from_str(s).ok())
+            .unwrap_or(Style::Dot),
+        show_exclusion: false,
+        debug_grid: false,
+        show_tiling: false,
+        labels: None,
+        color: Some(
+            m.get_one::<Rgba<u8>>("point-color")
+                .copied()
+                .unwrap_or(Rgba([0, 0, 0, 255])),
+        ),
+        palette: None,
+        background: m
+            .get_one::<Rgba<u8>>("background")
+            .copied()
+            .unwrap_or(Rgba([255, 255, 255, 255])),
+    };
+    let photo = m.get_one::<String>("PHOTO").expect("PHOTO argument is required");
+    let name = m
+        .get_one::<String>("OUTPUT")
+        .expect("OUTPUT argument is required");
     let master_rng = m
         .get_one::<String>("SEED")
-        .map(|s| {
-            let mut fnv = FnvHasher::with_key(0);
-            for b in s.bytes() {
-                fnv.write_u8(b);
+        .map(|s| seeded_rng(s))
+        .unwrap_or_else(|| SmallRng::from_rng(&mut rng()));
+    let brightness = stipple::load(photo).unwrap_or_else(|e| panic!("{}", e));
+    let tone = m
+        .get_one::<ToneMapping<f32>>("tone")
+        .copied()
+        .unwrap_or(ToneMapping::Equalized);
+    let points = stipple::generate(&settings, &brightness, min_radius, tone, master_rng.clone());
+    println!("{} dots", points.len());
+    let mut image = ImageBuffer::from_pixel(settings.width, settings.height, settings.background);
+    draw(&mut image, &settings, &points, master_rng);
+    save(image, name);
+}
+
+/// Saves the image, without alpha if it's opaque so that formats without it keep working.
+fn save(image: ImageBuffer<Rgba<u8>, Vec<u8>>, name: &str) {
+    let image = if image.pixels().all(|p| p[3] == 255) {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(image).to_rgb8())
+    } else {
+        DynamicImage::ImageRgba8(image)
+    };
+    image.save(name).expect("Failed to save generated image");
+}
+
+/// Writes nearest neighbour histogram as `<name>.stats.png` and summary as `<name>.stats.txt`.
+fn write_stats(path: &Path, settings: &Settings, points: &[Vector2<f32>]) {
+    let distances = stats::nearest_neighbour_distances(points);
+    let summary = stats::Summary::new(&distances);
+    print!("{}", summary);
+    stats::histogram(&distances, settings.radius, 512, 256)
+        .save(path.with_extension("stats.png"))
+        .expect("Failed to save histogram");
+    fs::write(path.with_extension("stats.txt"), summary.to_string())
+        .expect("Failed to save statistics");
+}
+
+/// Creates random number generator from hash of the seed.
+fn seeded_rng(seed: &str) -> SmallRng {
+    let mut fnv = FnvHasher::with_key(0);
+    for b in seed.bytes() {
+        fnv.write_u8(b);
+    }
+    SmallRng::seed_from_u64(fnv.finish())
+}
+
+/// Renders contact sheet where columns are values of the sweep and rows are different seeds.
+fn render_sweep(
+    settings: &Settings,
+    sweep: Sweep,
+    seeds: usize,
+    master_rng: SmallRng,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let (width, height) = (settings.width, settings.height);
+    let mut sheet = ImageBuffer::from_pixel(
+        width * sweep.steps as u32,
+        height * seeds as u32,
+        settings.background,
+    );
+    let mut seed_rng = master_rng.clone();
+    // First row uses the same seed as rendering without sweep would.
+    let rngs = (0..seeds)
+        .map(|row| {
+            if row == 0 {
+                master_rng.clone()
+            } else {
+                SmallRng::from_rng(&mut seed_rng)
             }
-            SmallRng::seed_from_u64(fnv.finish())
         })
-        .unwrap_or_else(|| SmallRng::from_rng(&mut rng()));
+        .collect::<Vec<_>>();
+    for column in 0..sweep.steps {
+        println!("Column {}: {:?} = {}", column, sweep.param, sweep.value(column));
+    }
+    let images = (0..seeds * sweep.steps)
+        .into_par_iter()
+        .map(|i| {
+            let (row, column) = (i / sweep.steps, i % sweep.steps);
+            render(&settings.with(sweep.param, sweep.value(column)), rngs[row].clone())
+        })
+        .collect::<Vec<_>>();
+    for (i, image) in images.iter().enumerate() {
+        let (row, column) = (i / sweep.steps, i % sweep.steps);
+        imageops::replace(
+            &mut sheet,
+            image,
+            (column as u32 * width) as i64,
+            (row as u32 * height) as i64,
+        );
+    }
+    sheet
+}
 
-    let mut style_rng = master_rng.clone();
+/// Renders independent distributions side by side, each with its own random number generator seeded from the one
+/// given. They are generated with the parallel backend of the library if asked, which gives the same distributions.
+fn render_ensemble(
+    settings: &Settings,
+    count: usize,
+    parallel: bool,
+    master_rng: SmallRng,
+) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let (width, height) = (settings.width, settings.height);
+    let mut sheet = ImageBuffer::from_pixel(width * count as u32, height, settings.background);
+    let builder = Builder::<_, Vector2<f32>>::with_disk_radius(settings.radius, Type::Normal);
+    let sets = match settings.algo {
+        Algo::Ebeida => generate_ensemble(&builder, master_rng.clone(), Ebeida, count, parallel),
+        Algo::Bridson => generate_ensemble(&builder, master_rng.clone(), Bridson, count, parallel),
+        Algo::DartThrowing => {
+            generate_ensemble(&builder, master_rng.clone(), DartThrowing, count, parallel)
+        }
+    };
+    for (i, points) in sets.iter().enumerate() {
+        let mut image = ImageBuffer::from_pixel(width, height, settings.background);
+        draw(&mut image, settings, points, master_rng.clone());
+        imageops::replace(&mut sheet, &image, (i as u32 * width) as i64, 0);
+    }
+    sheet
+}
 
-    let builder = Builder::<_, Vector2<f32>>::with_radius(radius, Type::Normal);
-    let points = if algo == Algo::Ebeida {
-        builder.build(master_rng, Ebeida).generate()
+fn generate_ensemble<A>(
+    builder: &Builder<f32, Vector2<f32>>,
+    rng: SmallRng,
+    algo: A,
+    count: usize,
+    parallel: bool,
+) -> Vec<Vec<Vector2<f32>>>
+where
+    A: Creator<f32, Vector2<f32>> + Send + Sync,
+{
+    let sets = if parallel {
+        builder.generate_ensemble_par(rng, algo, count)
     } else {
-        builder.build(master_rng, Bridson).generate()
+        builder.generate_ensemble(rng, algo, count).collect()
     };
+    sets.into_iter().map(Vec::from).collect()
+}
 
-    let mut ps = points.clone();
+fn render(settings: &Settings, master_rng: SmallRng) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    if settings.show_tiling {
+        return render_tiling(settings, master_rng);
+    }
+    let mut image = ImageBuffer::from_pixel(settings.width, settings.height, settings.background);
+    if settings.debug_grid {
+        draw_grid(&mut image, &occupancy(settings, master_rng.clone()));
+    }
+    let points = generate(settings, master_rng.clone());
+    draw(&mut image, settings, &points, master_rng);
+    image
+}
+
+/// Renders perioditic distribution as 3×3 tiles with their borders drawn, so that samples too close to each other
+/// across the borders show up as overlapping disks.
+fn render_tiling(settings: &Settings, master_rng: SmallRng) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+    let (width, height) = (settings.width, settings.height);
+    let mut tile = ImageBuffer::from_pixel(width, height, settings.background);
+    if settings.debug_grid {
+        draw_grid(&mut tile, &occupancy(settings, master_rng.clone()));
+    }
+    let points = generate(settings, master_rng.clone());
+    // Copies of the points from the neighbouring tiles are drawn first so that disks wrap around the borders.
+    let mut copies = vec![];
+    for x in -1..=1 {
+        for y in -1..=1 {
+            if x != 0 || y != 0 {
+                let offset = Vector2::new(x as f32, y as f32);
+                copies.extend(points.iter().map(|p| p + offset));
+            }
+        }
+    }
+    let unlabelled = Settings {
+        labels: None,
+        ..settings.clone()
+    };
+    draw(&mut tile, &unlabelled, &copies, master_rng.clone());
+    draw(&mut tile, settings, &points, master_rng);
+    let mut sheet = ImageBuffer::from_pixel(3 * width, 3 * height, settings.background);
+    for x in 0..3 {
+        for y in 0..3 {
+            imageops::replace(&mut sheet, &tile, (x * width) as i64, (y * height) as i64);
+        }
+    }
+    let border = Rgba([255, 220, 0, 255]);
+    for i in 1..3 {
+        for y in 0..3 * height {
+            sheet[(i * width, y)] = border;
+        }
+        for x in 0..3 * width {
+            sheet[(x, i * height)] = border;
+        }
+    }
+    sheet
+}
+
+fn generate(settings: &Settings, rng: SmallRng) -> Vec<Vector2<f32>> {
+    let poisson_type = if settings.show_tiling {
+        Type::Perioditic
+    } else {
+        Type::Normal
+    };
+    let builder = Builder::<_, Vector2<f32>>::with_disk_radius(settings.radius, poisson_type);
+    match settings.algo {
+        Algo::Ebeida => builder.build(rng, Ebeida).generate(),
+        Algo::Bridson => builder.build(rng, Bridson).generate(),
+        Algo::DartThrowing => builder.build(rng, DartThrowing).generate(),
+    }
+}
+
+fn occupancy(settings: &Settings, rng: SmallRng) -> OccupancyImage<f32> {
+    let poisson_type = if settings.show_tiling {
+        Type::Perioditic
+    } else {
+        Type::Normal
+    };
+    let builder = Builder::<_, Vector2<f32>>::with_disk_radius(settings.radius, poisson_type);
+    match settings.algo {
+        Algo::Ebeida => builder.build(rng, Ebeida).grid_occupancy_image(),
+        Algo::Bridson => builder.build(rng, Bridson).grid_occupancy_image(),
+        Algo::DartThrowing => builder.build(rng, DartThrowing).grid_occupancy_image(),
+    }
+}
+
+/// Shades the cells of the grid so that empty cells are red and fuller cells are brighter.
+fn draw_grid(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, occupancy: &OccupancyImage<f32>) {
+    let (width, height) = image.dimensions();
+    let max = occupancy.max().max(1);
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let cx = (x as f32 / width as f32 / occupancy.cell()) as usize;
+        let cy = ((height - 1 - y) as f32 / height as f32 / occupancy.cell()) as usize;
+        let count = occupancy.get(
+            cx.min(occupancy.width() - 1),
+            cy.min(occupancy.height() - 1),
+            0,
+        );
+        *pixel = if count == 0 {
+            Rgba([48, 0, 0, 255])
+        } else {
+            let shade = (32 + 96 * count / max) as u8;
+            Rgba([shade, shade, shade, 255])
+        };
+    }
+}
+
+/// Draws the points on top of the image.
+fn draw(
+    image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>,
+    settings: &Settings,
+    points: &[Vector2<f32>],
+    mut style_rng: SmallRng,
+) {
+    let Settings {
+        radius,
+        style,
+        show_exclusion,
+        labels,
+        color,
+        ref palette,
+        ..
+    } = *settings;
+    let (width, height) = image.dimensions();
+
+    let mut ps = points.to_vec();
     ps.shuffle(&mut style_rng);
 
-    let mut image = ImageBuffer::new(width, height);
     for p in points {
         let pp = ps.pop().expect("ps should have same length as points");
-        let col = Rgb(Lab {
-            l: style_rng.random::<f32>() * 80. + 10.,
-            a: pp.x * 256. - 128.,
-            b: pp.y * 256. - 128.,
-        }
-        .to_rgb());
+        let col = match *palette {
+            Some(ref palette) => palette.pick(&mut style_rng),
+            None => {
+                let [r, g, b] = Lab {
+                    l: style_rng.random::<f32>() * 80. + 10.,
+                    a: pp.x * 256. - 128.,
+                    b: pp.y * 256. - 128.,
+                }
+                .to_rgb();
+                Rgba([r, g, b, 255])
+            }
+        };
 
         let x = p.x * width as f32;
         let y = p.y * height as f32;
@@ -189,13 +857,46 @@ fn visualise(m: ArgMatches) {
                 if style == Style::Colorful {
                     image[(xxx, yyy)] = col;
                 } else {
-                    image[(xxx, yyy)] = Rgb([255, 255, 255]);
+                    image[(xxx, yyy)] = color.unwrap_or(Rgba([255, 255, 255, 255]));
                 }
                 if style == Style::Plain && (xx == 0. || yy == 0.) {
-                    image[(xxx, yyy)] = Rgb([255, 0, 0]);
+                    image[(xxx, yyy)] = Rgba([255, 0, 0, 255]);
                 }
             }
         }
     }
-    image.save(name).expect("Failed to save generated image");
+    if show_exclusion {
+        // Drawn after the disks so that outlines stay visible on top of them.
+        let (rx, ry) = (2. * radius * width as f32, 2. * radius * height as f32);
+        for p in points {
+            draw_outline(image, p.x * width as f32, p.y * height as f32, rx, ry);
+        }
+    }
+    if let Some(labels) = labels {
+        label::draw_labels(image, labels, points, radius);
+    }
+}
+
+fn draw_outline(image: &mut ImageBuffer<Rgba<u8>, Vec<u8>>, x: f32, y: f32, rx: f32, ry: f32) {
+    let (width, height) = image.dimensions();
+    // Relative thickness of the outline so that it's roughly one pixel wide.
+    let thickness = 1. / rx.min(ry).max(1.);
+    for xx in -rx as i32..=rx as i32 {
+        for yy in -ry as i32..=ry as i32 {
+            let xx = xx as f32;
+            let yy = yy as f32;
+            let xxx = (x + xx) as i32;
+            let yyy = height as i32 - (y + yy) as i32;
+            if xxx < 0 || xxx >= width as i32 || yyy < 0 || yyy >= height as i32 {
+                // Outside of the picture
+                continue;
+            }
+            let dist = (xx * xx / (rx * rx) + yy * yy / (ry * ry)).sqrt();
+            if dist > 1. || dist < 1. - thickness {
+                // Not on the outline
+                continue;
+            }
+            image[(xxx as u32, yyy as u32)] = Rgba([0, 160, 255, 255]);
+        }
+    }
 }
@@ -0,0 +1,173 @@
+//! Scenes of multiple distributions described in TOML files.
+//!
+//! ```toml
+//! width = 1024
+//! height = 1024
+//! background = [10, 10, 30]
+//!
+//! [[layer]]
+//! radius = 0.005
+//! style = "dot"
+//! color = [200, 200, 255]
+//! seed = "stars"
+//!
+//! [[layer]]
+//! radius = 0.03
+//! algorithm = "bridson"
+//! style = "colorful"
+//! masks = [{ shape = "ball", center = [0.5, 0.5], radius = 0.3 }]
+//! ```
+
+use crate::label::Labels;
+use crate::{draw, generate, seeded_rng, Algo, Settings, Style};
+
+use poisson::shape::{Ball, Cuboid, Shape};
+
+use nalgebra::Vector2;
+
+use image::{ImageBuffer, Rgba};
+
+use rand::rngs::SmallRng;
+use rand::SeedableRng;
+
+use rayon::prelude::*;
+
+use serde::Deserialize;
+
+use std::fs;
+
+/// Layers composited into one image in the order they are listed.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Scene {
+    width: Option<u32>,
+    height: Option<u32>,
+    background: Option<[u8; 3]>,
+    #[serde(default, rename = "layer")]
+    layers: Vec<Layer>,
+}
+
+/// Single distribution of the scene.
+/// Settings that are left out are taken from the command line.
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Layer {
+    radius: Option<f32>,
+    algorithm: Option<Algo>,
+    style: Option<Style>,
+    color: Option<[u8; 3]>,
+    seed: Option<String>,
+    #[serde(default)]
+    show_exclusion: bool,
+    labels: Option<Labels>,
+    #[serde(default)]
+    masks: Vec<Mask>,
+}
+
+/// Region where the samples of a layer are kept.
+#[derive(Deserialize, Debug)]
+#[serde(tag = "shape", rename_all = "lowercase", deny_unknown_fields)]
+pub enum Mask {
+    Ball {
+        center: [f32; 2],
+        radius: f32,
+        #[serde(default)]
+        invert: bool,
+    },
+    Box {
+        min: [f32; 2],
+        max: [f32; 2],
+        #[serde(default)]
+        invert: bool,
+    },
+}
+
+impl Mask {
+    fn contains(&self, v: &Vector2<f32>) -> bool {
+        match *self {
+            Mask::Ball {
+                center,
+                radius,
+                invert,
+            } => {
+                let ball = Ball {
+                    center: Vector2::from(center),
+                    radius,
+                };
+                ball.contains(v) != invert
+            }
+            Mask::Box { min, max, invert } => {
+                let cuboid = Cuboid {
+                    min: Vector2::from(min),
+                    max: Vector2::from(max),
+                };
+                cuboid.contains(v) != invert
+            }
+        }
+    }
+}
+
+impl Scene {
+    pub fn load(path: &str) -> Result<Scene, String> {
+        let text =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+        toml::from_str(&text).map_err(|e| format!("Invalid scene {}: {}", path, e))
+    }
+
+    /// Renders the layers on top of each other.
+    /// Layers without a seed get their own generator derived from the master generator.
+    pub fn render(
+        &self,
+        defaults: &Settings,
+        mut master_rng: SmallRng,
+    ) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+        let width = self.width.unwrap_or(defaults.width);
+        let height = self.height.unwrap_or(defaults.height);
+        let background = self
+            .background
+            .map_or(defaults.background, |[r, g, b]| Rgba([r, g, b, 255]));
+        let mut image = ImageBuffer::from_pixel(width, height, background);
+        // Generators are derived in order before generating the layers in parallel,
+        // so the image is the same with any amount of threads.
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let settings = Settings {
+                    width,
+                    height,
+                    radius: layer.radius.unwrap_or(defaults.radius),
+                    algo: layer.algorithm.unwrap_or(defaults.algo),
+                    style: layer.style.unwrap_or(defaults.style),
+                    show_exclusion: layer.show_exclusion || defaults.show_exclusion,
+                    debug_grid: false,
+                    show_tiling: false,
+                    labels: layer.labels.or(defaults.labels),
+                    color: layer
+                        .color
+                        .map(|[r, g, b]| Rgba([r, g, b, 255]))
+                        .or(defaults.color),
+                    palette: defaults.palette.clone(),
+                    background,
+                };
+                let rng = match layer.seed {
+                    Some(ref seed) => seeded_rng(seed),
+                    None => SmallRng::from_rng(&mut master_rng),
+                };
+                (layer, settings, rng)
+            })
+            .collect::<Vec<_>>();
+        let points = layers
+            .par_iter()
+            .map(|(layer, settings, rng)| {
+                let mut points = generate(settings, rng.clone());
+                points.retain(|p| layer.masks.iter().all(|m| m.contains(p)));
+                points
+            })
+            .collect::<Vec<_>>();
+        for ((_, settings, rng), points) in layers.into_iter().zip(points) {
+            draw(&mut image, &settings, &points, rng);
+        }
+        image
+    }
+}
@@ -0,0 +1,96 @@
+//! Nearest neighbour statistics of generated distributions.
+
+use nalgebra::Vector2;
+
+use image::{ImageBuffer, Rgb};
+
+use rayon::prelude::*;
+
+use std::fmt;
+
+/// Summary of the distances from each point to its nearest neighbour.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Summary {
+    count: usize,
+    min: f32,
+    mean: f32,
+    max: f32,
+}
+
+impl Summary {
+    pub fn new(distances: &[f32]) -> Summary {
+        Summary {
+            count: distances.len(),
+            min: distances.iter().cloned().fold(f32::INFINITY, f32::min),
+            mean: distances.iter().sum::<f32>() / distances.len().max(1) as f32,
+            max: distances.iter().cloned().fold(0., f32::max),
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "points: {}", self.count)?;
+        writeln!(f, "min nearest neighbour distance: {}", self.min)?;
+        writeln!(f, "mean nearest neighbour distance: {}", self.mean)?;
+        writeln!(f, "max nearest neighbour distance: {}", self.max)
+    }
+}
+
+/// Returns the distance from each point to its nearest neighbour.
+pub fn nearest_neighbour_distances(points: &[Vector2<f32>]) -> Vec<f32> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.x.partial_cmp(&b.x).expect("Points should not be NaN."));
+    (0..sorted.len())
+        .into_par_iter()
+        .map(|i| {
+            let p = sorted[i];
+            let mut best = f32::INFINITY;
+            // Points are sorted along x so the search can stop once they are farther along it than the best so far.
+            for q in sorted[i + 1..].iter() {
+                if q.x - p.x >= best {
+                    break;
+                }
+                best = best.min((q - p).norm());
+            }
+            for q in sorted[..i].iter().rev() {
+                if p.x - q.x >= best {
+                    break;
+                }
+                best = best.min((q - p).norm());
+            }
+            best
+        })
+        .collect()
+}
+
+/// Plots histogram of the distances from zero to the largest one.
+/// The minimum distance allowed by the radius is marked with a red line.
+pub fn histogram(
+    distances: &[f32],
+    radius: f32,
+    width: u32,
+    height: u32,
+) -> ImageBuffer<Rgb<u8>, Vec<u8>> {
+    let mut image = ImageBuffer::from_pixel(width, height, Rgb([255, 255, 255]));
+    let bins = (width / 4).max(1) as usize;
+    let max = distances.iter().cloned().fold(2. * radius, f32::max);
+    let mut counts = vec![0usize; bins];
+    for &d in distances {
+        let bin = ((d / max * bins as f32) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    let highest = counts.iter().cloned().max().unwrap_or(0).max(1);
+    for x in 0..width {
+        let bin = (x as usize * bins / width as usize).min(bins - 1);
+        let bar = (counts[bin] * height as usize / highest) as u32;
+        for y in height - bar..height {
+            image[(x, y)] = Rgb([60, 60, 60]);
+        }
+    }
+    let diameter = ((2. * radius / max * width as f32) as u32).min(width - 1);
+    for y in 0..height {
+        image[(diameter, y)] = Rgb([255, 0, 0]);
+    }
+    image
+}
@@ -0,0 +1,84 @@
+//! Stippling of photos with distributions that are denser where the photo is darker.
+
+use crate::{Algo, Settings};
+
+use poisson::algorithm::{Bridson, DartThrowing, Ebeida};
+use poisson::field::ToneMapping;
+use poisson::{Builder, Type};
+
+use nalgebra::Vector2;
+
+use image::imageops::{self, FilterType};
+
+use rand::rngs::SmallRng;
+
+/// Amount of cells along each axis of the density raster the photo is scaled to.
+const RESOLUTION: u32 = 256;
+
+/// Loads the brightness of the photo in [0, 1] scaled to the density raster.
+/// The bottom row of the photo comes first as the points have y growing upwards.
+pub fn load(path: &str) -> Result<Vec<f32>, String> {
+    let photo = image::open(path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?
+        .to_luma8();
+    let raster = imageops::resize(&photo, RESOLUTION, RESOLUTION, FilterType::Triangle);
+    Ok((0..RESOLUTION)
+        .rev()
+        .flat_map(|y| (0..RESOLUTION).map(move |x| (x, y)))
+        .map(|(x, y)| raster[(x, y)][0] as f32 / 255.)
+        .collect())
+}
+
+/// Generates points whose radius grows from `min_radius` where the photo is black
+/// to the radius of the settings where it's white.
+pub fn generate(
+    settings: &Settings,
+    brightness: &[f32],
+    min_radius: f32,
+    tone: ToneMapping<f32>,
+    rng: SmallRng,
+) -> Vec<Vector2<f32>> {
+    assert!(
+        0. < min_radius && min_radius <= settings.radius,
+        "Minimum radius should be in (0, {}]: {}",
+        settings.radius,
+        min_radius
+    );
+    let resolution = [RESOLUTION as usize; 2];
+    let builder = Builder::<_, Vector2<f32>>::with_disk_radius(settings.radius, Type::Normal)
+        .with_density_raster(brightness, &resolution, min_radius, settings.radius, tone);
+    match settings.algo {
+        Algo::Ebeida => builder.build(rng, Ebeida).generate(),
+        Algo::Bridson => builder.build(rng, Bridson).generate(),
+        Algo::DartThrowing => builder.build(rng, DartThrowing).generate(),
+    }
+}
+
+/// Parses radii of the disks where the photo is black and where it's white, of form `min..max`.
+pub fn parse_radii(s: &str) -> Result<(f32, f32), String> {
+    let invalid = || format!("Invalid radii, expected min..max with 0 < min <= max: {}", s);
+    let (min, max) = s.split_once("..").ok_or_else(invalid)?;
+    let min = min.parse::<f32>().map_err(|_| invalid())?;
+    let max = max.parse::<f32>().map_err(|_| invalid())?;
+    if 0. < min && min <= max {
+        Ok((min, max))
+    } else {
+        Err(invalid())
+    }
+}
+
+/// Parses mapping of the brightness to tones that is `linear`, `equalized` or `gamma:<power>`.
+pub fn parse_tone(s: &str) -> Result<ToneMapping<f32>, String> {
+    let invalid = || format!("Invalid tone, expected linear, equalized or gamma:<power>: {}", s);
+    match s.to_lowercase().as_str() {
+        "linear" => Ok(ToneMapping::Linear),
+        "equalized" => Ok(ToneMapping::Equalized),
+        tone => {
+            let power = tone.strip_prefix("gamma:").ok_or_else(invalid)?;
+            match power.parse::<f32>() {
+                Ok(power) if power > 0. => Ok(ToneMapping::Gamma(power)),
+                _ => Err(invalid()),
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::env;
+use std::fs;
+use std::process::{self, Command};
+
+/// Renders an ensemble with the arguments and returns the bytes of the image.
+fn render(name: &str, args: &[&str]) -> Vec<u8> {
+    let file = format!("poisson-visualisation-{}-{}.png", name, process::id());
+    let path = env::temp_dir().join(file);
+    let status = Command::new(env!("CARGO_BIN_EXE_poisson-visualisation"))
+        .arg(&path)
+        .arg("threads")
+        .args(["-w", "64", "-h", "64", "-r", "0.05", "--ensemble", "6"])
+        .args(args)
+        .status()
+        .expect("Failed to run the visualiser");
+    assert!(status.success());
+    let image = fs::read(&path).expect("Failed to read the rendered image");
+    fs::remove_file(&path).expect("Failed to remove the rendered image");
+    image
+}
+
+#[test]
+fn ensemble_is_the_same_with_any_amount_of_threads() {
+    for algo in ["ebeida", "bridson", "dart"] {
+        let single = render(&format!("{}-1", algo), &["-a", algo, "--threads", "1"]);
+        let multi = render(&format!("{}-4", algo), &["-a", algo, "--threads", "4"]);
+        let sequential = render(&format!("{}-0", algo), &["-a", algo]);
+        assert!(single == multi);
+        assert!(single == sequential);
+    }
+}
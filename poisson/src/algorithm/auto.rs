@@ -0,0 +1,109 @@
+use crate::algorithm::{Algorithm, Bridson, Creator, Ebeida};
+use crate::{Builder, Float, GenerationStats, Rejection, Vector};
+
+use rand::distr::StandardUniform;
+use rand::Rng;
+use rand_distr::Distribution;
+
+/// Picks the algorithm from the parameters of the generation so there is no need to benchmark them by hand.
+///
+/// Ebeida's algorithm is the only one that generates maximal distributions, so it's picked when
+/// `Builder::with_maximal` asks for one. Otherwise Bridson's algorithm is picked as it's the fastest one regardless of
+/// the boundaries of the domain, about twice as fast as Ebeida's algorithm in two dimensions and over a hundred times
+/// in four, as the subdivisions of Ebeida's algorithm grow exponentially with the dimension.
+/// Radii large enough that only a handful of samples fit are generated with `Sparse` whatever the algorithm.
+/// The algorithm picked is reported in `GenerationStats::algorithm`.
+#[derive(Debug, Clone, Copy)]
+pub struct Auto;
+
+impl<F, V> Creator<F, V> for Auto
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        if poisson.maximal {
+            Algo::Ebeida(Ebeida::create(poisson))
+        } else {
+            Algo::Bridson(Bridson::create(poisson))
+        }
+    }
+}
+
+/// Implementation for the automatically picked algorithm
+#[derive(Clone)]
+pub enum Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    /// Ebeida's algorithm for maximal distributions.
+    Ebeida(<Ebeida as Creator<F, V>>::Algo),
+    /// Bridson's algorithm for everything else.
+    Bridson(<Bridson as Creator<F, V>>::Algo),
+}
+
+impl<F, V> Algorithm<F, V> for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        match self {
+            Algo::Ebeida(algo) => algo.next(poisson, rng),
+            Algo::Bridson(algo) => algo.next(poisson, rng),
+        }
+    }
+
+    fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        match self {
+            Algo::Ebeida(algo) => algo.size_hint(poisson),
+            Algo::Bridson(algo) => algo.size_hint(poisson),
+        }
+    }
+
+    fn restrict(&mut self, sample: V) {
+        match self {
+            Algo::Ebeida(algo) => algo.restrict(sample),
+            Algo::Bridson(algo) => algo.restrict(sample),
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        match self {
+            Algo::Ebeida(algo) => algo.stays_legal(poisson, sample),
+            Algo::Bridson(algo) => algo.stays_legal(poisson, sample),
+        }
+    }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        match self {
+            Algo::Ebeida(algo) => algo.explain(poisson, sample),
+            Algo::Bridson(algo) => algo.explain(poisson, sample),
+        }
+    }
+
+    fn stats(&self) -> GenerationStats {
+        match self {
+            Algo::Ebeida(algo) => GenerationStats {
+                algorithm: Some("Ebeida"),
+                ..algo.stats()
+            },
+            Algo::Bridson(algo) => GenerationStats {
+                algorithm: Some("Bridson"),
+                ..algo.stats()
+            },
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::algorithm::{Algorithm, Creator};
 use crate::utils::*;
-use crate::{Builder, Float, Vector};
+use crate::{Builder, Buffers, Float, GenerationStats, Rejection, Vector};
 
 use num_traits::Float as NumFloat;
 use num_traits::NumCast;
@@ -11,6 +11,8 @@ use rand_distr::{Distribution, StandardNormal, Uniform};
 
 use sphere::sphere_volume;
 
+use std::collections::VecDeque;
+
 /// Generates approximately uniform non-maximal Poisson-disk distribution with O(n) time and O(n) space complexity relative to the number of samples generated.
 /// Based on Bridson, Robert. "Fast Poisson disk sampling in arbitrary dimensions." SIGGRAPH Sketches. 2007.
 #[derive(Debug, Clone, Copy)]
@@ -26,25 +28,68 @@ where
     type Algo = Algo<F, V>;
 
     fn create(poisson: &Builder<F, V>) -> Self::Algo {
-        Algo {
-            grid: Grid::new(poisson.radius, poisson.poisson_type),
-            active_samples: vec![],
+        let mut algo = Algo {
+            grid: Grid::scaled(poisson.radius, poisson.cell_scale, poisson.domain, poisson.precision)
+                .reusing(poisson.buffers.as_ref()),
+            active_samples: poisson
+                .buffers
+                .as_ref()
+                .map_or_else(Vec::new, Buffers::take_samples)
+                .into(),
             outside: vec![],
             success: 0,
+            stats: GenerationStats::default(),
+            buffers: poisson.buffers.clone(),
+        };
+        for sample in &poisson.start_points {
+            algo.restrict(sample.clone());
         }
+        algo
     }
 }
 
+/// Policy for choosing which active sample the Bridson algorithm expands next.
+///
+/// The policy changes the order in which the distribution grows and so the visual pattern of partial results,
+/// but each policy generates a valid poisson-disk distribution.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub enum ActivePolicy<V> {
+    /// Expands random active sample.
+    #[default]
+    Random,
+    /// Expands the oldest active sample first which makes the distribution grow as a wave front.
+    Fifo,
+    /// Expands the newest active sample first which makes the distribution grow as a winding path.
+    Lifo,
+    /// Expands the active sample nearest to the target first which makes the distribution grow radially around it.
+    Nearest(V),
+}
+
 /// Implementation for the Bridson algorithm
+#[derive(Clone)]
 pub struct Algo<F, V>
 where
     F: Float,
     V: Vector<F>,
 {
     grid: Grid<F, V>,
-    active_samples: Vec<V>,
+    active_samples: VecDeque<V>,
     outside: Vec<V>,
     success: usize,
+    stats: GenerationStats,
+    buffers: Option<Buffers<V>>,
+}
+
+impl<F, V> Drop for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn drop(&mut self) {
+        if let Some(buffers) = self.buffers.take() {
+            buffers.give_samples(std::mem::take(&mut self.active_samples).into());
+        }
+    }
 }
 
 impl<F, V> Algorithm<F, V> for Algo<F, V>
@@ -59,32 +104,64 @@ where
         R: Rng,
     {
         while !self.active_samples.is_empty() {
-            let index = rng.sample(Uniform::new(0, self.active_samples.len()).expect("Active samples should never be empty here"));
+            let index = self.choose_active(poisson, rng);
             let cur = self.active_samples[index].clone();
-            for _ in 0..30 {
-                let min = F::cast(2) * poisson.radius;
-                let max = F::cast(4) * poisson.radius;
+            // With regions the closest possible neighbour is in the region with the smallest radius,
+            // but the annulus has to reach past the exclusion zone of the samples own region.
+            let (closest, own) = match poisson.regions {
+                None => (F::cast(2) * poisson.radius, F::cast(2) * poisson.radius),
+                Some(ref r) => {
+                    let radius = r.radius_at(&cur);
+                    (
+                        r.spacing().distance(radius, r.min_radius()),
+                        r.spacing().distance(radius, radius),
+                    )
+                }
+            };
+            for _ in 0..poisson.attempts {
+                // Anisotropic exclusion zones allow samples closer than the radius in some directions.
+                let min = closest
+                    * poisson
+                        .anisotropy
+                        .as_ref()
+                        .map_or(poisson.min_axis_scale(), |a| a.min_scale());
+                let max = F::cast(2) * own;
                 let sample = cur.clone() + random_point_annulus(rng, min, max);
-                if (0..V::dimension())
-                    .map(|n| sample[n])
-                    .all(|c| F::cast(0) <= c && c < F::cast(1))
-                {
+                if poisson.domain.contains(&sample) {
                     let index = sample_to_index(&sample, self.grid.side());
                     if self.insert_if_valid(poisson, index, sample.clone()) {
                         return Some(sample);
                     }
+                } else {
+                    self.stats.reject();
+                }
+            }
+            match poisson.active_policy {
+                // Order of the rest of the active samples matters only for these policies.
+                ActivePolicy::Fifo | ActivePolicy::Lifo => {
+                    self.active_samples.remove(index);
+                }
+                _ => {
+                    self.active_samples.swap_remove_back(index);
                 }
             }
-            self.active_samples.swap_remove(index);
         }
-        while self.success == 0 {
+        // Restricting samples can leave pockets the distribution never grows into, so random samples are thrown
+        // into them until as many in a row as the rejection limit are rejected.
+        // The first sample is thrown the same way and is given up on too, as blockers can cover the whole domain.
+        let restricted = self.success != self.stats.accepted;
+        let mut rejections = 0;
+        while (self.success == 0 || restricted) && rejections < poisson.rejection_limit {
+            rejections += 1;
             let cell = rng.sample(Uniform::new(0, self.grid.cells()).expect("Grid should have at least one cell"));
-            let index: V = decode(cell, self.grid.side()).expect(
+            let index: V = decode(cell, self.grid.side(), poisson.domain).expect(
                 "Because we are decoding random index within grid \
                  this should work.",
             );
             let sample = choose_random_sample(rng, &self.grid, index.clone(), 0);
-            if self.insert_if_valid(poisson, index, sample.clone()) {
+            if poisson.domain.contains(&sample)
+                && self.insert_if_valid(poisson, index, sample.clone())
+            {
                 return Some(sample);
             }
         }
@@ -110,15 +187,29 @@ where
              castable to usize.",
         );
         lower = lower.saturating_sub(1);
-        (lower, Some(upper))
+        // Cells on the boundary of the shape or the extents of the domain are partly outside of it
+        // and blockers and clearances cover some of the volume so the volume left can't be bounded then.
+        // Neither can it when the cells are scaled as a sample no longer covers exactly its own cell.
+        if poisson.shape.is_some()
+            || poisson.cell_scale != F::cast(1)
+            || !poisson.domain.is_unit()
+            || !poisson.blockers.is_empty()
+            || !poisson.clearances.is_empty()
+        {
+            lower = 0;
+        }
+        // Anisotropic exclusion zones, regions and cells larger than inscribed can fit more than one sample into a cell
+        // so there is no upper bound then.
+        let single = poisson.uniform_exclusion() && poisson.cell_scale <= F::cast(1);
+        (lower, single.then_some(upper))
     }
 
     fn restrict(&mut self, sample: V) {
         self.success += 1;
+        // The distribution grows from the restricting samples like from the start points.
+        self.active_samples.push_back(sample.clone());
         let index = sample_to_index(&sample, self.grid.side());
-        if let Some(g) = self.grid.get_mut(index) {
-            g.push(sample);
-        } else {
+        if let Err(sample) = self.grid.insert(index, sample) {
             self.outside.push(sample);
         }
     }
@@ -127,6 +218,15 @@ where
         let index = sample_to_index(&sample, self.grid.side());
         is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
     }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        let index = sample_to_index(&sample, self.grid.side());
+        explain_disk(&self.grid, poisson, index, sample, &self.outside)
+    }
+
+    fn stats(&self) -> GenerationStats {
+        self.stats
+    }
 }
 
 impl<F, V> Algo<F, V>
@@ -134,6 +234,29 @@ where
     F: Float,
     V: Vector<F>,
 {
+    fn choose_active<R>(&self, poisson: &Builder<F, V>, rng: &mut R) -> usize
+    where
+        R: Rng,
+    {
+        let len = self.active_samples.len();
+        match poisson.active_policy {
+            ActivePolicy::Random => {
+                rng.sample(Uniform::new(0, len).expect("Active samples should never be empty here"))
+            }
+            ActivePolicy::Fifo => 0,
+            ActivePolicy::Lifo => len - 1,
+            ActivePolicy::Nearest(ref target) => self
+                .active_samples
+                .iter()
+                .map(|v| sqdist(v.clone(), target.clone(), poisson.domain))
+                .enumerate()
+                .fold((0, NumFloat::max_value()), |(i, a), (j, b)| {
+                    if b < a { (j, b) } else { (i, a) }
+                })
+                .0,
+        }
+    }
+
     fn insert_if_valid(&mut self, poisson: &mut Builder<F, V>, index: V, sample: V) -> bool {
         if is_disk_free(
             &self.grid,
@@ -143,20 +266,21 @@ where
             sample.clone(),
             &self.outside,
         ) {
-            self.active_samples.push(sample.clone());
-            self.grid
-                .get_mut(index)
-                .expect("Because the sample is [0, 1) indexing it should work.")
-                .push(sample);
+            self.active_samples.push_back(sample.clone());
+            self.grid.insert(index, sample).unwrap_or_else(|_| {
+                panic!("Because the sample is [0, 1) indexing it should work.")
+            });
             self.success += 1;
+            self.stats.accept();
             true
         } else {
+            self.stats.reject();
             false
         }
     }
 }
 
-fn random_point_annulus<F, V, R>(rand: &mut R, min: F, max: F) -> V
+pub(crate) fn random_point_annulus<F, V, R>(rand: &mut R, min: F, max: F) -> V
 where
     F: Float,
     V: Vector<F>,
@@ -0,0 +1,125 @@
+use crate::algorithm::{Algorithm, Creator};
+use crate::utils::*;
+use crate::{Builder, Float, GenerationStats, Rejection, Vector};
+
+use rand::distr::StandardUniform;
+use rand::Rng;
+use rand_distr::Distribution;
+
+/// Generates approximately uniform non-maximal Poisson-disk distribution by throwing uniformly random samples
+/// and rejecting the ones that conflict with earlier samples until too many consecutive samples are rejected.
+/// The amount of consecutive rejections is set with `Builder::with_rejection_limit`.
+/// Slow, but trivially correct which makes it useful as a baseline for the other algorithms.
+#[derive(Debug, Clone, Copy)]
+pub struct DartThrowing;
+
+impl<F, V> Creator<F, V> for DartThrowing
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        let mut algo = Algo {
+            grid: Grid::scaled(poisson.radius, poisson.cell_scale, poisson.domain, poisson.precision)
+                .reusing(poisson.buffers.as_ref()),
+            outside: vec![],
+            rejections: 0,
+            success: 0,
+            stats: GenerationStats::default(),
+        };
+        for sample in &poisson.start_points {
+            algo.restrict(sample.clone());
+        }
+        algo
+    }
+}
+
+/// Implementation for the dart throwing algorithm
+#[derive(Clone)]
+pub struct Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    grid: Grid<F, V>,
+    outside: Vec<V>,
+    rejections: usize,
+    success: usize,
+    stats: GenerationStats,
+}
+
+impl<F, V> Algorithm<F, V> for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        while self.rejections < poisson.rejection_limit {
+            let mut sample: V = rng.sample(StandardUniform);
+            for n in 0..V::dimension() {
+                sample[n] *= poisson.domain.extent_of::<F>(n);
+            }
+            let index = sample_to_index(&sample, self.grid.side());
+            if is_disk_free(
+                &self.grid,
+                poisson,
+                index.clone(),
+                0,
+                sample.clone(),
+                &self.outside,
+            ) {
+                self.grid.insert(index, sample.clone()).unwrap_or_else(|_| {
+                    panic!("Because the sample is [0, 1) indexing it should work.")
+                });
+                self.rejections = 0;
+                self.success += 1;
+                self.stats.accept();
+                return Some(sample);
+            }
+            self.rejections += 1;
+            self.stats.reject();
+        }
+        None
+    }
+
+    fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        // The next throw can always be the one that hits the rejection limit so there is no lower bound.
+        // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
+        // Anisotropic exclusion zones, regions and cells larger than inscribed can fit more than one sample into a cell
+        // so there is no upper bound then.
+        let upper = self.grid.cells().saturating_sub(self.success);
+        let single = poisson.uniform_exclusion() && poisson.cell_scale <= F::cast(1);
+        (0, single.then_some(upper))
+    }
+
+    fn restrict(&mut self, sample: V) {
+        self.success += 1;
+        let index = sample_to_index(&sample, self.grid.side());
+        if let Err(sample) = self.grid.insert(index, sample) {
+            self.outside.push(sample);
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        let index = sample_to_index(&sample, self.grid.side());
+        is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
+    }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        let index = sample_to_index(&sample, self.grid.side());
+        explain_disk(&self.grid, poisson, index, sample, &self.outside)
+    }
+
+    fn stats(&self) -> GenerationStats {
+        self.stats
+    }
+}
@@ -1,6 +1,6 @@
 use crate::algorithm::{Algorithm, Creator};
 use crate::utils::*;
-use crate::{Builder, Float, Vector};
+use crate::{Builder, Buffers, Float, GenerationStats, Rejection, Vector};
 
 use num_traits::Float as NumFloat;
 
@@ -25,38 +25,34 @@ where
     type Algo = Algo<F, V>;
 
     fn create(poisson: &Builder<F, V>) -> Self::Algo {
-        let dim = V::dimension();
-        let grid = Grid::new(poisson.radius, poisson.poisson_type);
-        let mut indices = Vec::with_capacity(grid.cells() * dim);
-        let choices = (0..grid.side()).collect::<Vec<_>>();
-        indices.extend(each_combination(&choices));
-        let a = match dim {
-            2 => 0.3,
-            3 => 0.3,
-            4 => 0.6,
-            5 => 10.,
-            6 => 700.,
-            // TODO: Figure out what are optimal values beyond 6 dimensions
-            _ => 700. + 100. * dim as f64,
-        };
-        Algo {
-            a,
-            grid,
-            throws: (a * indices.len() as f64).ceil() as usize,
-            range: Uniform::new(0, indices.len()).expect("Indices should not be empty at initialization"),
-            indices,
-            level: 0,
-            success: 0,
-            outside: vec![],
-            mantissa_digits: {
-                let (mantissa, _, _) = <F as NumFloat>::max_value().integer_decode();
-                mantissa.count_ones() as usize
-            },
-        }
+        Algo::new(poisson)
     }
 }
 
-/// Implementation for the Ebeida algorithm
+/// Implementation for the Ebeida algorithm.
+///
+/// Besides generating through `Algorithm::next`, the phases of the algorithm are exposed for measuring and
+/// modifying them individually: `new` initializes the grid, `throw` throws darts into the active cells of
+/// the current level and `subdivide` splits the cells left uncovered into the next level.
+///
+/// ````rust
+/// # use poisson::algorithm::EbeidaAlgo;
+/// # use poisson::{Builder, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// let poisson = Builder::<_, na::Vector2<f64>>::with_disk_radius(0.02, Type::Normal);
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// let mut algo = EbeidaAlgo::new(&poisson);
+/// let mut samples = vec![];
+/// // Stops after two levels of subdivision, leaving the distribution almost but not quite maximal.
+/// while algo.level() < 2 {
+///     samples.extend(std::iter::from_fn(|| algo.throw(&poisson, &mut rng)));
+///     algo.subdivide(&poisson);
+/// }
+/// assert!(!samples.is_empty());
+/// assert!(algo.active_cells() > 0);
+/// ````
+#[derive(Clone)]
 pub struct Algo<F, V>
 where
     F: Float,
@@ -68,9 +64,25 @@ where
     range: Uniform<usize>,
     throws: usize,
     success: usize,
+    stats: GenerationStats,
     outside: Vec<V>,
     mantissa_digits: usize,
+    rejections: usize,
+    deferred: Vec<(V, usize)>,
     a: f64,
+    buffers: Option<Buffers<V>>,
+}
+
+impl<F, V> Drop for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn drop(&mut self) {
+        if let Some(buffers) = self.buffers.take() {
+            buffers.give_samples(std::mem::take(&mut self.indices));
+        }
+    }
 }
 
 impl<F, V> Algorithm<F, V> for Algo<F, V>
@@ -85,70 +97,44 @@ where
         R: Rng,
     {
         if self.indices.is_empty() {
-            return None;
+            return self.throw_deferred(poisson, rng);
         }
-        while self.level < self.mantissa_digits {
-            while self.throws > 0 {
-                self.throws -= 1;
-                let index = rng.sample(self.range);
-                let cur = self.indices[index].clone();
-                let parent = get_parent(cur.clone(), self.level);
-                if !self
-                    .grid
-                    .get(parent.clone())
-                    .expect("Indexing base grid by valid parent failed.")
-                    .is_empty()
-                {
-                    self.indices.swap_remove(index);
-                    if self.indices.is_empty() {
-                        return None;
-                    }
-                    self.range = Uniform::new(0, self.indices.len()).expect("Indices should not be empty after removal");
-                } else {
-                    let sample = choose_random_sample(rng, &self.grid, cur.clone(), self.level);
-                    if is_disk_free(
-                        &self.grid,
-                        poisson,
-                        cur.clone(),
-                        self.level,
-                        sample.clone(),
-                        &self.outside,
-                    ) {
-                        self.grid
-                            .get_mut(parent)
-                            .expect("Indexing base grid by already indexed valid parent failed.")
-                            .push(sample.clone());
-                        self.indices.swap_remove(index);
-                        if !self.indices.is_empty() {
-                            self.range = Uniform::new(0, self.indices.len()).expect("Indices verified to be non-empty");
-                        }
-                        self.success += 1;
-                        return Some(sample);
-                    }
-                }
+        let deepest = poisson
+            .max_depth
+            .map_or(self.mantissa_digits, |depth| depth.min(self.mantissa_digits));
+        while self.level < deepest {
+            if let Some(sample) = self.throw(poisson, rng) {
+                return Some(sample);
+            }
+            if self.indices.is_empty() {
+                return self.throw_deferred(poisson, rng);
             }
             self.subdivide(poisson);
             if self.indices.is_empty() {
-                return None;
+                return self.throw_deferred(poisson, rng);
             }
-            self.range = Uniform::new(0, self.indices.len()).expect("Indices should not be empty at level advancement");
-            self.throws = (self.a * self.indices.len() as f64).ceil() as usize;
-            self.level += 1;
+        }
+        if self.level < self.mantissa_digits {
+            return self.resolve(poisson, rng);
         }
         let index = rng.sample(self.range);
         let cur = self.indices.swap_remove(index);
         let side = 2usize.pow(self.level as u32);
         let sample = index_to_sample(&cur, side);
-        if is_disk_free(
-            &self.grid,
-            poisson,
-            cur.clone(),
-            self.level,
-            sample.clone(),
-            &self.outside,
-        ) {
+        if poisson.domain.contains(&sample)
+            && is_disk_free(
+                &self.grid,
+                poisson,
+                cur.clone(),
+                self.level,
+                sample.clone(),
+                &self.outside,
+            )
+        {
+            self.stats.accept();
             Some(sample)
         } else {
+            self.stats.reject();
             None
         }
     }
@@ -167,17 +153,26 @@ where
              castable to usize.",
         );
         lower = lower.saturating_sub(1);
+        // Cells on the boundary of the shape or the extents of the domain are partly outside of it
+        // and blockers and clearances cover some of the volume so the volume left can't be bounded then.
+        if poisson.shape.is_some()
+            || !poisson.domain.is_unit()
+            || !poisson.blockers.is_empty()
+            || !poisson.clearances.is_empty()
+        {
+            lower = 0;
+        }
         // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
-        let upper = self.grid.cells() - self.success;
-        (lower, Some(upper))
+        // With regions or radii for each axis there can be more than one sample in a cell
+        // so there is no upper bound then.
+        let upper = self.grid.cells().saturating_sub(self.success);
+        (lower, (!poisson.shared_cells()).then_some(upper))
     }
 
     fn restrict(&mut self, sample: V) {
         self.success += 1;
         let index = sample_to_index(&sample, self.grid.side());
-        if let Some(g) = self.grid.get_mut(index) {
-            g.push(sample);
-        } else {
+        if let Err(sample) = self.grid.insert(index, sample) {
             self.outside.push(sample);
         }
     }
@@ -186,31 +181,268 @@ where
         let index = sample_to_index(&sample, self.grid.side());
         is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
     }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        let index = sample_to_index(&sample, self.grid.side());
+        explain_disk(&self.grid, poisson, index, sample, &self.outside)
+    }
+
+    fn stats(&self) -> GenerationStats {
+        self.stats
+    }
 }
 
 impl<F, V> Algo<F, V>
 where
     F: Float,
     V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
 {
-    fn subdivide(&mut self, poisson: &Builder<F, V>) {
+    /// Initializes the algorithm with the base grid, leaving out the cells outside the domain or shape,
+    /// and restricts the start points of the builder. The conflicts between start points are not resolved.
+    pub fn new(poisson: &Builder<F, V>) -> Self {
+        let dim = V::dimension();
+        let grid = Grid::new(poisson.radius, poisson.domain, poisson.precision)
+            .reusing(poisson.buffers.as_ref());
+        let mut indices = poisson.buffers.as_ref().map_or_else(Vec::new, Buffers::take_samples);
+        indices.reserve(grid.cells() * dim);
+        let choices = (0..grid.side()).collect::<Vec<_>>();
+        indices.extend(
+            each_combination(&choices)
+                .filter(|i: &V| !poisson.outside(i.clone() * grid.cell(), grid.cell())),
+        );
+        let a = match dim {
+            2 => 0.3,
+            3 => 0.3,
+            4 => 0.6,
+            5 => 10.,
+            6 => 700.,
+            // TODO: Figure out what are optimal values beyond 6 dimensions
+            _ => 700. + 100. * dim as f64,
+        };
+        let mut algo = Algo {
+            a,
+            grid,
+            throws: (a * indices.len() as f64).ceil() as usize,
+            // Shape can leave no cells to throw into, but then the range is never sampled.
+            range: Uniform::new(0, indices.len().max(1))
+                .expect("Range should not be empty at initialization"),
+            indices,
+            level: 0,
+            success: 0,
+            stats: GenerationStats::default(),
+            outside: vec![],
+            mantissa_digits: mantissa_digits::<F>(),
+            rejections: 0,
+            deferred: vec![],
+            buffers: poisson.buffers.clone(),
+        };
+        for sample in &poisson.start_points {
+            algo.restrict(sample.clone());
+        }
+        algo
+    }
+
+    /// Throws darts into random active cells of the current level until one lands on a legal spot and returns it,
+    /// or returns `None` once the throws of the level run out or no active cells are left.
+    /// Cells whose base cell already has a sample are dropped when they are picked.
+    pub fn throw<R>(&mut self, poisson: &Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        while self.throws > 0 && !self.indices.is_empty() {
+            self.throws -= 1;
+            let index = rng.sample(self.range);
+            let cur = self.indices[index].clone();
+            let parent = get_parent(cur.clone(), self.level);
+            // With regions or radii for each axis there can be room for more samples in the base cell.
+            if !poisson.shared_cells()
+                && !self
+                    .grid
+                    .get(parent.clone())
+                    .expect("Indexing base grid by valid parent failed.")
+                    .is_empty()
+            {
+                self.indices.swap_remove(index);
+                if !self.indices.is_empty() {
+                    self.range = Uniform::new(0, self.indices.len())
+                        .expect("Indices should not be empty after removal");
+                }
+            } else {
+                let sample = choose_random_sample(rng, &self.grid, cur.clone(), self.level);
+                if poisson.domain.contains(&sample)
+                    && is_disk_free(
+                        &self.grid,
+                        poisson,
+                        cur.clone(),
+                        self.level,
+                        sample.clone(),
+                        &self.outside,
+                    )
+                {
+                    self.grid
+                        .insert(parent, sample.clone())
+                        .unwrap_or_else(|_| {
+                            panic!("Indexing base grid by already indexed valid parent failed.")
+                        });
+                    if !poisson.shared_cells() {
+                        self.indices.swap_remove(index);
+                        if !self.indices.is_empty() {
+                            self.range = Uniform::new(0, self.indices.len())
+                                .expect("Indices verified to be non-empty");
+                        }
+                    }
+                    self.success += 1;
+                    self.stats.accept();
+                    return Some(sample);
+                }
+                self.stats.reject();
+            }
+        }
+        None
+    }
+
+    /// Throws darts into the active cells left at the deepest level allowed by `Builder::with_max_depth` until
+    /// as many consecutive darts as the rejection limit are rejected, after which the cells are dropped.
+    fn resolve<R>(&mut self, poisson: &Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        while self.rejections < poisson.rejection_limit && !self.indices.is_empty() {
+            self.throws = 1;
+            match self.throw(poisson, rng) {
+                Some(sample) => {
+                    self.rejections = 0;
+                    return Some(sample);
+                }
+                None => self.rejections += 1,
+            }
+        }
+        self.indices.clear();
+        self.throw_deferred(poisson, rng)
+    }
+
+    /// Throws darts into the cells set aside by custom checks rejecting all of their corners once there are no
+    /// active cells left. Each cell gets as many darts as the rejection limit before it is dropped, as the checks
+    /// can still accept candidates inside the cell.
+    fn throw_deferred<R>(&mut self, poisson: &Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        while !self.deferred.is_empty() {
+            let index = rng.random_range(0..self.deferred.len());
+            let (cur, level) = self.deferred.swap_remove(index);
+            let parent = get_parent(cur.clone(), level);
+            for _ in 0..poisson.rejection_limit {
+                if !poisson.shared_cells()
+                    && !self
+                        .grid
+                        .get(parent.clone())
+                        .expect("Indexing base grid by valid parent failed.")
+                        .is_empty()
+                {
+                    break;
+                }
+                let sample = choose_random_sample(rng, &self.grid, cur.clone(), level);
+                if poisson.domain.contains(&sample)
+                    && is_disk_free(
+                        &self.grid,
+                        poisson,
+                        cur.clone(),
+                        level,
+                        sample.clone(),
+                        &self.outside,
+                    )
+                {
+                    self.grid
+                        .insert(parent, sample.clone())
+                        .unwrap_or_else(|_| {
+                            panic!("Indexing base grid by already indexed valid parent failed.")
+                        });
+                    // With regions or radii for each axis there can be room for more samples in the cell.
+                    if poisson.shared_cells() {
+                        self.deferred.push((cur, level));
+                    }
+                    self.success += 1;
+                    self.stats.accept();
+                    return Some(sample);
+                }
+                self.stats.reject();
+            }
+        }
+        None
+    }
+
+    /// Splits each active cell into 2<sup>d</sup> cells of the next level, dropping the ones the samples cover
+    /// and setting aside the ones custom checks reject at every corner, and gives the new level throws in proportion
+    /// to the amount of cells left.
+    /// Panics past the deepest level, where the cells are as small as the precision of the float allows.
+    pub fn subdivide(&mut self, poisson: &Builder<F, V>) {
+        assert!(self.level < self.mantissa_digits, "Cells can't be subdivided further.");
         let choices = &[0, 1];
         let (grid, outside, level) = (&self.grid, &self.outside, self.level);
+        let deferred = &mut self.deferred;
         self.indices.flat_map_inplace(|i| {
             each_combination(choices)
                 .map(move |n: V| n + i.clone() * F::cast(2))
-                .filter(|c| !covered(grid, poisson, outside, c.clone(), level + 1))
+                .filter(|c| {
+                    let cover = cover(grid, poisson, outside, c.clone(), level + 1);
+                    if let Cover::Rejected = cover {
+                        deferred.push((c.clone(), level + 1));
+                    }
+                    matches!(cover, Cover::Partial)
+                })
+                .collect::<Vec<_>>()
         });
+        if !self.indices.is_empty() {
+            self.range = Uniform::new(0, self.indices.len())
+                .expect("Indices should not be empty at level advancement");
+        }
+        self.throws = (self.a * self.indices.len() as f64).ceil() as usize;
+        self.level += 1;
+        self.stats.depth = self.level;
+    }
+
+    /// Returns the level of subdivision of the active cells, which is 0 for the base grid.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Returns the deepest level of subdivision, where the cells are as small as the precision of the float allows.
+    /// At the deepest level the active cells are checked one by one instead of throwing darts into them.
+    pub fn max_level(&self) -> usize {
+        self.mantissa_digits
+    }
+
+    /// Returns the amount of cells that are still active at the current level.
+    pub fn active_cells(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns the amount of throws left for the current level.
+    pub fn throws_left(&self) -> usize {
+        self.throws
     }
 }
 
-fn covered<F, V>(
+/// How much of a cell is known to be illegal from its corners.
+enum Cover {
+    /// Samples, obstacles and custom checks that reject boxes leave no room in the cell.
+    Whole,
+    /// Custom checks reject every corner that is otherwise free, but may still accept candidates inside the cell.
+    Rejected,
+    /// Some of the corners are legal.
+    Partial,
+}
+
+fn cover<F, V>(
     grid: &Grid<F, V>,
     poisson: &Builder<F, V>,
     outside: &[V],
     index: V,
     level: usize,
-) -> bool
+) -> Cover
 where
     F: Float,
     V: Vector<F>,
@@ -218,15 +450,41 @@ where
     // TODO: This does 4^d checking of points even though it could be done 3^d
     let side = 2usize.pow(level as u32);
     let spacing = grid.cell() / F::cast(side);
-    let sqradius = NumFloat::powi(F::cast(2) * poisson.radius, 2);
+    if poisson.outside(index.clone() * spacing, spacing) {
+        return Cover::Whole;
+    }
     let parent = get_parent(index.clone(), level);
-    each_combination(&[0, 1])
-        .map(|t| (index.clone() + t) * spacing)
-        .all(|t| {
-            each_combination(&[-2, -1, 0, 1, 2])
-                .filter_map(|t| grid.get(parent.clone() + t))
-                .flatten()
-                .any(|v| sqdist(v.clone(), t.clone(), poisson.poisson_type) < sqradius)
-                || !is_valid(poisson, outside, t)
-        })
+    // Cells whose base cell already has a sample are never thrown into again unless there are regions
+    // or radii for each axis. Without anisotropy that sample covers the whole base cell anyway.
+    if !poisson.shared_cells()
+        && !grid
+            .get(parent.clone())
+            .expect("Indexing base grid by valid parent failed.")
+            .is_empty()
+    {
+        return Cover::Whole;
+    }
+    // Neighbours are gathered once instead of walking the grid again for each corner.
+    let neighbours = grid.neighbours(parent);
+    let mut whole = true;
+    for t in each_combination(&[0, 1]).map(|t: V| (index.clone() + t) * spacing) {
+        if neighbours.iter().any(|v| conflicts(poisson, v, &t))
+            || poisson.blocking(&t).is_some()
+            || !is_valid(poisson, outside, t.clone())
+        {
+            continue;
+        }
+        // Checks whose rejected candidates don't form convex regions can accept candidates between the corners.
+        let mut failed = poisson
+            .legality_checks()
+            .filter(|c| !c.is_legal(&t, &neighbours))
+            .peekable();
+        if failed.peek().is_none() {
+            return Cover::Partial;
+        }
+        if !failed.any(|c| c.rejects_boxes()) {
+            whole = false;
+        }
+    }
+    if whole { Cover::Whole } else { Cover::Rejected }
 }
@@ -0,0 +1,184 @@
+use crate::algorithm::{Algorithm, Creator};
+use crate::utils::*;
+use crate::{Builder, Float, GenerationStats, Rejection, SeedTree, Vector};
+
+use num_traits::Float as NumFloat;
+
+use rand::Rng;
+
+/// Amount of candidates thrown into each cell.
+const ROUNDS: usize = 30;
+
+/// Generates non-maximal Poisson-disk distribution where each candidate is a pure function of a seed and its grid cell
+/// instead of the position in a random number stream.
+///
+/// The candidates are thrown in rounds, each round visiting the cells in a fixed order of phases where cells of
+/// the same phase are too far apart to affect each other. The sample of a cell therefore depends only on the seed and
+/// the samples near it, so restricting the distribution or changing it in one place leaves distant regions intact,
+/// which keeps procedurally generated worlds stable under edits.
+/// The seed is the first value drawn from the random number generator and the candidates are derived from it with
+/// `SeedTree` so they are the same on every platform.
+#[derive(Debug, Clone, Copy)]
+pub struct Hashed;
+
+impl<F, V> Creator<F, V> for Hashed
+where
+    F: Float,
+    V: Vector<F>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        let dim = V::dimension();
+        let grid = Grid::new(poisson.radius, poisson.domain, poisson.precision)
+            .reusing(poisson.buffers.as_ref());
+        // Cells this many cells apart along an axis are farther than the diameter from each other.
+        let period = NumFloat::ceil(NumFloat::sqrt(dim as f64)) as usize + 1;
+        let mut phases = vec![vec![]; period.pow(dim as u32)];
+        for cell in 0..grid.cells() {
+            let index: V = decode(cell, grid.side(), poisson.domain)
+                .expect("Cell should be inside of the grid.");
+            if poisson.outside(index.clone() * grid.cell(), grid.cell()) {
+                continue;
+            }
+            let phase = (0..dim).rev().fold(0, |phase, n| {
+                let i = index[n]
+                    .to_usize()
+                    .expect("Index should be castable to usize.");
+                phase * period + i % period
+            });
+            phases[phase].push(cell);
+        }
+        let mut algo = Algo {
+            grid,
+            phases,
+            outside: vec![],
+            seed: None,
+            round: 0,
+            phase: 0,
+            position: 0,
+            success: 0,
+            stats: GenerationStats::default(),
+        };
+        for sample in &poisson.start_points {
+            algo.restrict(sample.clone());
+        }
+        algo
+    }
+}
+
+/// Implementation for the hashed algorithm
+#[derive(Clone)]
+pub struct Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    grid: Grid<F, V>,
+    phases: Vec<Vec<usize>>,
+    outside: Vec<V>,
+    seed: Option<u64>,
+    round: usize,
+    phase: usize,
+    position: usize,
+    success: usize,
+    stats: GenerationStats,
+}
+
+impl<F, V> Algorithm<F, V> for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        let seed = *self.seed.get_or_insert_with(|| rng.random());
+        let uniform = poisson.uniform_exclusion();
+        while self.round < ROUNDS {
+            if self.phase >= self.phases.len() {
+                self.phase = 0;
+                self.round += 1;
+                continue;
+            }
+            if self.position >= self.phases[self.phase].len() {
+                if uniform {
+                    // Occupied cells can't fit more samples so they are left out of the next rounds.
+                    let grid = &self.grid;
+                    self.phases[self.phase].retain(|&cell| {
+                        let index = decode(cell, grid.side(), poisson.domain)
+                            .expect("Cell should be inside of the grid.");
+                        grid.get(index).is_some_and(|c| c.is_empty())
+                    });
+                }
+                self.phase += 1;
+                self.position = 0;
+                continue;
+            }
+            let cell = self.phases[self.phase][self.position];
+            self.position += 1;
+            let index: V = decode(cell, self.grid.side(), poisson.domain)
+                .expect("Cell should be inside of the grid.");
+            if uniform && !self.grid.get(index.clone()).is_some_and(|c| c.is_empty()) {
+                continue;
+            }
+            let tree = SeedTree::new(seed)
+                .child(cell as u64)
+                .child(self.round as u64);
+            let mut offset = V::zero();
+            for n in 0..V::dimension() {
+                offset[n] = unit_from_bits(tree.child(n as u64).seed());
+            }
+            let sample = (index.clone() + offset) * self.grid.cell();
+            if poisson.domain.contains(&sample)
+                && is_disk_free(
+                    &self.grid,
+                    poisson,
+                    index.clone(),
+                    0,
+                    sample.clone(),
+                    &self.outside,
+                )
+            {
+                self.grid.insert(index, sample.clone()).unwrap_or_else(|_| {
+                    panic!("Because the sample is inside of the cell indexing it should work.")
+                });
+                self.success += 1;
+                self.stats.accept();
+                return Some(sample);
+            }
+            self.stats.reject();
+        }
+        None
+    }
+
+    fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        // Every remaining candidate can be rejected so there is no lower bound.
+        // Calculating upper bound should work because there is this many places left in the grid and no more can fit into it.
+        let upper = self.grid.cells().saturating_sub(self.success);
+        (0, poisson.uniform_exclusion().then_some(upper))
+    }
+
+    fn restrict(&mut self, sample: V) {
+        self.success += 1;
+        let index = sample_to_index(&sample, self.grid.side());
+        if let Err(sample) = self.grid.insert(index, sample) {
+            self.outside.push(sample);
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        let index = sample_to_index(&sample, self.grid.side());
+        is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
+    }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        let index = sample_to_index(&sample, self.grid.side());
+        explain_disk(&self.grid, poisson, index, sample, &self.outside)
+    }
+
+    fn stats(&self) -> GenerationStats {
+        self.stats
+    }
+}
@@ -0,0 +1,230 @@
+use crate::algorithm::bridson::random_point_annulus;
+use crate::algorithm::{Algorithm, Creator};
+use crate::utils::*;
+use crate::{Builder, Float, GenerationStats, Rejection, Vector};
+
+use num_traits::Float as NumFloat;
+
+use rand::distr::StandardUniform;
+use rand::Rng;
+use rand_distr::Distribution;
+
+/// Generates samples on a triangular lattice, which packs the disks as densely as possible in two dimensions,
+/// with each sample moved randomly from its lattice point by at most `Builder::with_jitter` times the radius.
+/// The lattice points are spread so that the moved samples never conflict, which gives more samples than the
+/// poisson-disk algorithms at the cost of visible structure.
+/// In higher dimensions the rows are stacked along the other axes so that neighbouring rows are offset by half the spacing.
+#[derive(Debug, Clone, Copy)]
+pub struct Triangular;
+
+impl<F, V> Creator<F, V> for Triangular
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        Algo::new(poisson, false)
+    }
+}
+
+/// Generates samples on a hexagonal lattice, the corners of a honeycomb, with each sample moved randomly from its
+/// lattice point by at most `Builder::with_jitter` times the radius.
+/// The lattice is the triangular one with every third point left out, so it has two thirds of the samples
+/// of `Triangular` and each sample has three nearest neighbours instead of six in two dimensions.
+#[derive(Debug, Clone, Copy)]
+pub struct Hexagonal;
+
+impl<F, V> Creator<F, V> for Hexagonal
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        Algo::new(poisson, true)
+    }
+}
+
+/// Implementation for the lattice algorithms
+#[derive(Clone)]
+pub struct Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    grid: Grid<F, V>,
+    outside: Vec<V>,
+    spacing: Vec<F>,
+    counts: Vec<usize>,
+    honeycomb: bool,
+    next: usize,
+    success: usize,
+    stats: GenerationStats,
+}
+
+impl<F, V> Algorithm<F, V> for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        let jitter = poisson.jitter * poisson.radius;
+        while self.next < self.total() {
+            let point = self.point(self.next);
+            self.next += 1;
+            let point = match point {
+                Some(point) if poisson.domain.contains(&point) => point,
+                _ => continue,
+            };
+            for _ in 0..30 {
+                let mut sample = point.clone() + random_point_annulus(rng, F::cast(0), jitter);
+                for n in 0..V::dimension() {
+                    if poisson.domain.is_perioditic(n) {
+                        let extent = poisson.domain.extent_of::<F>(n);
+                        let floor = NumFloat::floor(sample[n] / extent);
+                        sample[n] -= floor * extent;
+                    }
+                }
+                if poisson.domain.contains(&sample) {
+                    let index = sample_to_index(&sample, self.grid.side());
+                    if is_disk_free(
+                        &self.grid,
+                        poisson,
+                        index.clone(),
+                        0,
+                        sample.clone(),
+                        &self.outside,
+                    ) {
+                        self.grid.insert(index, sample.clone()).unwrap_or_else(|_| {
+                            panic!("Because the sample is [0, 1) indexing it should work.")
+                        });
+                        self.success += 1;
+                        self.stats.accept();
+                        return Some(sample);
+                    }
+                }
+                self.stats.reject();
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self, _poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        // Restricted samples, shapes and blockers can leave any lattice point without a sample so there is no lower bound.
+        // Calculating upper bound should work because each lattice point left gives at most one sample.
+        (0, Some(self.total() - self.next))
+    }
+
+    fn restrict(&mut self, sample: V) {
+        self.success += 1;
+        let index = sample_to_index(&sample, self.grid.side());
+        if let Err(sample) = self.grid.insert(index, sample) {
+            self.outside.push(sample);
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        let index = sample_to_index(&sample, self.grid.side());
+        is_disk_free(&self.grid, poisson, index, 0, sample.clone(), &self.outside)
+    }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        let index = sample_to_index(&sample, self.grid.side());
+        explain_disk(&self.grid, poisson, index, sample, &self.outside)
+    }
+
+    fn stats(&self) -> GenerationStats {
+        self.stats
+    }
+}
+
+impl<F, V> Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    fn new(poisson: &Builder<F, V>, honeycomb: bool) -> Self {
+        // Lattice points are spread by the jitter on both sides so that moved samples are still the diameter apart.
+        let step = F::cast(2) * poisson.radius * (F::cast(1) + poisson.jitter);
+        let row = step * NumFloat::sqrt(F::cast(3)) / F::cast(2);
+        let (mut spacing, mut counts) = (vec![], vec![]);
+        for n in 0..V::dimension() {
+            let extent = poisson.domain.extent_of::<F>(n);
+            let step = if n == 0 { step } else { row };
+            let fits = NumFloat::floor(extent / step)
+                .to_usize()
+                .expect("Extent divided by spacing should be always castable to usize.");
+            if poisson.domain.is_perioditic(n) {
+                // Perioditic axes are stretched to fit whole rows, and even amount of them when the rows are offset,
+                // so that the lattice continues seamlessly across the boundary.
+                let mut count = fits.max(1);
+                if n > 0 && count > 1 && count % 2 == 1 {
+                    count -= 1;
+                }
+                spacing.push(extent / F::cast(count));
+                counts.push(count);
+            } else {
+                spacing.push(step);
+                counts.push(fits + 1);
+            }
+        }
+        let mut algo = Algo {
+            grid: Grid::new(poisson.radius, poisson.domain, poisson.precision)
+                .reusing(poisson.buffers.as_ref()),
+            outside: vec![],
+            spacing,
+            counts,
+            honeycomb,
+            next: 0,
+            success: 0,
+            stats: GenerationStats::default(),
+        };
+        for sample in &poisson.start_points {
+            algo.restrict(sample.clone());
+        }
+        algo
+    }
+
+    fn total(&self) -> usize {
+        self.counts.iter().product()
+    }
+
+    /// Returns the lattice point with the index or `None` if the honeycomb leaves it out.
+    fn point(&self, mut index: usize) -> Option<V> {
+        let mut position = V::zero();
+        let mut rows = 0;
+        let mut first = 0;
+        for n in 0..V::dimension() {
+            let k = index % self.counts[n];
+            index /= self.counts[n];
+            if n == 0 {
+                first = k;
+            } else {
+                rows += k;
+                position[n] = F::cast(k) * self.spacing[n];
+            }
+        }
+        position[0] = (F::cast(2 * first + rows % 2) / F::cast(2)) * self.spacing[0];
+        // In skewed coordinates, where each row starts half a spacing farther than the one below it,
+        // the honeycomb leaves out the points whose coordinates are equal modulo three.
+        let skewed = first as i64 - (rows / 2) as i64;
+        if self.honeycomb && (skewed - rows as i64).rem_euclid(3) == 0 {
+            return None;
+        }
+        Some(position)
+    }
+}
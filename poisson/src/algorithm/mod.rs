@@ -1,16 +1,26 @@
 //! Module that contains traits that describe poisson-disk distribution generating algorithms.
 
-use crate::{Builder, Float, Vector};
+use crate::{Builder, Float, GenerationStats, Rejection, Vector};
 
 use rand::Rng;
 
 use std::fmt::Debug;
 
-pub use self::bridson::Bridson;
-pub use self::ebeida::Ebeida;
+pub use self::auto::Auto;
+pub use self::bridson::{ActivePolicy, Bridson};
+pub use self::dart::DartThrowing;
+pub use self::ebeida::{Algo as EbeidaAlgo, Ebeida};
+pub use self::hashed::Hashed;
+pub use self::lattice::{Hexagonal, Triangular};
+pub use self::sparse::Sparse;
 
+mod auto;
 mod bridson;
+mod dart;
 mod ebeida;
+mod hashed;
+mod lattice;
+mod sparse;
 
 /// Constructs new instance of the algorithm.
 pub trait Creator<F, V>: Copy + Debug
@@ -25,6 +35,112 @@ where
     fn create(_: &Builder<F, V>) -> Self::Algo;
 }
 
+/// Algorithm instance picked by the generator.
+/// The grid-free `Sparse` algorithm is used when the radius is so large that only a handful of samples fit
+/// and the algorithm asked for otherwise.
+#[derive(Clone)]
+pub enum Selected<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Algorithm<F, V>,
+{
+    /// The algorithm asked for and its name.
+    Chosen(A, &'static str),
+    /// The grid-free algorithm.
+    Sparse(<Sparse as Creator<F, V>>::Algo),
+}
+
+impl<F, V, A> Selected<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Algorithm<F, V>,
+{
+    pub(crate) fn create<C>(poisson: &Builder<F, V>) -> Self
+    where
+        C: Creator<F, V, Algo = A>,
+    {
+        if sparse::is_sparse(poisson) {
+            Selected::Sparse(Sparse::create(poisson))
+        } else {
+            Selected::Chosen(C::create(poisson), name::<F, V, C>())
+        }
+    }
+}
+
+impl<F, V, A> Algorithm<F, V> for Selected<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Algorithm<F, V>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        match self {
+            Selected::Chosen(algo, _) => algo.next(poisson, rng),
+            Selected::Sparse(algo) => algo.next(poisson, rng),
+        }
+    }
+
+    fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        match self {
+            Selected::Chosen(algo, _) => algo.size_hint(poisson),
+            Selected::Sparse(algo) => algo.size_hint(poisson),
+        }
+    }
+
+    fn restrict(&mut self, sample: V) {
+        match self {
+            Selected::Chosen(algo, _) => algo.restrict(sample),
+            Selected::Sparse(algo) => algo.restrict(sample),
+        }
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        match self {
+            Selected::Chosen(algo, _) => algo.stays_legal(poisson, sample),
+            Selected::Sparse(algo) => algo.stays_legal(poisson, sample),
+        }
+    }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        match self {
+            Selected::Chosen(algo, _) => algo.explain(poisson, sample),
+            Selected::Sparse(algo) => algo.explain(poisson, sample),
+        }
+    }
+
+    fn stats(&self) -> GenerationStats {
+        match self {
+            Selected::Chosen(algo, name) => {
+                let stats = algo.stats();
+                GenerationStats {
+                    algorithm: stats.algorithm.or(Some(name)),
+                    ..stats
+                }
+            }
+            Selected::Sparse(algo) => GenerationStats {
+                algorithm: Some("Sparse"),
+                ..algo.stats()
+            },
+        }
+    }
+}
+
+/// Returns the name of the algorithm without its module path.
+pub(crate) fn name<F, V, A>() -> &'static str
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    let name = std::any::type_name::<A>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
 /// Trait that describes poisson-disk distribution generating algorithm.
 pub trait Algorithm<F, V>
 where
@@ -44,4 +160,17 @@ where
 
     /// Checks if a sample is valid for the poisson-disk distribution generated thus far by the algorithm.
     fn stays_legal(&self, _: &Builder<F, V>, _: V) -> bool;
+
+    /// Explains why a sample isn't valid for the poisson-disk distribution generated thus far by the algorithm,
+    /// or returns `None` if it is.
+    /// Algorithms that can't tell which rule rejects a sample return `None` for every sample.
+    fn explain(&self, _: &Builder<F, V>, _: V) -> Option<Rejection<F, V>> {
+        None
+    }
+
+    /// Returns statistics about the samples the algorithm has tried thus far.
+    /// Algorithms that don't keep any report empty statistics.
+    fn stats(&self) -> GenerationStats {
+        GenerationStats::default()
+    }
 }
@@ -0,0 +1,127 @@
+use crate::algorithm::{Algorithm, Creator};
+use crate::utils::*;
+use crate::{Builder, Float, GenerationStats, Rejection, Vector};
+
+use num_traits::Float as NumFloat;
+
+use rand::Rng;
+
+use std::marker::PhantomData;
+
+/// Generates non-maximal Poisson-disk distribution by throwing uniformly random samples and checking them exactly
+/// against every earlier sample without a grid, until too many consecutive samples are rejected.
+/// The amount of consecutive rejections is set with `Builder::with_rejection_limit`.
+///
+/// Generators pick this instead of the algorithm asked for when the radius is so large that the grid would have
+/// less than two cells along an axis and only a handful of samples fit, which makes the grid pure overhead.
+/// It works for any radius, including the ones above √2 / 2 where at most a single sample fits.
+#[derive(Debug, Clone, Copy)]
+pub struct Sparse;
+
+impl<F, V> Creator<F, V> for Sparse
+where
+    F: Float,
+    V: Vector<F>,
+{
+    type Algo = Algo<F, V>;
+
+    fn create(poisson: &Builder<F, V>) -> Self::Algo {
+        Algo {
+            samples: poisson.start_points.clone(),
+            rejections: 0,
+            stats: GenerationStats::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Checks if the radius is large enough that the grid would have less than two cells along an axis.
+pub(crate) fn is_sparse<F, V>(poisson: &Builder<F, V>) -> bool
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let cell = (F::cast(2) * poisson.radius) / NumFloat::sqrt(F::cast(V::dimension()));
+    F::cast(1) / cell < F::cast(2)
+}
+
+/// Implementation for the sparse algorithm
+#[derive(Clone)]
+pub struct Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    samples: Vec<V>,
+    rejections: usize,
+    stats: GenerationStats,
+    _marker: PhantomData<F>,
+}
+
+impl<F, V> Algorithm<F, V> for Algo<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn next<R>(&mut self, poisson: &mut Builder<F, V>, rng: &mut R) -> Option<V>
+    where
+        R: Rng,
+    {
+        while self.rejections < poisson.rejection_limit {
+            let mut sample = V::zero();
+            for n in 0..V::dimension() {
+                sample[n] = unit_from_bits::<F>(rng.next_u64()) * poisson.domain.extent_of::<F>(n);
+            }
+            if poisson.inside_shape(&sample)
+                && poisson.blocking(&sample).is_none()
+                && is_valid(poisson, &self.samples, sample.clone())
+                && poisson.failed_check(&sample, &self.samples).is_none()
+            {
+                self.samples.push(sample.clone());
+                self.rejections = 0;
+                self.stats.accept();
+                return Some(sample);
+            }
+            self.rejections += 1;
+            self.stats.reject();
+        }
+        None
+    }
+
+    fn size_hint(&self, poisson: &Builder<F, V>) -> (usize, Option<usize>) {
+        // The next throw can always be the one that hits the rejection limit so there is no lower bound.
+        // Cubes whose diagonal is the diameter can't hold two samples so covering the domain with them bounds the amount.
+        let cell = (F::cast(2) * poisson.radius) / NumFloat::sqrt(F::cast(V::dimension()));
+        let upper = NumFloat::ceil(F::cast(1) / cell)
+            .to_usize()
+            .and_then(|side| side.checked_pow(V::dimension() as u32))
+            .map(|cells| cells.saturating_sub(self.samples.len()));
+        (0, upper.filter(|_| poisson.uniform_exclusion()))
+    }
+
+    fn restrict(&mut self, sample: V) {
+        self.samples.push(sample);
+    }
+
+    fn stays_legal(&self, poisson: &Builder<F, V>, sample: V) -> bool {
+        poisson.inside_shape(&sample)
+            && poisson.blocking(&sample).is_none()
+            && poisson.failed_check(&sample, &self.samples).is_none()
+            && is_valid(poisson, &self.samples, sample)
+    }
+
+    fn explain(&self, poisson: &Builder<F, V>, sample: V) -> Option<Rejection<F, V>> {
+        if !poisson.inside_shape(&sample) {
+            return Some(Rejection::OutsideShape);
+        }
+        if let Some(rejection) = explain_blocking(poisson, &sample) {
+            return Some(rejection);
+        }
+        explain_valid(poisson, &self.samples, sample.clone())
+            .or_else(|| explain_check(poisson, &self.samples, &sample))
+    }
+
+    fn stats(&self) -> GenerationStats {
+        self.stats
+    }
+}
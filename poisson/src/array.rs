@@ -0,0 +1,24 @@
+//! Vectors stored as plain arrays, for generating distributions without a linear algebra crate.
+//!
+//! Each vector wraps an array and has the same layout as it, so the generated samples can be turned into arrays
+//! with `into_vec` without copying them.
+//!
+//! The algorithms still work on the `Vector` trait built on alga, which stays a dependency of the crate,
+//! so these only spare users that want `[f32; 2]` output from depending on nalgebra or a crate like it.
+
+use crate::utils::vector::mirror;
+
+mirror! {
+    /// Two dimensional vector stored as an array.
+    Array2([F; 2]) { 0, 1 }
+}
+
+mirror! {
+    /// Three dimensional vector stored as an array.
+    Array3([F; 3]) { 0, 1, 2 }
+}
+
+mirror! {
+    /// Four dimensional vector stored as an array.
+    Array4([F; 4]) { 0, 1, 2, 3 }
+}
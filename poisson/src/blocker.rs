@@ -0,0 +1,132 @@
+//! Module that contains dynamic blockers that samples keep away from.
+
+use crate::utils::sqdist;
+use crate::{Domain, Float, Vector};
+
+use num_traits::Float as NumFloat;
+
+/// Handle to a blocker added to the generation with `PoissonIter::add_blocker`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlockerId(usize);
+
+/// Position occupied by something else than a sample, like a player or a vehicle.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Blocker<F, V> {
+    pub(crate) position: V,
+    pub(crate) radius: F,
+}
+
+/// Line segment with the capsule of positions at most the clearance away from it occupied, such as a road.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Segment<F, V> {
+    pub(crate) start: V,
+    pub(crate) end: V,
+    pub(crate) clearance: F,
+}
+
+impl<F, V> Segment<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Returns the point of the segment closest to the sample and the squared distance to it.
+    /// Across perioditic axes the image of the segment closest to its start is used,
+    /// so the segment should be shorter than half of the domain along them.
+    pub(crate) fn closest(&self, sample: &V, domain: Domain) -> (V, F) {
+        let mut offset = sample.clone() - self.start.clone();
+        for n in 0..V::dimension() {
+            if domain.is_perioditic(n) {
+                let extent = domain.extent_of::<F>(n);
+                let images = NumFloat::round(offset[n] / extent);
+                offset[n] -= images * extent;
+            }
+        }
+        let direction = self.end.clone() - self.start.clone();
+        let length = direction.norm_squared();
+        let t = if length > F::cast(0) {
+            NumFloat::max(
+                F::cast(0),
+                NumFloat::min(F::cast(1), offset.dot(&direction) / length),
+            )
+        } else {
+            F::cast(0)
+        };
+        let along = direction * t;
+        let sqdistance = (offset - along.clone()).norm_squared();
+        (self.start.clone() + along, sqdistance)
+    }
+}
+
+/// Blockers that can be added, moved and removed while generating.
+/// They are checked one by one so they are meant for a handful of moving objects, not as many as there are samples.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Blockers<F, V> {
+    slots: Vec<Option<Blocker<F, V>>>,
+    free: Vec<usize>,
+}
+
+impl<F, V> Default for Blockers<F, V> {
+    fn default() -> Self {
+        Blockers {
+            slots: vec![],
+            free: vec![],
+        }
+    }
+}
+
+impl<F, V> Blockers<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    pub(crate) fn add(&mut self, blocker: Blocker<F, V>) -> BlockerId {
+        match self.free.pop() {
+            Some(i) => {
+                self.slots[i] = Some(blocker);
+                BlockerId(i)
+            }
+            None => {
+                self.slots.push(Some(blocker));
+                BlockerId(self.slots.len() - 1)
+            }
+        }
+    }
+
+    pub(crate) fn get_mut(&mut self, id: BlockerId) -> Option<&mut Blocker<F, V>> {
+        self.slots.get_mut(id.0).and_then(|b| b.as_mut())
+    }
+
+    pub(crate) fn remove(&mut self, id: BlockerId) -> Option<Blocker<F, V>> {
+        let blocker = self.slots.get_mut(id.0).and_then(|b| b.take());
+        if blocker.is_some() {
+            self.free.push(id.0);
+        }
+        blocker
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.slots.clear();
+        self.free.clear();
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.slots.len() == self.free.len()
+    }
+
+    /// Returns the closest blocker whose zone the sample with given radius overlaps and the distance to it.
+    pub(crate) fn blocking(&self, sample: &V, radius: F, domain: Domain) -> Option<(&V, F)> {
+        self.slots
+            .iter()
+            .flatten()
+            .filter_map(|b| {
+                let sqdistance = sqdist(b.position.clone(), sample.clone(), domain);
+                let reach = b.radius + radius;
+                (sqdistance < reach * reach).then_some((&b.position, sqdistance))
+            })
+            .fold(None, |closest: Option<(&V, F)>, (p, d)| match closest {
+                Some((_, c)) if c <= d => closest,
+                _ => Some((p, d)),
+            })
+            .map(|(p, d)| (p, NumFloat::sqrt(d)))
+    }
+}
@@ -0,0 +1,250 @@
+//! Module that contains sampling of the boundary of the sampling space.
+
+use crate::shape::Parametric;
+use crate::utils::{
+    conflicts, is_disk_free, is_valid, mantissa_digits, sample_to_index, unit_from_bits, Grid,
+};
+use crate::{Builder, Float, Precision, Vector};
+
+use num_traits::Float as NumFloat;
+
+use rand::Rng;
+
+use smallvec::SmallVec;
+
+/// Distribution split into the samples on the boundary and the samples inside it.
+/// Both groups are one distribution so the samples of each group keep away from the samples of the other too.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DualSamples<V> {
+    /// Samples on the outline of the shape, or on the faces of the domain without one.
+    pub boundary: Vec<V>,
+    /// Samples inside the boundary, including the start points if they are emitted.
+    pub interior: Vec<V>,
+}
+
+/// Generates maximal distribution on the faces of [0, 1)<sup>d</sup> in the order of their dimension
+/// so that the corners are sampled first, then the edges and so on up to the faces of dimension d - 1.
+/// Faces across perioditic axes are skipped as those axes have no sides.
+/// The samples respect the start points of the builder and each other.
+pub(crate) fn sample<F, V, R>(poisson: &Builder<F, V>, rng: &mut R) -> Vec<V>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng,
+{
+    let dim = V::dimension();
+    let mut faces = Faces {
+        poisson,
+        grid: Grid::new(poisson.radius, poisson.domain, Precision::Full),
+        outside: vec![],
+        samples: vec![],
+    };
+    for sample in &poisson.start_points {
+        faces.insert(sample.clone());
+    }
+    // Perioditic axes have no sides so they are free on every face.
+    let perioditic = (0..dim)
+        .filter(|&n| poisson.domain.is_perioditic(n))
+        .fold(0usize, |mask, n| mask | 1 << n);
+    for free in 0..dim {
+        for axes in (0..1usize << dim)
+            .filter(|a| a.count_ones() as usize == free && a & perioditic == perioditic)
+        {
+            for sides in 0..1usize << (dim - free) {
+                faces.fill(axes, sides, rng);
+            }
+        }
+    }
+    faces.samples
+}
+
+struct Faces<'a, F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    poisson: &'a Builder<F, V>,
+    grid: Grid<F, V>,
+    outside: Vec<V>,
+    samples: Vec<V>,
+}
+
+impl<F, V> Faces<'_, F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn insert(&mut self, sample: V) {
+        let index = sample_to_index(&sample, self.grid.side());
+        if let Err(sample) = self.grid.insert(index, sample) {
+            self.outside.push(sample);
+        }
+    }
+
+    /// Fills the face whose free axes are the set bits of `axes` with Ebeida's algorithm.
+    /// The n:th fixed axis lies on the upper side of the domain if the n:th bit of `sides` is set.
+    fn fill<R>(&mut self, axes: usize, sides: usize, rng: &mut R)
+    where
+        R: Rng,
+    {
+        let dim = V::dimension();
+        let free = (0..dim).filter(|n| axes & 1 << n != 0).collect::<Vec<_>>();
+        let top = F::cast(1) - <F as NumFloat>::epsilon();
+        let mut origin = V::zero();
+        for (bit, n) in (0..dim).filter(|n| axes & 1 << n == 0).enumerate() {
+            if sides & 1 << bit != 0 {
+                origin[n] = top;
+            }
+        }
+        // Cells are small enough that a sample anywhere in a cell covers it.
+        let side = if free.is_empty() {
+            1
+        } else {
+            NumFloat::ceil(NumFloat::sqrt(F::cast(free.len())) / (F::cast(2) * self.poisson.radius))
+                .to_usize()
+                .expect("Amount of cells should be castable to usize.")
+        };
+        let mut width = F::cast(1) / F::cast(side);
+        let mut cells = vec![origin];
+        for &n in &free {
+            cells = cells
+                .into_iter()
+                .flat_map(|cell| {
+                    (0..side).map(move |i| {
+                        let mut cell = cell.clone();
+                        cell[n] = width * F::cast(i);
+                        cell
+                    })
+                })
+                .collect();
+        }
+        let mantissa_digits = mantissa_digits::<F>();
+        let mut level = 0;
+        while !cells.is_empty() && level < mantissa_digits {
+            for _ in 0..cells.len() {
+                if cells.is_empty() {
+                    break;
+                }
+                let index = rng.random_range(0..cells.len());
+                let mut sample = cells[index].clone();
+                for &n in &free {
+                    let t: F = unit_from_bits(rng.next_u64());
+                    sample[n] = NumFloat::min(sample[n] + width * t, top);
+                }
+                let grid_index = sample_to_index(&sample, self.grid.side());
+                if is_disk_free(
+                    &self.grid,
+                    self.poisson,
+                    grid_index,
+                    0,
+                    sample.clone(),
+                    &self.outside,
+                ) {
+                    self.insert(sample.clone());
+                    self.samples.push(sample);
+                    if self.poisson.uniform_exclusion() {
+                        cells.swap_remove(index);
+                    }
+                }
+            }
+            width /= F::cast(2);
+            cells = cells
+                .into_iter()
+                .flat_map(|cell| children(&cell, &free, width))
+                .filter(|cell| !self.covered(cell, &free, width))
+                .collect();
+            level += 1;
+        }
+    }
+
+    /// Checks if a single sample covers every corner of the cell.
+    fn covered(&self, cell: &V, free: &[usize], width: F) -> bool {
+        let mut center = cell.clone();
+        for &n in free {
+            center[n] += width / F::cast(2);
+        }
+        let index = sample_to_index(&center, self.grid.side());
+        let corners = children(cell, free, width);
+        self.grid
+            .neighbours(index)
+            .iter()
+            .chain(&self.outside)
+            .any(|s| corners.iter().all(|c| conflicts(self.poisson, s, c)))
+    }
+}
+
+/// Returns the corners of the cell offset by zero or `width` along each free axis.
+/// Faces of up to three dimensions fit in the inline buffer.
+fn children<F, V>(cell: &V, free: &[usize], width: F) -> SmallVec<[V; 8]>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    (0..1usize << free.len())
+        .map(|bits| {
+            let mut child = cell.clone();
+            for (bit, &n) in free.iter().enumerate() {
+                if bits & 1 << bit != 0 {
+                    child[n] += width;
+                }
+            }
+            child
+        })
+        .collect()
+}
+
+/// Generates distribution along the outline of the shape by walking it from a random point in steps much shorter than
+/// the radius and keeping each point that doesn't conflict with the start points of the builder or the points kept before it.
+/// Points outside the domain are wrapped around perioditic axes and skipped otherwise.
+pub(crate) fn outline<F, V, R>(
+    poisson: &Builder<F, V>,
+    shape: &dyn Parametric<F, V>,
+    rng: &mut R,
+) -> Vec<V>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng,
+{
+    assert_eq!(
+        2,
+        V::dimension(),
+        "Only outlines of two dimensional shapes can be sampled."
+    );
+    let radius = poisson
+        .regions
+        .as_ref()
+        .map_or(poisson.radius, |r| r.min_radius());
+    let steps = NumFloat::ceil(shape.perimeter() / (radius / F::cast(8)))
+        .to_usize()
+        .expect("Amount of steps should be castable to usize.")
+        .max(1);
+    let offset: F = unit_from_bits(rng.next_u64());
+    let mut samples = poisson.start_points.clone();
+    let start = samples.len();
+    for i in 0..steps {
+        let t = offset + F::cast(i) / F::cast(steps);
+        let t = t - NumFloat::floor(t);
+        if !shape.on_outline(t) {
+            continue;
+        }
+        let mut sample = shape.outline(t);
+        let inside = (0..V::dimension()).all(|n| {
+            let extent = poisson.domain.extent_of::<F>(n);
+            if poisson.domain.is_perioditic(n) {
+                let floor = NumFloat::floor(sample[n] / extent);
+                sample[n] -= floor * extent;
+                true
+            } else {
+                F::cast(0) <= sample[n] && sample[n] < extent
+            }
+        });
+        if inside
+            && is_valid(poisson, &samples, sample.clone())
+            && poisson.failed_check(&sample, &samples).is_none()
+        {
+            samples.push(sample);
+        }
+    }
+    samples.split_off(start)
+}
@@ -0,0 +1,92 @@
+//! Module that contains the pool of buffers the algorithms reuse between generations.
+
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+/// Pool of buffers for the grids and active sample lists of the algorithms, given to `Builder::with_buffers`.
+///
+/// Generations take their buffers from the pool when they start and give them back when they are dropped,
+/// so generating a distribution every frame reuses the same memory instead of allocating it again.
+/// Clones share the same pool. The memory is released all at once when the last clone is dropped
+/// or when the pool is cleared. The buffers are ordinary vectors, as custom allocators need the unstable
+/// `allocator_api`.
+#[derive(Clone)]
+pub struct Buffers<V>(Arc<Mutex<Pool<V>>>);
+
+struct Pool<V> {
+    indices: Vec<Vec<usize>>,
+    samples: Vec<Vec<V>>,
+}
+
+impl<V> Buffers<V> {
+    /// Creates an empty pool.
+    pub fn new() -> Self {
+        Buffers(Arc::new(Mutex::new(Pool {
+            indices: vec![],
+            samples: vec![],
+        })))
+    }
+
+    /// Returns the amount of bytes the buffers in the pool have room for.
+    /// Buffers in use by generations aren't counted until they are given back.
+    pub fn capacity(&self) -> usize {
+        let pool = self.lock();
+        let indices = pool.indices.iter().map(Vec::capacity).sum::<usize>();
+        let samples = pool.samples.iter().map(Vec::capacity).sum::<usize>();
+        indices * mem::size_of::<usize>() + samples * mem::size_of::<V>()
+    }
+
+    /// Releases the memory of the buffers in the pool.
+    pub fn clear(&self) {
+        let mut pool = self.lock();
+        pool.indices.clear();
+        pool.samples.clear();
+    }
+
+    /// Takes an empty buffer of indices from the pool, or a new one if the pool has none.
+    pub(crate) fn take_indices(&self) -> Vec<usize> {
+        self.lock().indices.pop().unwrap_or_default()
+    }
+
+    /// Takes an empty buffer of samples from the pool, or a new one if the pool has none.
+    pub(crate) fn take_samples(&self) -> Vec<V> {
+        self.lock().samples.pop().unwrap_or_default()
+    }
+
+    /// Gives the buffer back to the pool emptied, keeping its memory.
+    pub(crate) fn give_indices(&self, mut buffer: Vec<usize>) {
+        buffer.clear();
+        self.lock().indices.push(buffer);
+    }
+
+    /// Gives the buffer back to the pool emptied, keeping its memory.
+    pub(crate) fn give_samples(&self, mut buffer: Vec<V>) {
+        buffer.clear();
+        self.lock().samples.push(buffer);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Pool<V>> {
+        // Pool holds only empty buffers so it is usable even if a thread panicked while holding it.
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<V> Default for Buffers<V> {
+    fn default() -> Self {
+        Buffers::new()
+    }
+}
+
+impl<V> fmt::Debug for Buffers<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Buffers")
+    }
+}
+
+impl<V> PartialEq for Buffers<V> {
+    /// Pools are equal only if they are the same pool.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
@@ -0,0 +1,208 @@
+//! Module that contains capacity-constrained Voronoi refinement of distributions.
+
+use crate::utils::sqdist;
+use crate::{Domain, Float, PointSet, Vector};
+
+use num_traits::Float as NumFloat;
+
+use rand::distr::StandardUniform;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_distr::Distribution;
+
+/// Parameters of the capacity-constrained Voronoi tessellation refinement.
+///
+/// The domain is discretized into random sites which are split evenly between the samples.
+/// Each iteration swaps sites between neighbouring samples whenever that brings both closer to their sites
+/// and then moves the samples to the centroids of their sites, so that every sample ends up with the same area.
+/// More sites per sample give more accurate areas at the cost of generation time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ccvt<F>
+where
+    F: Float,
+{
+    iterations: usize,
+    threshold: F,
+    sites: usize,
+}
+
+impl<F> Default for Ccvt<F>
+where
+    F: Float,
+{
+    fn default() -> Self {
+        Ccvt::new()
+    }
+}
+
+impl<F> Ccvt<F>
+where
+    F: Float,
+{
+    /// New refinement with 20 iterations, no convergence threshold and 32 sites per sample.
+    pub fn new() -> Self {
+        Ccvt {
+            iterations: 20,
+            threshold: F::cast(0),
+            sites: 32,
+        }
+    }
+
+    /// Sets the largest amount of iterations.
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Returns the largest amount of iterations.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Sets the distance that the samples have to move in an iteration for the refinement to continue.
+    /// The refinement stops early once no sample moves farther than this or no sites are swapped.
+    pub fn with_threshold(mut self, threshold: F) -> Self {
+        assert!(threshold >= F::cast(0));
+        self.threshold = threshold;
+        self
+    }
+
+    /// Returns the distance that the samples have to move in an iteration for the refinement to continue.
+    pub fn threshold(&self) -> F {
+        self.threshold
+    }
+
+    /// Sets the amount of sites for each sample.
+    pub fn with_sites(mut self, sites: usize) -> Self {
+        assert!(sites > 0);
+        self.sites = sites;
+        self
+    }
+
+    /// Returns the amount of sites for each sample.
+    pub fn sites(&self) -> usize {
+        self.sites
+    }
+}
+
+impl<F, V> PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Refines the distribution with capacity-constrained Voronoi tessellation for better blue noise spectrum.
+    /// The amount of samples stays the same but the radius is not enforced as the samples move,
+    /// though it is rarely violated by much as equal areas keep the samples evenly spaced.
+    pub fn refine_ccvt<R>(self, ccvt: &Ccvt<F>, rng: &mut R) -> Self
+    where
+        R: Rng,
+        StandardUniform: Distribution<V>,
+    {
+        let count = self.len();
+        if count < 2 {
+            return self;
+        }
+        let (radius, domain, algorithm) = (self.radius(), self.domain(), self.algorithm());
+        let mut points = self.into_points();
+        let sites = (0..count * ccvt.sites)
+            .map(|_| {
+                let mut site: V = rng.sample(StandardUniform);
+                for n in 0..V::dimension() {
+                    site[n] *= domain.extent_of::<F>(n);
+                }
+                site
+            })
+            .collect::<Vec<V>>();
+        let mut order = (0..sites.len()).collect::<Vec<_>>();
+        order.shuffle(rng);
+        let mut owned = order
+            .chunks(ccvt.sites)
+            .map(|c| c.to_vec())
+            .collect::<Vec<_>>();
+        // Only samples close to each other can have sites worth swapping.
+        let reach = NumFloat::powi(F::cast(8) * radius, 2);
+        let sqthreshold = NumFloat::powi(ccvt.threshold, 2);
+        for _ in 0..ccvt.iterations {
+            let mut swaps = 0;
+            for i in 0..count {
+                for j in i + 1..count {
+                    let (xi, xj) = (points[i].clone(), points[j].clone());
+                    if sqdist(xi.clone(), xj.clone(), domain) > reach {
+                        continue;
+                    }
+                    let gain = |s: usize, from: &V, to: &V| {
+                        sqdist(sites[s].clone(), from.clone(), domain)
+                            - sqdist(sites[s].clone(), to.clone(), domain)
+                    };
+                    let mut hi = owned[i]
+                        .iter()
+                        .map(|&s| (gain(s, &xi, &xj), s))
+                        .collect::<Vec<_>>();
+                    let mut hj = owned[j]
+                        .iter()
+                        .map(|&s| (gain(s, &xj, &xi), s))
+                        .collect::<Vec<_>>();
+                    let descending = |a: &(F, usize), b: &(F, usize)| {
+                        b.0.partial_cmp(&a.0).expect("Distances should not be NaN.")
+                    };
+                    hi.sort_by(descending);
+                    hj.sort_by(descending);
+                    let mut k = 0;
+                    while k < hi.len() && k < hj.len() && hi[k].0 + hj[k].0 > F::cast(0) {
+                        k += 1;
+                    }
+                    if k > 0 {
+                        swaps += k;
+                        owned[i] = hj[..k].iter().chain(&hi[k..]).map(|h| h.1).collect();
+                        owned[j] = hi[..k].iter().chain(&hj[k..]).map(|h| h.1).collect();
+                    }
+                }
+            }
+            let mut moved = F::cast(0);
+            for (p, own) in points.iter_mut().zip(&owned) {
+                let mut shift = V::zero();
+                for &s in own {
+                    shift += difference(p, &sites[s], domain);
+                }
+                shift *= F::cast(1) / F::cast(own.len());
+                let sqshift = shift.norm_squared();
+                if sqshift > moved {
+                    moved = sqshift;
+                }
+                *p += shift;
+                for n in 0..V::dimension() {
+                    if domain.is_perioditic(n) {
+                        let extent = domain.extent_of::<F>(n);
+                        let floor = NumFloat::floor(p[n] / extent);
+                        p[n] -= floor * extent;
+                    }
+                }
+            }
+            if swaps == 0 || moved <= sqthreshold {
+                break;
+            }
+        }
+        let set = PointSet::new(points, radius, domain);
+        match algorithm {
+            Some(algorithm) => set.with_algorithm(algorithm),
+            None => set,
+        }
+    }
+}
+
+/// Returns the difference from one point to another through the closest image across perioditic axes.
+fn difference<F, V>(from: &V, to: &V, domain: Domain) -> V
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let mut diff = to.clone() - from.clone();
+    for n in 0..V::dimension() {
+        if domain.is_perioditic(n) {
+            let extent = domain.extent_of::<F>(n);
+            let round = NumFloat::round(diff[n] / extent);
+            diff[n] -= round * extent;
+        }
+    }
+    diff
+}
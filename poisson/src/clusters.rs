@@ -0,0 +1,137 @@
+//! Module that contains generation of clumps of samples around the samples of a sparser distribution.
+
+use crate::algorithm::Creator;
+use crate::shape::Sdf;
+use crate::utils::sqdist;
+use crate::{Builder, Float, PointSet, SeedTree, Vector};
+
+use num_traits::Float as NumFloat;
+
+use rand::{Rng, SeedableRng};
+
+/// Two levels of poisson-disk distributions where parents are generated first and children are then generated
+/// in the disks around the parents, like the clumps vegetation grows in.
+///
+/// The children are generated as one distribution restricted to the union of the disks,
+/// so children of neighbouring clumps keep their spacing too and the clumps are as maximal as they can be.
+/// Each child belongs to the clump of the parent closest to it.
+///
+/// The parents are generated with the random number generator seeded from the child of `SeedTree` with index 0
+/// and the children with the one with index 1, with the root seed drawn from the random number generator given.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, Clusters, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// type Vec2 = na::Vector2<f64>;
+/// let (bushes, clumps) = Clusters::new(
+///     Builder::<_, Vec2>::with_disk_radius(0.15, Type::Normal),
+///     Builder::with_disk_radius(0.01, Type::Normal),
+///     0.08,
+/// )
+/// .generate(SmallRng::seed_from_u64(1), algorithm::Ebeida);
+/// assert_eq!(bushes.len(), clumps.len());
+/// for (bush, clump) in bushes.iter().zip(&clumps) {
+///     assert!(clump.iter().all(|leaf| (leaf - bush).norm() <= 0.08));
+/// }
+/// ````
+#[derive(Clone, Debug)]
+pub struct Clusters<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    parents: Builder<F, V>,
+    children: Builder<F, V>,
+    spread: F,
+}
+
+impl<F, V> Clusters<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates clusters with the builders of the parents and the children and the radius of the disks around
+    /// the parents the children are generated in.
+    /// Both builders have to have the same domain and the shape of the children is replaced by the disks.
+    pub fn new(parents: Builder<F, V>, children: Builder<F, V>, spread: F) -> Self {
+        assert!(
+            parents.domain == children.domain,
+            "Parents and children should have the same domain."
+        );
+        assert!(F::cast(0) < spread);
+        Clusters {
+            parents,
+            children,
+            spread,
+        }
+    }
+
+    /// Returns the builder of the parents.
+    pub fn parents(&self) -> &Builder<F, V> {
+        &self.parents
+    }
+
+    /// Returns the builder of the children.
+    pub fn children(&self) -> &Builder<F, V> {
+        &self.children
+    }
+
+    /// Returns the radius of the disks around the parents the children are generated in.
+    pub fn spread(&self) -> F {
+        self.spread
+    }
+
+    /// Generates the parents and the clumps of children around them, returning the parents and a clump for each parent
+    /// in the same order. Inside the disks the distance to every parent is checked,
+    /// so the parents are meant to be sparse.
+    /// Bridson grows the distribution from the samples it has and can miss disks that don't overlap the others,
+    /// so Ebeida is the algorithm to use when every clump should be filled.
+    pub fn generate<R, A>(&self, mut rng: R, algo: A) -> (PointSet<F, V>, Vec<PointSet<F, V>>)
+    where
+        V: Send + Sync + 'static,
+        R: Rng + SeedableRng + Clone,
+        A: Creator<F, V>,
+    {
+        let tree = SeedTree::from_rng(&mut rng);
+        let parents = self
+            .parents
+            .clone()
+            .build(tree.child(0).rng::<R>(), algo)
+            .generate_set();
+        let domain = self.children.domain;
+        let centers = parents.points().to_vec();
+        let spread = self.spread;
+        // Distance to the closest parent minus the spread is the exact signed distance to the union of the disks.
+        let disks = Sdf(move |p: &V| {
+            let closest = centers
+                .iter()
+                .map(|c| sqdist(c.clone(), p.clone(), domain))
+                .fold(NumFloat::max_value(), NumFloat::min);
+            NumFloat::sqrt(closest) - spread
+        });
+        let children = self
+            .children
+            .clone()
+            .with_shape(disks)
+            .build(tree.child(1).rng::<R>(), algo)
+            .generate_set();
+        let mut clumps = vec![vec![]; parents.len()];
+        for p in &children {
+            let closest = (0..parents.len())
+                .map(|i| (sqdist(parents.points()[i].clone(), p.clone(), domain), i))
+                .fold(None, |closest: Option<(F, usize)>, (d, i)| match closest {
+                    Some((c, _)) if c <= d => closest,
+                    _ => Some((d, i)),
+                });
+            if let Some((_, i)) = closest {
+                clumps[i].push(p.clone());
+            }
+        }
+        let clumps = clumps
+            .into_iter()
+            .map(|points| PointSet::new(points, children.radius(), domain))
+            .collect();
+        (parents, clumps)
+    }
+}
@@ -0,0 +1,88 @@
+//! Module that contains integration with the distributions of `rand`.
+
+use crate::algorithm::{self, Creator};
+use crate::{Builder, Float, PointSet, Vector};
+
+use rand::distr::Distribution;
+use rand::Rng;
+
+/// Poisson-disk distribution usable wherever `rand` distributions are.
+///
+/// Each sample is a whole independent poisson-disk distribution generated with the random number generator given to it.
+///
+/// ````rust
+/// # extern crate nalgebra as na;
+/// use poisson::{algorithm, Builder, PoissonDisk, PointSet, Type};
+/// use rand::{rngs::SmallRng, Rng, SeedableRng};
+///
+/// let disk = PoissonDisk::new(
+///     Builder::<f64, na::Vector2<f64>>::with_disk_radius(0.1, Type::Normal),
+///     algorithm::Bridson,
+/// );
+/// let mut rng = SmallRng::seed_from_u64(1);
+/// let points: Vec<_> = rng.sample(&disk);
+/// let sets: Vec<PointSet<_, _>> = (&mut rng).sample_iter(&disk).take(3).collect();
+/// assert!(!points.is_empty());
+/// assert_eq!(3, sets.len());
+/// ````
+#[derive(Clone, Debug)]
+pub struct PoissonDisk<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    poisson: Builder<F, V>,
+    algo: A,
+}
+
+impl<F, V, A> PoissonDisk<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    /// Creates distribution generating with the builder and algorithm specified.
+    pub fn new(poisson: Builder<F, V>, algo: A) -> Self {
+        PoissonDisk { poisson, algo }
+    }
+
+    /// Returns the builder the distributions are generated with.
+    pub fn builder(&self) -> &Builder<F, V> {
+        &self.poisson
+    }
+}
+
+impl<F, V, A> Distribution<Vec<V>> for PoissonDisk<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    fn sample<R>(&self, rng: &mut R) -> Vec<V>
+    where
+        R: Rng + ?Sized,
+    {
+        self.poisson
+            .clone()
+            .build(rng, self.algo)
+            .into_iter()
+            .collect()
+    }
+}
+
+impl<F, V, A> Distribution<PointSet<F, V>> for PoissonDisk<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    fn sample<R>(&self, rng: &mut R) -> PointSet<F, V>
+    where
+        R: Rng + ?Sized,
+    {
+        let points: Vec<V> = self.sample(rng);
+        PointSet::new(points, self.poisson.radius(), self.poisson.domain())
+            .with_algorithm(algorithm::name::<F, V, A>())
+    }
+}
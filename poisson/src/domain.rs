@@ -0,0 +1,167 @@
+//! Module that contains the description of the space the samples are generated in.
+
+use crate::{Float, Type, Vector};
+
+use num_traits::NumCast;
+
+/// Condition on the sides of an axis of the domain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// Acts like there is void beyond the sides placing no restrictions to samples near them.
+    #[default]
+    Open,
+    /// Wraps the axis around so that the distribution can be tiled along it.
+    Perioditic,
+}
+
+/// Description of the space the samples are generated in.
+///
+/// The domain is [0, 1)<sup>d</sup> with its own boundary condition for each axis,
+/// so that for example a distribution on the surface of a cylinder wraps around only one axis.
+/// Axes can be shorter than the unit so that long thin domains like a 4096 × 128 strip are described
+/// with the longest axis normalized to the unit instead of squeezing the strip into the unit cube.
+/// `Type` converts into a domain where every axis has the same condition.
+/// New properties of the domain are added as builder methods so that existing descriptions keep working.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Domain {
+    perioditic: u64,
+    extents: [f64; MAX_EXTENTS],
+}
+
+/// Amount of axes whose extent can be set.
+const MAX_EXTENTS: usize = 8;
+
+// Extents are asserted to be finite so the domain is reflexively equal.
+impl Eq for Domain {}
+
+impl Default for Domain {
+    fn default() -> Self {
+        Domain::open()
+    }
+}
+
+impl Domain {
+    /// Domain where every axis is open.
+    pub fn open() -> Self {
+        Domain {
+            perioditic: 0,
+            extents: [1.; MAX_EXTENTS],
+        }
+    }
+
+    /// Domain where every axis wraps around.
+    pub fn perioditic() -> Self {
+        Domain {
+            perioditic: u64::MAX,
+            extents: [1.; MAX_EXTENTS],
+        }
+    }
+
+    /// Sets the boundary condition of an axis.
+    /// There can be at most 64 axes.
+    pub fn with_boundary(mut self, axis: usize, boundary: Boundary) -> Self {
+        assert!(axis < 64);
+        match boundary {
+            Boundary::Open => self.perioditic &= !(1 << axis),
+            Boundary::Perioditic => self.perioditic |= 1 << axis,
+        }
+        self
+    }
+
+    /// Returns the boundary condition of an axis.
+    pub fn boundary(&self, axis: usize) -> Boundary {
+        if self.is_perioditic(axis) {
+            Boundary::Perioditic
+        } else {
+            Boundary::Open
+        }
+    }
+
+    /// Checks if an axis wraps around.
+    #[inline]
+    pub fn is_perioditic(&self, axis: usize) -> bool {
+        self.perioditic & (1 << axis) != 0
+    }
+
+    /// Sets the length of an axis in (0, 1] so that the domain is [0, extent) along it.
+    /// The extent can be set for the first 8 axes.
+    pub fn with_extent(mut self, axis: usize, extent: f64) -> Self {
+        assert!(axis < MAX_EXTENTS);
+        assert!(0. < extent && extent <= 1.);
+        self.extents[axis] = extent;
+        self
+    }
+
+    /// Returns the length of an axis.
+    #[inline]
+    pub fn extent(&self, axis: usize) -> f64 {
+        self.extents.get(axis).copied().unwrap_or(1.)
+    }
+
+    /// Checks if every axis is as long as the unit.
+    pub fn is_unit(&self) -> bool {
+        self.extents.iter().all(|&e| e == 1.)
+    }
+
+    /// Returns the volume of the domain in given dimension.
+    pub fn volume(&self, dim: usize) -> f64 {
+        (0..dim).map(|n| self.extent(n)).product()
+    }
+
+    /// Returns the amount of grid cells along an axis when there are `side` cells along the unit.
+    /// The last cell is cut short when the extent isn't a whole amount of cells.
+    #[inline]
+    pub(crate) fn cells(&self, axis: usize, side: usize) -> usize {
+        let extent = self.extent(axis);
+        if extent == 1. {
+            side
+        } else {
+            ((extent * side as f64).ceil() as usize).clamp(1, side)
+        }
+    }
+
+    /// Checks if any perioditic axis among the first `dim` ends in a cut short cell when there are `side` cells
+    /// along the unit. The short cell wraps onto the first one, so searches across the seam cover less distance
+    /// than the same amount of whole cells would and have to go one cell further.
+    pub(crate) fn has_short_seam(&self, dim: usize, side: usize) -> bool {
+        (0..dim).any(|n| self.is_perioditic(n) && self.cells(n, side) as f64 != self.extent(n) * side as f64)
+    }
+
+    /// Checks if the position is inside the extent of every axis.
+    pub(crate) fn contains<F, V>(&self, position: &V) -> bool
+    where
+        F: Float,
+        V: Vector<F>,
+    {
+        (0..V::dimension())
+            .all(|n| F::cast(0) <= position[n] && position[n] < self.extent_of::<F>(n))
+    }
+
+    /// Returns the length of an axis as a float.
+    #[inline]
+    pub(crate) fn extent_of<F>(&self, axis: usize) -> F
+    where
+        F: Float,
+    {
+        NumCast::from(self.extent(axis)).expect("f64 should be castable to float.")
+    }
+
+    /// Returns the type closest to the domain in given dimension.
+    /// The domain is perioditic only if every axis wraps around.
+    pub fn poisson_type(&self, dim: usize) -> Type {
+        if (0..dim).all(|n| self.is_perioditic(n)) {
+            Type::Perioditic
+        } else {
+            Type::Normal
+        }
+    }
+}
+
+impl From<Type> for Domain {
+    fn from(poisson_type: Type) -> Self {
+        match poisson_type {
+            Type::Normal => Domain::open(),
+            Type::Perioditic => Domain::perioditic(),
+        }
+    }
+}
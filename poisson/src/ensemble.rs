@@ -0,0 +1,79 @@
+//! Module that contains generation of multiple independent distributions.
+
+use crate::algorithm::Creator;
+use crate::{Builder, Float, Generator, PointSet, SeedTree, Vector};
+
+use rand::{Rng, SeedableRng};
+
+use std::marker::PhantomData;
+
+/// Iterator over independent poisson-disk distributions generated with the same parameters.
+/// Each distribution is generated with its own random number generator seeded from the child of `SeedTree`
+/// with the index of the distribution, with the root seed drawn from the random number generator given.
+#[derive(Clone, Debug)]
+pub struct Ensemble<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + SeedableRng + Clone,
+    A: Creator<F, V>,
+{
+    poisson: Builder<F, V>,
+    tree: SeedTree,
+    index: usize,
+    count: usize,
+    _rng: PhantomData<R>,
+    _algo: PhantomData<A>,
+}
+
+impl<F, V, R, A> Ensemble<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + SeedableRng + Clone,
+    A: Creator<F, V>,
+{
+    pub(crate) fn new(poisson: Builder<F, V>, mut rng: R, count: usize) -> Self {
+        Ensemble {
+            poisson,
+            tree: SeedTree::from_rng(&mut rng),
+            index: 0,
+            count,
+            _rng: PhantomData,
+            _algo: PhantomData,
+        }
+    }
+}
+
+impl<F, V, R, A> Iterator for Ensemble<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + SeedableRng + Clone,
+    A: Creator<F, V>,
+{
+    type Item = PointSet<F, V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.count {
+            return None;
+        }
+        let rng = self.tree.child(self.index as u64).rng::<R>();
+        self.index += 1;
+        Some(Generator::<F, V, R, A>::new(self.poisson.clone(), rng).generate_set())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.count - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<F, V, R, A> ExactSizeIterator for Ensemble<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + SeedableRng + Clone,
+    A: Creator<F, V>,
+{
+}
@@ -0,0 +1,34 @@
+//! Module that contains errors for parameters that can't be generated with.
+
+use std::error::Error;
+use std::fmt;
+
+/// Error for a separation distance or a disk radius that no distribution can be generated with.
+/// The value is kept as `f64` so that the error doesn't depend on the float type of the builder.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RadiusError {
+    /// The separation distance was zero or negative.
+    NonPositiveSeparation(f64),
+    /// The disk radius was zero or negative.
+    NonPositiveRadius(f64),
+    /// The separation distance or the disk radius was infinite or NaN.
+    NotFinite(f64),
+}
+
+impl fmt::Display for RadiusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RadiusError::NonPositiveSeparation(d) => {
+                write!(f, "Separation distance should be larger than 0, got {}", d)
+            }
+            RadiusError::NonPositiveRadius(r) => {
+                write!(f, "Disk radius should be larger than 0, got {}", r)
+            }
+            RadiusError::NotFinite(v) => {
+                write!(f, "Separation distance and disk radius should be finite, got {}", v)
+            }
+        }
+    }
+}
+
+impl Error for RadiusError {}
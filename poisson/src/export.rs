@@ -0,0 +1,163 @@
+//! Module that contains exporting distributions for shaders.
+
+use crate::algorithm::Creator;
+use crate::{Float, Generator, PointSet, Vector};
+
+use rand::Rng;
+
+use std::fmt::Write;
+use std::io::{self, Seek, SeekFrom};
+
+/// Magic bytes at the start of the binary buffers written with `PointSet::to_buffer`.
+pub const BUFFER_MAGIC: [u8; 4] = *b"PDSK";
+
+/// Amount of samples `Generator::generate_to_writer` collects before writing them.
+const WRITE_CHUNK: usize = 1 << 16;
+
+/// Returns the coordinates of the sample as `f32`, which is what shaders use.
+fn coordinates<F, V>(p: &V) -> Vec<f32>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    (0..V::dimension())
+        .map(|n| p[n].to_f32().expect("Coordinate should be castable to f32."))
+        .collect()
+}
+
+/// Writes the coordinates separated by commas.
+fn join(coordinates: &[f32]) -> String {
+    coordinates
+        .iter()
+        .map(|c| format!("{:?}", c))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl<F, V> PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Writes the samples as a WGSL constant array with given name, ready to be included in a shader.
+    /// Samples are `vecN<f32>` for two to four dimensions and `f32` for one.
+    pub fn to_wgsl(&self, name: &str) -> String {
+        let dim = V::dimension();
+        assert!(dim <= 4, "Shaders have vectors of at most four dimensions.");
+        let element = match dim {
+            1 => "f32".to_owned(),
+            _ => format!("vec{}<f32>", dim),
+        };
+        let array = format!("array<{}, {}>", element, self.len());
+        let mut wgsl = format!("const {}: {} = {}(\n", name, array, array);
+        for p in self {
+            writeln!(wgsl, "    {}({}),", element, join(&coordinates(p)))
+                .expect("Writing to string can't fail.");
+        }
+        wgsl.push_str(");\n");
+        wgsl
+    }
+
+    /// Writes the samples as a GLSL constant array with given name, ready to be included in a shader.
+    /// Samples are `vecN` for two to four dimensions and `float` for one.
+    pub fn to_glsl(&self, name: &str) -> String {
+        let dim = V::dimension();
+        assert!(dim <= 4, "Shaders have vectors of at most four dimensions.");
+        let element = match dim {
+            1 => "float".to_owned(),
+            _ => format!("vec{}", dim),
+        };
+        let mut glsl = format!(
+            "const {} {}[{}] = {}[{}](\n",
+            element,
+            name,
+            self.len(),
+            element,
+            self.len()
+        );
+        for (i, p) in self.iter().enumerate() {
+            let separator = if i + 1 < self.len() { "," } else { "" };
+            writeln!(glsl, "    {}({}){}", element, join(&coordinates(p)), separator)
+                .expect("Writing to string can't fail.");
+        }
+        glsl.push_str(");\n");
+        glsl
+    }
+
+    /// Writes the samples into a little-endian binary buffer for uploading to the GPU.
+    /// The buffer starts with a 16 byte header of `BUFFER_MAGIC`, the dimension and the amount of samples as `u32`
+    /// and the radius as `f32`, followed by the coordinates of the samples as tightly packed `f32`s.
+    pub fn to_buffer(&self) -> Vec<u8> {
+        let dim = V::dimension();
+        let mut buffer = Vec::with_capacity(16 + 4 * dim * self.len());
+        buffer.extend_from_slice(&BUFFER_MAGIC);
+        buffer.extend_from_slice(&(dim as u32).to_le_bytes());
+        buffer.extend_from_slice(&(self.len() as u32).to_le_bytes());
+        let radius = self
+            .radius()
+            .to_f32()
+            .expect("Radius should be castable to f32.");
+        buffer.extend_from_slice(&radius.to_le_bytes());
+        for p in self {
+            for c in coordinates(p) {
+                buffer.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+        buffer
+    }
+}
+
+impl<F, V, R, A> Generator<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + Clone,
+    A: Creator<F, V>,
+{
+    /// Generates the samples straight into the writer in the format of `PointSet::to_buffer`,
+    /// writing them in chunks of fixed size as they are accepted instead of collecting the whole distribution first.
+    /// This allows distributions with more samples than fit into memory twice, such as mock catalogs of galaxies.
+    /// The algorithms still keep the samples for checking distances, which `Precision::Single` makes smaller.
+    ///
+    /// The amount of samples in the header is known only at the end so it's written as 0 and filled in
+    /// once the samples are written, which is why the writer has to be seekable, like a file that is
+    /// memory-mapped afterwards. The writer is left at the end of the buffer.
+    /// Returns the amount of samples written.
+    pub fn generate_to_writer<W>(&self, mut writer: W) -> io::Result<usize>
+    where
+        W: io::Write + Seek,
+    {
+        let dim = V::dimension();
+        let start = writer.stream_position()?;
+        let radius = self
+            .radius()
+            .to_f32()
+            .expect("Radius should be castable to f32.");
+        writer.write_all(&BUFFER_MAGIC)?;
+        writer.write_all(&(dim as u32).to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&radius.to_le_bytes())?;
+        let mut chunk = Vec::with_capacity(4 * dim * WRITE_CHUNK);
+        let mut count = 0;
+        for p in self.clone() {
+            for c in coordinates(&p) {
+                chunk.extend_from_slice(&c.to_le_bytes());
+            }
+            count += 1;
+            if count % WRITE_CHUNK == 0 {
+                writer.write_all(&chunk)?;
+                chunk.clear();
+            }
+        }
+        writer.write_all(&chunk)?;
+        let amount = u32::try_from(count).map_err(|_| {
+            io::Error::new(io::ErrorKind::InvalidData, "Too many samples for the buffer format.")
+        })?;
+        let end = writer.stream_position()?;
+        writer.seek(SeekFrom::Start(start + 8))?;
+        writer.write_all(&amount.to_le_bytes())?;
+        writer.seek(SeekFrom::Start(end))?;
+        writer.flush()?;
+        Ok(count)
+    }
+}
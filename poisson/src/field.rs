@@ -0,0 +1,276 @@
+//! Module that contains fields that vary the distribution over the sampling space.
+
+use crate::{Float, Vector};
+
+use num_traits::Float as NumFloat;
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Vector field given as a callback from position to vector.
+#[derive(Clone)]
+pub struct VectorField<V>(Arc<dyn Fn(&V) -> V + Send + Sync>);
+
+impl<V> VectorField<V> {
+    /// Creates vector field from a callback.
+    pub fn new<T>(field: T) -> Self
+    where
+        T: Fn(&V) -> V + Send + Sync + 'static,
+    {
+        VectorField(Arc::new(field))
+    }
+
+    /// Evaluates the field at the position.
+    pub fn at(&self, position: &V) -> V {
+        (self.0)(position)
+    }
+}
+
+impl<V> fmt::Debug for VectorField<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VectorField")
+    }
+}
+
+impl<V> PartialEq for VectorField<V> {
+    /// Fields are equal only if they share the same callback.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Exclusion zone that is an ellipsoid aligned with the direction of a vector field.
+///
+/// The exclusion zone extends `along` times the radius in the direction of the field and `across` times
+/// the radius perpendicular to it. Both scales are in ]0, 1] so the zone always fits inside the isotropic one.
+/// Where the field is zero the zone is a ball scaled by `across`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Anisotropy<F, V> {
+    /// Field whose direction the exclusion zones are aligned with.
+    pub field: VectorField<V>,
+    /// Scale of the exclusion zone in the direction of the field.
+    pub along: F,
+    /// Scale of the exclusion zone perpendicular to the field.
+    pub across: F,
+}
+
+impl<F, V> Anisotropy<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Returns the smallest scale of the exclusion zone.
+    pub fn min_scale(&self) -> F {
+        if self.along < self.across {
+            self.along
+        } else {
+            self.across
+        }
+    }
+
+    /// Returns squared length of the difference scaled by the exclusion zone at the position.
+    pub(crate) fn sqdist(&self, position: &V, diff: V) -> F {
+        let mut dir = self.field.at(position);
+        let norm = dir.norm();
+        if norm > F::cast(0) {
+            dir *= F::cast(1) / norm;
+        }
+        let mut along = F::cast(0);
+        for n in 0..V::dimension() {
+            along += diff[n] * dir[n];
+        }
+        let across = (diff - dir * along).norm_squared();
+        along * along / (self.along * self.along) + across / (self.across * self.across)
+    }
+}
+
+/// Rule for the distance between samples from regions with different radii.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Spacing {
+    /// Samples are at least the sum of their radii apart so that their disks don't overlap.
+    #[default]
+    Sum,
+    /// Samples are at least twice the larger of their radii apart.
+    Larger,
+    /// Samples are at least twice the smaller of their radii apart.
+    Smaller,
+}
+
+impl Spacing {
+    /// Returns the minimum distance between samples with given radii.
+    pub fn distance<F>(&self, r1: F, r2: F) -> F
+    where
+        F: Float,
+    {
+        match *self {
+            Spacing::Sum => r1 + r2,
+            Spacing::Larger => F::cast(2) * if r1 < r2 { r2 } else { r1 },
+            Spacing::Smaller => F::cast(2) * if r1 < r2 { r1 } else { r2 },
+        }
+    }
+}
+
+/// Partition of the sampling space into labelled regions each with its own radius.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regions<F, V> {
+    labels: Labels<V>,
+    radii: Vec<F>,
+    spacing: Spacing,
+}
+
+/// Labeling of the sampling space given as a callback from position to label.
+#[derive(Clone)]
+struct Labels<V>(Arc<dyn Fn(&V) -> usize + Send + Sync>);
+
+impl<V> fmt::Debug for Labels<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Labels")
+    }
+}
+
+impl<V> PartialEq for Labels<V> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<F, V> Regions<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates regions from a labeling and radii indexed by the labels.
+    pub fn new<T>(labels: T, radii: &[F], spacing: Spacing) -> Self
+    where
+        T: Fn(&V) -> usize + Send + Sync + 'static,
+    {
+        assert!(!radii.is_empty());
+        assert!(radii.iter().all(|&r| F::cast(0) < r));
+        Regions {
+            labels: Labels(Arc::new(labels)),
+            radii: radii.to_vec(),
+            spacing,
+        }
+    }
+
+    /// Returns the label of the region the position is in.
+    pub fn label(&self, position: &V) -> usize {
+        (self.labels.0)(position)
+    }
+
+    /// Returns the radius of the region the position is in.
+    pub fn radius_at(&self, position: &V) -> F {
+        *self
+            .radii
+            .get(self.label(position))
+            .expect("Each label should have a radius.")
+    }
+
+    /// Returns the radii of the regions indexed by their labels.
+    pub fn radii(&self) -> &[F] {
+        &self.radii
+    }
+
+    /// Returns the smallest radius of the regions.
+    pub fn min_radius(&self) -> F {
+        self.radii
+            .iter()
+            .cloned()
+            .fold(self.radii[0], |a, b| if b < a { b } else { a })
+    }
+
+    /// Returns the largest radius of the regions.
+    pub fn max_radius(&self) -> F {
+        self.radii
+            .iter()
+            .cloned()
+            .fold(self.radii[0], |a, b| if a < b { b } else { a })
+    }
+
+    /// Returns the rule for the distance between samples from different regions.
+    pub fn spacing(&self) -> Spacing {
+        self.spacing
+    }
+
+    /// Returns the minimum distance between the samples.
+    pub fn distance(&self, v1: &V, v2: &V) -> F {
+        self.spacing
+            .distance(self.radius_at(v1), self.radius_at(v2))
+    }
+}
+
+/// Creates labeling from a raster of labels covering [0, 1)<sup>d</sup>.
+/// The raster has `resolution[n]` cells along the n:th axis and the first axis varies fastest in it.
+pub fn raster_labels<F, V>(
+    labels: Vec<usize>,
+    resolution: &[usize],
+) -> impl Fn(&V) -> usize + Send + Sync + 'static
+where
+    F: Float,
+    V: Vector<F>,
+{
+    assert_eq!(V::dimension(), resolution.len());
+    assert_eq!(resolution.iter().product::<usize>(), labels.len());
+    let resolution = resolution.to_vec();
+    move |v: &V| {
+        let mut index = 0;
+        for n in (0..resolution.len()).rev() {
+            let cell = (v[n] * F::cast(resolution[n]))
+                .to_usize()
+                .unwrap_or(0)
+                .min(resolution[n] - 1);
+            index = index * resolution[n] + cell;
+        }
+        labels[index]
+    }
+}
+
+/// Mapping of the values of a density image or scalar field to the tones the radii are interpolated by.
+///
+/// Linear mapping keeps the values as they are, but as the amount of samples in an area falls with the square
+/// of the radius, the mid tones come out too light and the stipples look washed out.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ToneMapping<F> {
+    /// Values are used as they are.
+    #[default]
+    Linear,
+    /// Values are raised to the power, so powers above 1 darken the mid tones and below 1 lighten them.
+    Gamma(F),
+    /// Values are replaced by the fraction of the field that is at most as bright, which spreads them evenly
+    /// over [0, 1] so that each tone covers as much of the field as the others.
+    Equalized,
+}
+
+/// Maps the values of a field to tones with the mapping.
+/// The values have to be in [0, 1] and the tones are in it too.
+pub fn tone_map<F>(values: &[F], mapping: ToneMapping<F>) -> Vec<F>
+where
+    F: Float,
+{
+    assert!(values.iter().all(|&v| F::cast(0) <= v && v <= F::cast(1)));
+    match mapping {
+        ToneMapping::Linear => values.to_vec(),
+        ToneMapping::Gamma(gamma) => {
+            assert!(F::cast(0) < gamma);
+            values.iter().map(|&v| NumFloat::powf(v, gamma)).collect()
+        }
+        ToneMapping::Equalized => {
+            let mut sorted = values.to_vec();
+            sorted.sort_by(|a, b| a.partial_cmp(b).expect("Values should be comparable."));
+            let cdf = |v: F| F::cast(sorted.partition_point(|&s| s <= v));
+            let lowest = match sorted.first() {
+                Some(&v) => cdf(v),
+                None => return vec![],
+            };
+            let total = F::cast(sorted.len());
+            if lowest == total {
+                // Field with a single value has no histogram to spread so it's kept as it is.
+                return values.to_vec();
+            }
+            values
+                .iter()
+                .map(|&v| (cdf(v) - lowest) / (total - lowest))
+                .collect()
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Module that contains generation of layers of distributions where earlier layers carve into later ones.
+
+use crate::algorithm::Creator;
+use crate::{Builder, Float, PointSet, SeedTree, Vector};
+
+use rand::{Rng, SeedableRng};
+
+/// Layers of poisson-disk distributions generated in order of priority, such as landmarks first, trees second and
+/// grass third. Each layer is generated with its own parameters and the samples of the layers before it restricting
+/// it, so that the disks of samples of different layers don't overlap either.
+///
+/// Each layer is generated with its own random number generator seeded from the child of `SeedTree` with the index
+/// of the layer, with the root seed drawn from the random number generator given. Adding a layer to the end
+/// therefore leaves the layers before it as they were.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, Layers, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// type Vec2 = na::Vector2<f64>;
+/// let layers = Layers::new()
+///     .with_layer(Builder::<_, Vec2>::with_disk_radius(0.1, Type::Normal))
+///     .with_layer(Builder::with_disk_radius(0.02, Type::Normal))
+///     .generate(SmallRng::seed_from_u64(1), algorithm::Bridson);
+/// let (trees, grass) = (&layers[0], &layers[1]);
+/// assert!(grass.iter().all(|g| trees.iter().all(|t| (g - t).norm() >= 0.1 + 0.02)));
+/// ````
+#[derive(Clone, Debug, Default)]
+pub struct Layers<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    layers: Vec<Builder<F, V>>,
+}
+
+impl<F, V> Layers<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates layers without any layers.
+    pub fn new() -> Self {
+        Layers { layers: vec![] }
+    }
+
+    /// Adds a layer below the layers added before it.
+    /// Every layer has to have the same domain.
+    pub fn with_layer(mut self, layer: Builder<F, V>) -> Self {
+        assert!(
+            self.layers.iter().all(|l| l.domain == layer.domain),
+            "Every layer should have the same domain."
+        );
+        self.layers.push(layer);
+        self
+    }
+
+    /// Returns the builders of the layers from the highest priority to the lowest.
+    pub fn layers(&self) -> &[Builder<F, V>] {
+        &self.layers
+    }
+
+    /// Generates the layers from the highest priority to the lowest, returning a distribution for each.
+    /// The samples of earlier layers are checked one by one when they have larger radius than the layer,
+    /// so those layers are meant to be sparse.
+    pub fn generate<R, A>(&self, mut rng: R, algo: A) -> Vec<PointSet<F, V>>
+    where
+        R: Rng + SeedableRng + Clone,
+        A: Creator<F, V>,
+    {
+        let tree = SeedTree::from_rng(&mut rng);
+        let mut sets: Vec<PointSet<F, V>> = vec![];
+        for (i, layer) in self.layers.iter().enumerate() {
+            let generator = layer.clone().build(tree.child(i as u64).rng::<R>(), algo);
+            let mut iter = generator.clone().into_iter();
+            for set in &sets {
+                for p in set {
+                    // The exclusion zone of the layer alone keeps the disks apart unless the earlier disk is larger.
+                    if set.radius() > layer.radius {
+                        iter.restrict_with_radius(p.clone(), set.radius());
+                    } else {
+                        iter.restrict(p.clone());
+                    }
+                }
+            }
+            let points = iter.by_ref().collect();
+            sets.push(generator.set(points, &iter));
+        }
+        sets
+    }
+}
@@ -0,0 +1,88 @@
+//! Module that contains custom rules for which candidates can be accepted.
+
+use crate::{Float, Vector};
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Custom rule that candidates have to pass on top of the distance between samples, the shape and the obstacles,
+/// such as line-of-sight between samples or reachability on a navigation mesh.
+///
+/// The candidate is passed with the accepted samples in the cells of the acceleration grid around it, which include
+/// every sample closer than twice the radius, or with all of the samples for `algorithm::Sparse`. The check is
+/// made only for candidates that pass the built-in checks, so it can be slower than them. Closures taking the
+/// candidate and its neighbours implement the trait.
+///
+/// The candidates a check rejects can form any region, but `algorithm::Ebeida` judges its cells by their corners
+/// and drops the ones whose corners are all rejected only if `rejects_boxes` tells that the region is convex.
+/// Otherwise it throws darts into such cells after the rest are filled, so legal spots between rejected corners
+/// still get samples at the cost of more rejected candidates.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// type Vec2 = na::Vector2<f64>;
+/// // Samples on different sides of the wall at x = 0.5 can't see each other so they have to be farther apart.
+/// let hidden = |c: &Vec2, near: &[Vec2]| {
+///     near.iter().all(|p| (p.x < 0.5) == (c.x < 0.5) || (p - c).norm() >= 0.12)
+/// };
+/// let samples = Builder::<_, Vec2>::with_disk_radius(0.05, Type::Normal)
+///     .with_legality_check(hidden)
+///     .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+///     .generate();
+/// assert!(!samples.is_empty());
+/// ````
+pub trait LegalityCheck<F, V>: Send + Sync
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Checks if the candidate can be accepted next to the samples near it.
+    fn is_legal(&self, candidate: &V, neighbours: &[V]) -> bool;
+
+    /// Returns whether rejecting every corner of a box means that the check rejects all of the box next to the same
+    /// samples, as when the candidates it rejects form a convex region. `algorithm::Ebeida` drops the cells whose
+    /// corners such checks reject, while it throws darts into the others at the end. False by default.
+    fn rejects_boxes(&self) -> bool {
+        false
+    }
+}
+
+impl<F, V, T> LegalityCheck<F, V> for T
+where
+    F: Float,
+    V: Vector<F>,
+    T: Fn(&V, &[V]) -> bool + Send + Sync,
+{
+    fn is_legal(&self, candidate: &V, neighbours: &[V]) -> bool {
+        self(candidate, neighbours)
+    }
+}
+
+/// Legality check shared between clones of the builder.
+#[derive(Clone)]
+pub(crate) struct Check<F, V>(pub(crate) Arc<dyn LegalityCheck<F, V>>)
+where
+    F: Float,
+    V: Vector<F>;
+
+impl<F, V> fmt::Debug for Check<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LegalityCheck")
+    }
+}
+
+impl<F, V> PartialEq for Check<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
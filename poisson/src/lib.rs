@@ -13,6 +13,11 @@
 //! as source distribution for digital stipling,
 //! as distribution for sampling in rendering or for (re)meshing.
 //!
+//! Samples can have any dimension and generation is tested from 2 to 6 dimensions, such as with `Vector2` to
+//! `Vector6` of nalgebra. The cost grows exponentially with the dimension, so in 5 and 6 dimensions maximal
+//! distributions of a few hundred samples already take minutes to generate with `algorithm::Ebeida`.
+//! The radius for an amount of samples is known for normal distributions only up to 4 dimensions.
+//!
 //! # Examples
 //!
 //! Generate non-tiling poisson-disk distribution in [0, 1)<sup>2</sup> with disk radius 0.1
@@ -26,15 +31,19 @@
 //! use rand::{SeedableRng, rng};
 //! use rand::rngs::SmallRng;
 //!
+//! # #[cfg(feature = "full")]
 //! use poisson::{Builder, Type, algorithm};
 //!
+//! # #[cfg(feature = "full")]
 //! fn main() {
 //!     let poisson =
-//!         Builder::<_, na::Vector2<f64>>::with_radius(0.1, Type::Normal)
+//!         Builder::<_, na::Vector2<f64>>::with_disk_radius(0.1, Type::Normal)
 //!             .build(SmallRng::from_rng(&mut rng()), algorithm::Ebeida);
 //!     let samples = poisson.generate();
 //!     println!("{:?}", samples);
 //! }
+//! # #[cfg(not(feature = "full"))]
+//! # fn main() {}
 //! ````
 //!
 //! Generate tiling poisson-disk distribution in [0, 1)<sup>3</sup> with approximately 100 samples
@@ -42,10 +51,12 @@
 //!
 //! ````rust
 //! # extern crate nalgebra as na;
+//! # #[cfg(feature = "full")]
 //! # use poisson::{Builder, Type, algorithm};
 //! # use rand::{SeedableRng, rng};
 //! # use rand::rngs::SmallRng;
 //!
+//! # #[cfg(feature = "full")]
 //! fn main() {
 //!     let poisson =
 //!         Builder::<_, na::Vector3<f32>>::with_samples(100, 0.9, Type::Perioditic)
@@ -54,44 +65,145 @@
 //!         println!("{:?}", sample)
 //!     }
 //! }
+//! # #[cfg(not(feature = "full"))]
+//! # fn main() {}
 //! ````
 
-use rand::Rng;
+/// Declares items that need the default `full` feature, which is everything except the `minimal` module and `Type`.
+macro_rules! cfg_full {
+    ($($item:item)*) => {
+        $(
+            #[cfg(feature = "full")]
+            $item
+        )*
+    };
+}
+
+cfg_full! {
+    use rand::{Rng, SeedableRng};
 
-use num_traits::Float as NumFloat;
-use num_traits::{NumCast, Zero};
+    use num_traits::Float as NumFloat;
+    use num_traits::{NumCast, Zero};
 
-use alga::general::RealField;
-use alga::linear::{FiniteDimVectorSpace, NormedSpace};
+    use alga::general::RealField;
+    use alga::linear::{FiniteDimVectorSpace, NormedSpace};
 
+    use sphere::sphere_volume;
+}
+
+#[cfg(feature = "full")]
 #[macro_use]
 extern crate lazy_static;
 
-use std::marker::PhantomData;
-use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+cfg_full! {
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::mem;
+    use std::ops::{AddAssign, DivAssign, MulAssign, SubAssign};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use crate::algorithm::{ActivePolicy, Algorithm, Creator, Selected};
+    use crate::blocker::{Blocker, Blockers, Segment};
+    use crate::field::{
+        raster_labels, tone_map, Anisotropy, Regions, Spacing, ToneMapping, VectorField,
+    };
+    use crate::legality::Check;
+    use crate::shape::{Parametric, Restriction};
+    use crate::utils::math::{calc_radius, max_radius, maximal_density};
+    use crate::utils::{conflicts, sample_to_index, Grid};
 
-use crate::algorithm::{Algorithm, Creator};
-use crate::utils::math::calc_radius;
+    pub use crate::blocker::BlockerId;
+    pub use crate::boundary::DualSamples;
+    pub use crate::buffers::Buffers;
+    pub use crate::ccvt::Ccvt;
+    pub use crate::clusters::Clusters;
+    pub use crate::distribution::PoissonDisk;
+    pub use crate::domain::{Boundary, Domain};
+    pub use crate::ensemble::Ensemble;
+    pub use crate::error::RadiusError;
+    pub use crate::layers::Layers;
+    pub use crate::legality::LegalityCheck;
+    pub use crate::export::BUFFER_MAGIC;
+    pub use crate::manifest::Manifest;
+    pub use crate::mesh::MeshSeeds;
+    pub use crate::mip::MipChain;
+    pub use crate::occupancy::OccupancyImage;
+    pub use crate::plan::Plan;
+    pub use crate::rejection::Rejection;
+    pub use crate::sample::Sample;
+    pub use crate::seed::{Seed, SeedTree};
+    pub use crate::set::{Conflict, MergePolicy, PointSet, SetDiff};
+    pub use crate::simple::{sample_2d, sample_3d};
+    pub use crate::stats::GenerationStats;
+    pub use crate::trace::{Trace, TraceEvent};
+    pub use crate::tuning::Tuning;
+    pub use crate::volume::VolumeSampler;
 
-pub mod algorithm;
-mod utils;
+    pub mod algorithm;
+    pub mod array;
+    mod blocker;
+    mod boundary;
+    mod buffers;
+    mod ccvt;
+    mod clusters;
+    mod distribution;
+    mod domain;
+    mod ensemble;
+    mod error;
+    mod export;
+    pub mod field;
+    mod layers;
+    mod legality;
+    mod manifest;
+    mod mesh;
+    mod mip;
+    mod occupancy;
+    mod plan;
+    #[cfg(any(feature = "cgmath", feature = "euclid"))]
+    pub mod mirror;
+    #[cfg(feature = "noise")]
+    pub mod noise;
+    pub mod presets;
+    mod rejection;
+    mod sample;
+    mod seed;
+    mod set;
+    pub mod shape;
+    mod simple;
+    mod stats;
+    mod trace;
+    mod tuning;
+    mod utils;
+    pub mod verify;
+    mod volume;
+}
+
+pub mod minimal;
 
 /// Describes what floats are.
+/// Implemented for the floats alga implements `RealField` for, which are `f32` and `f64`.
+/// Constants and random values are converted to the float with as many bits as it represents exactly,
+/// so nothing assumes the precision of `f64`.
+#[cfg(feature = "full")]
 pub trait Float: NumFloat + RealField + AddAssign + SubAssign + MulAssign + DivAssign {
     /// Casts usize to float.
     fn cast(n: usize) -> Self {
         NumCast::from(n).expect("Casting usize to float should always succeed.")
     }
 }
+#[cfg(feature = "full")]
 impl<T> Float for T where T: NumFloat + RealField + AddAssign + SubAssign + MulAssign + DivAssign {}
 
 /// Describes what vectors are.
+#[cfg(feature = "full")]
 pub trait Vector<F>:
     Zero + FiniteDimVectorSpace + NormedSpace<ComplexField = F, RealField = F>
 where
     F: Float,
 {
 }
+#[cfg(feature = "full")]
 impl<T, F> Vector<F> for T
 where
     F: Float,
@@ -110,8 +222,74 @@ pub enum Type {
     Perioditic,
 }
 
+/// Precision of the samples stored in the background grid that is used for checking distances between samples.
+///
+/// Lower precision cuts the memory the grid takes, but the stored samples are rounded which weakens the separation
+/// guarantee: samples are at least 2 * radius - √d * ε apart where ε is the largest rounding error of
+/// a coordinate in [0, 1). Storing samples in precision wider than the float type gains nothing.
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Precision {
+    /// Stores samples as they are.
+    #[default]
+    Full,
+    /// Stores coordinates of the samples as `f32`, where ε is 2<sup>-25</sup>.
+    Single,
+    /// Stores coordinates of the samples as `f16`, where ε is 2<sup>-12</sup>.
+    #[cfg(feature = "half")]
+    Half,
+}
+
+/// Amount of work a single step of generation may do with `PoissonIter::step`.
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Budget {
+    /// Generates at most this many samples.
+    Points(usize),
+    /// Generates samples until this much time has passed.
+    /// The time is checked between samples so a step can overrun it by the time of generating a single sample.
+    Time(Duration),
+}
+
+/// Policy for start points that conflict with each other.
+/// A start point conflicts if it is closer to an earlier start point than the exclusion zones allow,
+/// which `Builder::conflicting_start_points` reports.
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PrefillPolicy {
+    /// Keeps every start point, so the distribution isn't legal where they conflict.
+    #[default]
+    KeepAll,
+    /// Leaves out the start points that conflict with an earlier start point.
+    RejectConflicting,
+    /// Panics when the generator is built if any start points conflict.
+    Panic,
+}
+
+/// What is done with start points and restricted samples outside the domain on the axes that wrap around.
+/// Prefill data for tileable domains often comes in the coordinates of the neighbouring tiles,
+/// and wrapping it into the domain keeps it in the acceleration grid instead of checking it one by one.
+#[cfg(feature = "full")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    /// Wraps the points that are in the neighbouring tiles and panics on points farther away,
+    /// as those are more likely in wrong coordinates than meant to wrap around.
+    #[default]
+    Strict,
+    /// Wraps the points however far outside the domain they are.
+    Loose,
+}
+
+/// Default amount of consecutive rejected samples after which dart throwing stops.
+#[cfg(feature = "full")]
+pub const DEFAULT_REJECTION_LIMIT: usize = 1000;
+
+/// Default amount of samples Bridson tries around an active sample before deactivating it.
+#[cfg(feature = "full")]
+pub const DEFAULT_ATTEMPTS: usize = 30;
 
 /// Builder for the generator.
+#[cfg(feature = "full")]
 #[derive(Default, Clone, Debug, PartialEq)]
 pub struct Builder<F, V>
 where
@@ -119,78 +297,895 @@ where
     V: Vector<F>,
 {
     radius: F,
-    poisson_type: Type,
+    domain: Domain,
+    active_policy: ActivePolicy<V>,
+    start_points: Vec<V>,
+    emit_start_points: bool,
+    fixed_points: usize,
+    prefill_policy: PrefillPolicy,
+    wrap: Wrap,
+    rejection_limit: usize,
+    max_depth: Option<usize>,
+    jitter: F,
+    anisotropy: Option<Anisotropy<F, V>>,
+    radii: Option<V>,
+    precision: Precision,
+    buffers: Option<Buffers<V>>,
+    cell_scale: F,
+    attempts: usize,
+    exact: bool,
+    trace: bool,
+    max_points: Option<usize>,
+    regions: Option<Regions<F, V>>,
+    boundary: bool,
+    shape: Option<Restriction<F, V>>,
+    blockers: Blockers<F, V>,
+    clearances: Blockers<F, V>,
+    segments: Vec<Segment<F, V>>,
+    checks: Vec<Check<F, V>>,
+    maximal: bool,
     _marker: PhantomData<V>,
 }
 
+#[cfg(feature = "full")]
 impl<V, F> Builder<F, V>
 where
     F: Float,
     V: Vector<F>,
 {
-    /// New Builder with type of distribution and radius specified.
-    /// The radius should be ]0, √2 / 2]
-    pub fn with_radius(radius: F, poisson_type: Type) -> Self {
-        assert!(F::cast(0) < radius);
-        assert!(
-            radius
-                <= NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work.")
-        );
-        Builder {
-            radius,
-            poisson_type,
-            _marker: PhantomData,
+    /// New Builder with domain or type of distribution and radius specified.
+    /// The radius is half of the minimum distance between samples, which is easy to mix up with the distance itself,
+    /// so `with_disk_radius` or `with_separation` should be used to say which one is meant.
+    #[deprecated(note = "Use `with_disk_radius`, or `with_separation` for the minimum distance between samples.")]
+    pub fn with_radius<D>(radius: F, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        Builder::with_disk_radius(radius, domain)
+    }
+
+    /// New Builder with domain or type of distribution and radius of the disks around samples specified,
+    /// so that samples are at least twice the radius apart.
+    /// The radius should be larger than 0.
+    /// Above √2 / 2 at most one sample fits into the domain.
+    pub fn with_disk_radius<D>(radius: F, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        Builder::try_with_disk_radius(radius, domain).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// New Builder with domain or type of distribution and minimum distance between samples specified,
+    /// which is twice the radius of the disks around them.
+    /// The separation should be larger than 0.
+    pub fn with_separation<D>(separation: F, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        Builder::try_with_separation(separation, domain).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// New Builder with minimum distance between samples like `with_separation`,
+    /// returning an error instead of panicking if no distribution can be generated with it.
+    pub fn try_with_separation<D>(separation: F, domain: D) -> Result<Self, RadiusError>
+    where
+        D: Into<Domain>,
+    {
+        let value = separation.to_f64().unwrap_or(f64::NAN);
+        if !NumFloat::is_finite(separation) {
+            return Err(RadiusError::NotFinite(value));
         }
+        if separation <= F::cast(0) {
+            return Err(RadiusError::NonPositiveSeparation(value));
+        }
+        Builder::try_with_disk_radius(separation / F::cast(2), domain)
     }
 
-    /// New Builder with type of distribution and relative radius specified.
+    /// New Builder with radius of the disks like `with_disk_radius`,
+    /// returning an error instead of panicking if no distribution can be generated with it.
+    pub fn try_with_disk_radius<D>(radius: F, domain: D) -> Result<Self, RadiusError>
+    where
+        D: Into<Domain>,
+    {
+        let value = radius.to_f64().unwrap_or(f64::NAN);
+        if !NumFloat::is_finite(radius) {
+            return Err(RadiusError::NotFinite(value));
+        }
+        if radius <= F::cast(0) {
+            return Err(RadiusError::NonPositiveRadius(value));
+        }
+        Ok(Builder::base(radius, domain.into()))
+    }
+
+    /// New Builder with domain or type of distribution and radius of the disks along each axis specified,
+    /// which stretches the exclusion zones into axis-aligned ellipsoids.
+    /// Samples conflict if the sum over the axes of `(difference / (2 * radius))²` is less than 1.
+    /// The radii should be larger than 0 and the largest one is used as the radius of the generator.
+    /// The flow field of `with_flow_field` takes the place of the radii if both are set.
+    pub fn with_radii<D>(radii: V, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        Builder::try_with_radii(radii, domain).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// New Builder with radius of the disks along each axis like `with_radii`,
+    /// returning an error for the first radius no distribution can be generated with instead of panicking.
+    pub fn try_with_radii<D>(radii: V, domain: D) -> Result<Self, RadiusError>
+    where
+        D: Into<Domain>,
+    {
+        for n in 0..V::dimension() {
+            let value = radii[n].to_f64().unwrap_or(f64::NAN);
+            if !NumFloat::is_finite(radii[n]) {
+                return Err(RadiusError::NotFinite(value));
+            }
+            if radii[n] <= F::cast(0) {
+                return Err(RadiusError::NonPositiveRadius(value));
+            }
+        }
+        let radius = (0..V::dimension())
+            .map(|n| radii[n])
+            .fold(F::cast(0), NumFloat::max);
+        let mut builder = Builder::try_with_disk_radius(radius, domain)?;
+        builder.radii = Some(radii);
+        Ok(builder)
+    }
+
+    /// New Builder with domain or type of distribution and relative radius specified.
     /// The relative radius should be ]0, 1]
-    pub fn with_relative_radius(relative: F, poisson_type: Type) -> Self {
+    pub fn with_relative_radius<D>(relative: F, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
         assert!(relative >= F::cast(0));
         assert!(relative <= F::cast(1));
-        Builder {
-            radius: relative
-                * NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work."),
-            poisson_type,
-            _marker: PhantomData,
-        }
+        Builder::base(relative * max_radius(), domain.into())
     }
 
-    /// New Builder with type of distribution, approximate amount of samples and relative radius specified.
+    /// New Builder with domain or type of distribution, approximate amount of samples and relative radius specified.
     /// The amount of samples should be larger than 0.
     /// The relative radius should be [0, 1].
     /// For non-perioditic this is supported only for 2, 3 and 4 dimensional generation.
     /// For perioditic this is supported up to 8 dimensions.
-    pub fn with_samples(samples: usize, relative: F, poisson_type: Type) -> Self {
+    /// The samples fill the domain, so domains with axes shorter than the unit get a smaller radius.
+    pub fn with_samples<D>(samples: usize, relative: F, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        let domain = domain.into();
+        let dim = V::dimension();
+        // Radius scales with the dimensionth root of the volume each sample gets.
+        let volume: F =
+            NumCast::from(domain.volume(dim)).expect("f64 should be castable to float.");
+        let scale = NumFloat::powf(volume, F::cast(1) / F::cast(dim));
+        let radius = calc_radius::<F, V>(samples, relative, domain.poisson_type(dim)) * scale;
+        Builder::base(radius, domain)
+    }
+
+    /// Builder with the radius and domain and every other setting at its default.
+    fn base(radius: F, domain: Domain) -> Self {
         Builder {
-            radius: calc_radius::<F, V>(samples, relative, poisson_type),
-            poisson_type,
+            radius,
+            domain,
+            active_policy: ActivePolicy::default(),
+            start_points: vec![],
+            emit_start_points: false,
+            fixed_points: 0,
+            prefill_policy: PrefillPolicy::KeepAll,
+            wrap: Wrap::Strict,
+            rejection_limit: DEFAULT_REJECTION_LIMIT,
+            max_depth: None,
+            jitter: F::cast(1) / F::cast(2),
+            anisotropy: None,
+            radii: None,
+            precision: Precision::Full,
+            buffers: None,
+            cell_scale: F::cast(1),
+            attempts: DEFAULT_ATTEMPTS,
+            exact: false,
+            trace: false,
+            max_points: None,
+            regions: None,
+            boundary: false,
+            shape: None,
+            blockers: Blockers::default(),
+            clearances: Blockers::default(),
+            segments: vec![],
+            checks: vec![],
+            maximal: false,
             _marker: PhantomData,
         }
     }
 
-    /// Returns the radius of the generator.
+    /// Returns the radius of the generator normalized to [0, 1)<sup>d</sup>.
     pub fn radius(&self) -> F {
         self.radius
     }
 
+    /// Returns the radius of the generator relative to the largest allowed radius √2 / 2.
+    pub fn relative_radius(&self) -> F {
+        self.radius() / max_radius()
+    }
+
+    /// Returns the radius of the generator scaled to a domain whose side is `extent` long.
+    pub fn absolute_radius(&self, extent: F) -> F {
+        self.radius() * extent
+    }
+
+    /// Returns the radius of the disks along each axis if they were set with `with_radii`.
+    pub fn radii(&self) -> Option<&V> {
+        self.radii.as_ref()
+    }
+
+    /// Returns the smallest radius along an axis relative to the radius of the generator.
+    pub(crate) fn min_axis_scale(&self) -> F {
+        match self.radii {
+            Some(ref radii) => (0..V::dimension())
+                .map(|n| radii[n] / self.radius)
+                .fold(F::cast(1), NumFloat::min),
+            None => F::cast(1),
+        }
+    }
+
+    /// Returns the minimum distance between samples, which is twice the radius.
+    pub fn diameter(&self) -> F {
+        F::cast(2) * self.radius()
+    }
+
+    /// Returns the minimum distance between samples as set by `with_separation`, which is the same as `diameter`.
+    pub fn separation(&self) -> F {
+        self.diameter()
+    }
+
+    /// Returns the radius of the disks around samples as set by `with_disk_radius`, which is the same as `radius`.
+    pub fn disk_radius(&self) -> F {
+        self.radius()
+    }
+
     /// Returns the type of the generator.
+    /// Domains where only some of the axes wrap around are of the normal type.
     pub fn poisson_type(&self) -> Type {
-        self.poisson_type
+        self.domain.poisson_type(V::dimension())
+    }
+
+    /// Returns the domain of the generator.
+    pub fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    /// Sets the policy for choosing which active sample is expanded next.
+    /// Only affects algorithms that grow the distribution from active samples such as Bridson.
+    pub fn with_active_policy(mut self, policy: ActivePolicy<V>) -> Self {
+        self.active_policy = policy;
+        self
+    }
+
+    /// Returns the policy for choosing which active sample is expanded next.
+    pub fn active_policy(&self) -> &ActivePolicy<V> {
+        &self.active_policy
+    }
+
+    /// Sets the samples the generation starts from.
+    /// Algorithms that grow the distribution such as Bridson grow it from these instead of a random sample
+    /// and others treat them as if they had been restricted before the generation.
+    /// If `emit` is true the start points are also returned as the first samples of the distribution.
+    /// The samples of neighbouring tiles are kept in front of them.
+    pub fn with_start_points(mut self, points: &[V], emit: bool) -> Self {
+        self.start_points.truncate(self.fixed_points);
+        self.start_points.extend_from_slice(points);
+        self.emit_start_points = emit;
+        self
+    }
+
+    /// Returns the samples the generation starts from, starting with the samples of neighbouring tiles.
+    pub fn start_points(&self) -> &[V] {
+        &self.start_points
+    }
+
+    /// Adds the samples of an already generated neighbouring tile that the samples near the borders keep
+    /// their distance to, so that tiles generated one at a time, such as the chunks of a world streamed
+    /// across sessions, are legal across their borders too.
+    /// The offset is the position of the neighbour in tiles along each axis, such as `[-1, 0]` for the tile
+    /// before this one along the first axis, and the samples are in the coordinates of the neighbour.
+    /// Samples farther from the domain than the distance between samples can't conflict and are left out.
+    /// Samples of neighbours are never returned and conflicts with them are handled by the prefill policy
+    /// like with any earlier start point.
+    pub fn with_neighbour_tile(mut self, offset: &[i64], points: &[V]) -> Self {
+        let dim = V::dimension();
+        assert_eq!(offset.len(), dim, "Offset should have a tile for each axis.");
+        let radius = self.radii.as_ref().map_or(self.radius, |radii| {
+            (0..dim).map(|n| radii[n]).fold(self.radius, NumFloat::max)
+        });
+        let reach = F::cast(2) * radius;
+        let translated = points
+            .iter()
+            .map(|p| {
+                let mut p = p.clone();
+                for (n, &o) in offset.iter().enumerate() {
+                    let o: F = NumCast::from(o).expect("Offset should be castable to float.");
+                    p[n] += o * self.domain.extent_of::<F>(n);
+                }
+                p
+            })
+            .filter(|p| {
+                let sqgap = (0..dim).fold(F::cast(0), |sum, n| {
+                    let extent = self.domain.extent_of::<F>(n);
+                    let gap = NumFloat::max(NumFloat::max(-p[n], p[n] - extent), F::cast(0));
+                    sum + gap * gap
+                });
+                sqgap < reach * reach
+            })
+            .collect::<Vec<_>>();
+        let fixed = self.fixed_points;
+        self.fixed_points += translated.len();
+        self.start_points.splice(fixed..fixed, translated);
+        self
+    }
+
+    /// Returns the start points that are also returned as the first samples of the distribution.
+    fn emitted_start_points(&self) -> &[V] {
+        if self.emit_start_points {
+            &self.start_points[self.fixed_points..]
+        } else {
+            &[]
+        }
+    }
+
+    /// Sets what is done with start points that conflict with each other when the generator is built.
+    pub fn with_prefill_policy(mut self, policy: PrefillPolicy) -> Self {
+        self.prefill_policy = policy;
+        self
+    }
+
+    /// Returns what is done with start points that conflict with each other when the generator is built.
+    pub fn prefill_policy(&self) -> PrefillPolicy {
+        self.prefill_policy
+    }
+
+    /// Sets what is done with start points and restricted samples outside the domain on the axes that wrap around.
+    pub fn with_wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Returns what is done with start points and restricted samples outside the domain on the axes that wrap around.
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    /// Wraps the position into the domain on the axes that wrap around, as set by `with_wrap`.
+    pub(crate) fn wrapped(&self, mut position: V) -> V {
+        for n in 0..V::dimension() {
+            if !self.domain.is_perioditic(n) {
+                continue;
+            }
+            let extent = self.domain.extent_of::<F>(n);
+            let value = position[n];
+            if F::cast(0) <= value && value < extent {
+                continue;
+            }
+            assert!(
+                self.wrap == Wrap::Loose || (-extent <= value && value < extent + extent),
+                "Position is farther than the neighbouring tiles on axis {}.",
+                n
+            );
+            let mut value = value - NumFloat::floor(value / extent) * extent;
+            // Rounding can land tiny negative values exactly on the extent.
+            if value >= extent {
+                value = F::cast(0);
+            }
+            position[n] = value;
+        }
+        position
+    }
+
+    /// Returns the indices of the start points that conflict with an earlier start point that doesn't conflict itself.
+    /// These are the start points `PrefillPolicy::RejectConflicting` leaves out.
+    pub fn conflicting_start_points(&self) -> Vec<usize> {
+        let mut grid = Grid::<F, V>::new(self.radius, self.domain, Precision::Full);
+        let mut outside = vec![];
+        let mut conflicting = vec![];
+        for (i, point) in self.start_points.iter().enumerate() {
+            let index = sample_to_index(point, grid.side());
+            let near = grid.neighbours(index.clone());
+            if near.iter().chain(&outside).any(|v| conflicts(self, v, point)) {
+                conflicting.push(i);
+            } else if let Err(point) = grid.insert(index, point.clone()) {
+                outside.push(point);
+            }
+        }
+        conflicting
+    }
+
+    /// Sets the amount of consecutive rejected samples after which algorithms based on dart throwing stop.
+    /// Larger limit makes the distribution closer to maximal at the cost of generation time.
+    pub fn with_rejection_limit(mut self, limit: usize) -> Self {
+        self.rejection_limit = limit;
+        self
+    }
+
+    /// Returns the amount of consecutive rejected samples after which algorithms based on dart throwing stop.
+    pub fn rejection_limit(&self) -> usize {
+        self.rejection_limit
+    }
+
+    /// Sets the deepest level Ebeida subdivides the cells left uncovered to, where 0 is the base grid.
+    /// Adversarial start points and restrictions can leave slivers that need many levels to resolve,
+    /// so past the depth the remaining cells are resolved with dart throwing that stops after as many
+    /// consecutive rejected samples as the rejection limit. This bounds the time of the generation at the cost
+    /// of the distribution no longer being guaranteed maximal. Without a depth the cells are subdivided until
+    /// they are as small as the precision of the float allows.
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Returns the deepest level Ebeida subdivides the cells left uncovered to if it's limited.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Sets how far the lattice algorithms move the samples from the lattice points relative to the radius.
+    /// Larger jitter makes the distribution look less regular but spreads the lattice points farther apart.
+    /// The jitter should be [0, 1]. Defaults to 0.5.
+    pub fn with_jitter(mut self, jitter: F) -> Self {
+        assert!(F::cast(0) <= jitter && jitter <= F::cast(1));
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns how far the lattice algorithms move the samples from the lattice points relative to the radius.
+    pub fn jitter(&self) -> F {
+        self.jitter
+    }
+
+    /// Aligns the exclusion zones of the samples with the direction of the vector field so that samples follow its flow lines.
+    /// The exclusion zone extends `along` times the radius in the direction of the field and `across` times the radius perpendicular to it.
+    /// Both scales should be ]0, 1].
+    pub fn with_flow_field<T>(mut self, field: T, along: F, across: F) -> Self
+    where
+        T: Fn(&V) -> V + Send + Sync + 'static,
+    {
+        assert!(F::cast(0) < along && along <= F::cast(1));
+        assert!(F::cast(0) < across && across <= F::cast(1));
+        self.anisotropy = Some(Anisotropy {
+            field: VectorField::new(field),
+            along,
+            across,
+        });
+        self
+    }
+
+    /// Returns the anisotropy of the exclusion zones if there is any.
+    pub fn anisotropy(&self) -> Option<&Anisotropy<F, V>> {
+        self.anisotropy.as_ref()
+    }
+
+    /// Sets the precision of the samples stored for checking distances between samples.
+    pub fn with_precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Returns the precision of the samples stored for checking distances between samples.
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    /// Sets the pool the grids and active sample lists of the algorithms take their buffers from,
+    /// so generating distributions repeatedly, such as every frame, doesn't allocate them each time.
+    pub fn with_buffers(mut self, buffers: Buffers<V>) -> Self {
+        self.buffers = Some(buffers);
+        self
+    }
+
+    /// Returns the pool the algorithms take their buffers from if there is one.
+    pub fn buffers(&self) -> Option<&Buffers<V>> {
+        self.buffers.as_ref()
+    }
+
+    /// Scales the cells of the background grid used for finding nearby samples relative to the cells inscribed
+    /// in the disk of the radius. Smaller cells check fewer false candidates at the cost of visiting more cells,
+    /// so the fastest scale depends on the dimension. Only Bridson and dart throwing honour this as the other
+    /// algorithms rely on a sample covering its whole cell.
+    /// The scale should be larger than 0 and defaults to 1.
+    pub fn with_cell_scale(mut self, scale: F) -> Self {
+        assert!(F::cast(0) < scale);
+        self.cell_scale = scale;
+        self
+    }
+
+    /// Returns the scale of the cells of the background grid relative to the cells inscribed in the disk of the radius.
+    pub fn cell_scale(&self) -> F {
+        self.cell_scale
+    }
+
+    /// Sets the amount of samples Bridson tries around an active sample before deactivating it.
+    /// Fewer attempts generate faster but leave more gaps as active samples are given up on sooner.
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        assert!(attempts > 0);
+        self.attempts = attempts;
+        self
+    }
+
+    /// Returns the amount of samples Bridson tries around an active sample before deactivating it.
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// Applies the settings of the tuning, leaving the parameters of the distribution as they are.
+    pub fn with_tuning(self, tuning: &Tuning) -> Self {
+        let scale = NumCast::from(tuning.grid_factor).expect("Grid factor should be castable to float.");
+        self.with_cell_scale(scale).with_attempts(tuning.bridson_k)
+    }
+
+    /// Sets whether distances within rounding error of the diameter are compared without rounding.
+    /// The rounded squared distance can put a pair that is closer than the diameter on the wrong side of it,
+    /// so with exact checks samples are always farther apart than the diameter, counting pairs exactly at it as
+    /// conflicting. Anisotropic exclusion zones are still compared with rounding.
+    pub fn with_exact_checks(mut self, exact: bool) -> Self {
+        self.exact = exact;
+        self
+    }
+
+    /// Returns whether distances within rounding error of the diameter are compared without rounding.
+    pub fn exact_checks(&self) -> bool {
+        self.exact
+    }
+
+    /// Records the candidates the iterator goes through as a trace that `PoissonIter::trace` returns.
+    /// Recording keeps every sample a second time so it's off by default.
+    pub fn record_trace(mut self) -> Self {
+        self.trace = true;
+        self
+    }
+
+    /// Returns whether the candidates are recorded as a trace.
+    pub fn records_trace(&self) -> bool {
+        self.trace
+    }
+
+    /// Stops the generation once this many samples have been generated, including emitted start points.
+    /// The result is legal but not maximal. Ebeida and dart throwing choose new samples uniformly from the
+    /// remaining space so the early stopped distribution still covers the whole space evenly, whereas
+    /// Bridson grows the distribution outwards from its first samples.
+    pub fn with_max_points(mut self, max: usize) -> Self {
+        self.max_points = Some(max);
+        self
+    }
+
+    /// Returns the amount of samples after which the generation stops if there is one.
+    pub fn max_points(&self) -> Option<usize> {
+        self.max_points
+    }
+
+    /// Partitions the space into regions labelled by the callback, each having radius from `radii` indexed by the label.
+    /// Samples from different regions are spaced according to `spacing`.
+    /// The radius of the builder is used for the background grid so it has to be at least as large as the radii of the regions.
+    pub fn with_regions<T>(mut self, labels: T, radii: &[F], spacing: Spacing) -> Self
+    where
+        T: Fn(&V) -> usize + Send + Sync + 'static,
+    {
+        let regions = Regions::new(labels, radii, spacing);
+        assert!(regions.max_radius() <= self.radius);
+        self.regions = Some(regions);
+        self
+    }
+
+    /// Varies the radius with fractal noise from `r_min` where the noise is 0 to `r_max` where it's 1.
+    /// The noise is quantized into regions spaced by the sum of their radii and the radius of the builder
+    /// is set to `r_max` as the background grid needs the largest radius.
+    #[cfg(feature = "noise")]
+    pub fn with_noise_density(mut self, fbm: noise::Fbm, r_min: F, r_max: F) -> Self {
+        const LEVELS: usize = 32;
+        assert!(F::cast(0) < r_min && r_min <= r_max);
+        let radii = (0..LEVELS)
+            .map(|i| r_min + (r_max - r_min) * F::cast(i) / F::cast(LEVELS - 1))
+            .collect::<Vec<_>>();
+        let labels = move |v: &V| {
+            let value: F = fbm.get(v);
+            NumFloat::round(value * F::cast(LEVELS - 1))
+                .to_usize()
+                .unwrap_or(0)
+                .min(LEVELS - 1)
+        };
+        self.radius = r_max;
+        self.with_regions(labels, &radii, Spacing::Sum)
+    }
+
+    /// Varies the radius with a density image or scalar field from `r_min` where the tone is 0 to `r_max` where it's 1,
+    /// so dark pixels get dense samples. The values are in [0, 1] and mapped to tones with `mapping`.
+    /// The raster has `resolution[n]` cells along the n:th axis and the first axis varies fastest in it.
+    /// The tones are quantized into regions spaced by the sum of their radii and the radius of the builder
+    /// is set to `r_max` as the background grid needs the largest radius.
+    pub fn with_density_raster(
+        mut self,
+        values: &[F],
+        resolution: &[usize],
+        r_min: F,
+        r_max: F,
+        mapping: ToneMapping<F>,
+    ) -> Self {
+        const LEVELS: usize = 32;
+        assert!(F::cast(0) < r_min && r_min <= r_max);
+        let radii = (0..LEVELS)
+            .map(|i| r_min + (r_max - r_min) * F::cast(i) / F::cast(LEVELS - 1))
+            .collect::<Vec<_>>();
+        let labels = tone_map(values, mapping)
+            .into_iter()
+            .map(|tone| {
+                NumFloat::round(tone * F::cast(LEVELS - 1))
+                    .to_usize()
+                    .unwrap_or(0)
+                    .min(LEVELS - 1)
+            })
+            .collect();
+        self.radius = r_max;
+        self.with_regions(raster_labels::<F, V>(labels, resolution), &radii, Spacing::Sum)
+    }
+
+    /// Returns the regions of the space if there are any.
+    pub fn regions(&self) -> Option<&Regions<F, V>> {
+        self.regions.as_ref()
+    }
+
+    /// Sets whether the distribution has to be maximal so that no more samples fit into it.
+    /// This guides `algorithm::Auto` to pick an algorithm that generates maximal distributions.
+    pub fn with_maximal(mut self, maximal: bool) -> Self {
+        self.maximal = maximal;
+        self
+    }
+
+    /// Returns whether the distribution has to be maximal.
+    pub fn maximal(&self) -> bool {
+        self.maximal
+    }
+
+    /// Restricts the samples inside the shape.
+    /// The generators skip the parts of the space outside of it so the distribution is as maximal inside the shape
+    /// as it would be in the whole space. The shape should overlap [0, 1)<sup>d</sup>.
+    pub fn with_shape<S>(mut self, shape: S) -> Self
+    where
+        S: Parametric<F, V> + Send + Sync + 'static,
+    {
+        self.shape = Some(Restriction(Arc::new(shape)));
+        self
+    }
+
+    /// Returns the shape the samples are restricted inside of if there is one.
+    pub fn shape(&self) -> Option<&(dyn Parametric<F, V> + Send + Sync)> {
+        self.shape.as_ref().map(|s| &*s.0)
+    }
+
+    /// Keeps the samples away from the line segment between the positions, such as a road.
+    /// Samples are generated so that their exclusion zones don't overlap the capsule of positions
+    /// at most the clearance away from the segment.
+    /// Across perioditic axes the segment should be shorter than half of the domain.
+    /// The segments are checked one by one so they are meant for a network of roads, not as many as there are samples.
+    pub fn with_segment_obstacle(mut self, start: V, end: V, clearance: F) -> Self {
+        assert!(F::cast(0) <= clearance);
+        self.segments.push(Segment {
+            start,
+            end,
+            clearance,
+        });
+        self
+    }
+
+    /// Adds custom check that candidates have to pass on top of the built-in ones.
+    /// Checks are made in the order they were added and a candidate has to pass all of them.
+    pub fn with_legality_check<C>(mut self, check: C) -> Self
+    where
+        C: LegalityCheck<F, V> + 'static,
+    {
+        self.checks.push(Check(Arc::new(check)));
+        self
+    }
+
+    /// Returns the custom checks in the order they were added.
+    pub fn legality_checks(&self) -> impl Iterator<Item = &dyn LegalityCheck<F, V>> {
+        self.checks.iter().map(|c| &*c.0)
+    }
+
+    /// Returns the index of the first custom check that rejects the candidate next to the samples near it.
+    pub(crate) fn failed_check(&self, candidate: &V, neighbours: &[V]) -> Option<usize> {
+        self.checks
+            .iter()
+            .position(|c| !c.0.is_legal(candidate, neighbours))
+    }
+
+    /// Returns the closest dynamic blocker or segment obstacle the sample would overlap and the distance to it.
+    /// For segments the position is the point of the segment closest to the sample.
+    pub(crate) fn blocking(&self, sample: &V) -> Option<(V, F)> {
+        if self.blockers.is_empty() && self.segments.is_empty() {
+            return None;
+        }
+        let radius = self.radius_at(sample);
+        let blocker = self
+            .blockers
+            .blocking(sample, radius, self.domain)
+            .map(|(p, d)| (p.clone(), d));
+        self.segments
+            .iter()
+            .filter_map(|s| {
+                let (closest, sqdistance) = s.closest(sample, self.domain);
+                let reach = s.clearance + radius;
+                (sqdistance < reach * reach).then(|| (closest, NumFloat::sqrt(sqdistance)))
+            })
+            .chain(blocker)
+            .fold(None, |closest: Option<(V, F)>, (p, d)| match closest {
+                Some((_, c)) if c <= d => closest,
+                _ => Some((p, d)),
+            })
+    }
+
+    /// Returns the closest sample restricted with clearance that the sample would be too close to and the distance to it.
+    pub(crate) fn clearing(&self, sample: &V) -> Option<(&V, F)> {
+        if self.clearances.is_empty() {
+            return None;
+        }
+        self.clearances
+            .blocking(sample, self.radius_at(sample), self.domain)
+    }
+
+    /// Checks if the position is inside the shape the samples are restricted inside of.
+    pub(crate) fn inside_shape(&self, position: &V) -> bool {
+        self.shape.as_ref().is_none_or(|s| s.0.contains(position))
+    }
+
+    /// Checks if the cell with given minimum corner and side is entirely outside the extents of the domain
+    /// or the shape the samples are restricted inside of.
+    pub(crate) fn outside(&self, corner: V, side: F) -> bool {
+        if (0..V::dimension()).any(|n| corner[n] >= self.domain.extent_of::<F>(n)) {
+            return true;
+        }
+        self.shape.as_ref().is_some_and(|s| {
+            let half = side / F::cast(2);
+            let mut center = corner;
+            for n in 0..V::dimension() {
+                center[n] += half;
+            }
+            s.0.distance(&center) > half * NumFloat::sqrt(F::cast(V::dimension()))
+        })
+    }
+
+    /// Sets whether the boundary is sampled before the interior.
+    /// Without a shape the faces of the domain are sampled, the corners first, then the edges and so on,
+    /// each with a maximal distribution so that the samples are well distributed also when projected to the boundary.
+    /// Only the faces across open axes are sampled so at least one axis of the domain has to be open,
+    /// and the axes have to be as long as the unit.
+    /// With a shape set before this its outline is sampled instead.
+    /// The boundary samples are returned before the rest of the distribution.
+    pub fn with_boundary_sampling(mut self, boundary: bool) -> Self {
+        assert!(
+            !boundary
+                || self.shape.is_some()
+                || (self.domain.is_unit()
+                    && (0..V::dimension()).any(|n| !self.domain.is_perioditic(n)))
+        );
+        self.boundary = boundary;
+        self
+    }
+
+    /// Returns whether the faces of the domain are sampled before its interior.
+    pub fn boundary_sampling(&self) -> bool {
+        self.boundary
+    }
+
+    /// Returns the radius of the exclusion zone of a sample at the position.
+    pub fn radius_at(&self, position: &V) -> F {
+        match self.regions {
+            Some(ref regions) => regions.radius_at(position),
+            None => self.radius,
+        }
+    }
+
+    /// Checks if every sample has the same exclusion zone so that a sample covers its whole grid cell.
+    pub(crate) fn uniform_exclusion(&self) -> bool {
+        self.anisotropy.is_none() && self.regions.is_none() && self.radii.is_none()
+    }
+
+    /// Checks if a base cell of the grid can have room for more than one sample.
+    pub(crate) fn shared_cells(&self) -> bool {
+        self.regions.is_some() || self.radii.is_some()
     }
 
     /// Builds generator with random number generator and algorithm specified.
-    pub fn build<R, A>(self, rng: R, _algo: A) -> Generator<F, V, R, A>
+    /// Start points that conflict with each other are handled according to the prefill policy.
+    pub fn build<R, A>(mut self, rng: R, _algo: A) -> Generator<F, V, R, A>
     where
         R: Rng,
         A: Creator<F, V>,
     {
+        self.prefill();
         Generator::new(self, rng)
     }
+
+    /// Prepares the generation with the algorithm once so that it can be executed with many random number
+    /// generators, telling the costs of the parameters before anything is generated.
+    /// Start points that conflict with each other are handled according to the prefill policy.
+    pub fn plan<A>(mut self, algo: A) -> Plan<F, V, A>
+    where
+        A: Creator<F, V>,
+    {
+        self.prefill();
+        Plan::new(self, algo)
+    }
+
+    /// Handles the start points that conflict with each other according to the prefill policy.
+    fn prefill(&mut self) {
+        let start = mem::take(&mut self.start_points);
+        self.start_points = start.into_iter().map(|p| self.wrapped(p)).collect();
+        match self.prefill_policy {
+            PrefillPolicy::KeepAll => {}
+            PrefillPolicy::RejectConflicting => {
+                let conflicting = self.conflicting_start_points();
+                let (fixed, mut kept) = (self.fixed_points, 0);
+                let mut i = 0;
+                self.start_points.retain(|_| {
+                    i += 1;
+                    let keep = conflicting.binary_search(&(i - 1)).is_err();
+                    if keep && i <= fixed {
+                        kept += 1;
+                    }
+                    keep
+                });
+                self.fixed_points = kept;
+            }
+            PrefillPolicy::Panic => {
+                let conflicting = self.conflicting_start_points();
+                assert!(
+                    conflicting.is_empty(),
+                    "Start points {:?} conflict with earlier start points.",
+                    conflicting
+                );
+            }
+        }
+    }
+
+    /// Generates given amount of independent distributions, each with its own random number generator seeded from the one given.
+    /// The ith distribution uses the random number generator of `SeedTree::from_rng(&mut rng).child(i)`.
+    /// The distributions are generated lazily as the returned iterator is advanced.
+    pub fn generate_ensemble<R, A>(&self, rng: R, _algo: A, count: usize) -> Ensemble<F, V, R, A>
+    where
+        R: Rng + SeedableRng + Clone,
+        A: Creator<F, V>,
+    {
+        Ensemble::new(self.clone(), rng, count)
+    }
+
+    /// Generates given amount of independent distributions in parallel.
+    /// The result is the same as collecting the iterator returned by `generate_ensemble` regardless of the amount of threads.
+    #[cfg(feature = "rayon")]
+    pub fn generate_ensemble_par<R, A>(
+        &self,
+        mut rng: R,
+        algo: A,
+        count: usize,
+    ) -> Vec<PointSet<F, V>>
+    where
+        F: Send + Sync,
+        V: Send + Sync,
+        R: Rng + SeedableRng + Clone + Send,
+        A: Creator<F, V> + Send + Sync,
+    {
+        use rayon::prelude::*;
+        let tree = SeedTree::from_rng(&mut rng);
+        (0..count)
+            .into_par_iter()
+            .map(|i| {
+                let rng = tree.child(i as u64).rng::<R>();
+                self.clone().build(rng, algo).generate_set()
+            })
+            .collect()
+    }
 }
 
 /// Generates poisson-disk distribution in [0, 1]<sup>d</sup> area.
-#[derive(Clone, Debug)]
+/// Cloning the generator clones the state of its random number generator so the clone generates the same distribution.
+#[cfg(feature = "full")]
+#[derive(Clone)]
 pub struct Generator<F, V, R, A>
 where
     F: Float,
@@ -203,6 +1198,7 @@ where
     _algo: PhantomData<A>,
 }
 
+#[cfg(feature = "full")]
 impl<F, V, R, A> Generator<F, V, R, A>
 where
     F: Float,
@@ -221,24 +1217,41 @@ where
     /// Sets the radius of the generator.
     pub fn set_radius(&mut self, radius: F) {
         assert!(F::cast(0) < radius);
-        assert!(
-            radius
-                <= NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work.")
-        );
         self.poisson.radius = radius;
     }
 
-    /// Returns the radius of the generator.
+    /// Returns the radius of the generator normalized to [0, 1)<sup>d</sup>.
     pub fn radius(&self) -> F {
         self.poisson.radius
     }
 
+    /// Returns the radius of the generator relative to the largest allowed radius √2 / 2.
+    pub fn relative_radius(&self) -> F {
+        self.radius() / max_radius()
+    }
+
+    /// Returns the radius of the generator scaled to a domain whose side is `extent` long.
+    pub fn absolute_radius(&self, extent: F) -> F {
+        self.radius() * extent
+    }
+
+    /// Returns the minimum distance between samples, which is twice the radius.
+    pub fn diameter(&self) -> F {
+        F::cast(2) * self.radius()
+    }
+
     /// Returns the type of the generator.
     pub fn poisson_type(&self) -> Type {
-        self.poisson.poisson_type
+        self.poisson.poisson_type()
+    }
+
+    /// Returns the domain of the generator.
+    pub fn domain(&self) -> Domain {
+        self.poisson.domain
     }
 }
 
+#[cfg(feature = "full")]
 impl<F, V, R, A> Generator<F, V, R, A>
 where
     F: Float,
@@ -250,8 +1263,138 @@ where
     pub fn generate(&self) -> Vec<V> {
         self.clone().into_iter().collect()
     }
+
+    /// Generates Poisson-disk distribution into the buffer replacing its contents.
+    /// The capacity of the buffer is reused so generating repeatedly, for example every frame, doesn't have to allocate.
+    pub fn generate_into_buffer(&self, buffer: &mut Vec<V>) {
+        buffer.clear();
+        buffer.extend(self.clone());
+    }
+
+    /// Generates Poisson-disk distribution where each sample carries the radius of its exclusion zone.
+    pub fn generate_samples(&self) -> Vec<Sample<F, V>> {
+        self.generate()
+            .into_iter()
+            .map(|p| {
+                let radius = self.poisson.radius_at(&p);
+                Sample::new(p, radius)
+            })
+            .collect()
+    }
+
+    /// Returns the first `n` samples that would be generated without generating the rest of the distribution.
+    pub fn preview_next(&self, n: usize) -> Vec<V> {
+        self.clone().into_iter().take(n).collect()
+    }
+
+    /// Explains why the sample would be rejected before anything is generated, or returns `None` if it wouldn't be.
+    /// The sample is checked against the start points and the boundary samples.
+    pub fn explain(&self, value: V) -> Option<Rejection<F, V>> {
+        self.clone().into_iter().explain(value)
+    }
+
+    /// Generates samples on the boundary and inside it at the same time, as with boundary sampling,
+    /// returning the two groups separately. This is useful for meshing, where the boundary samples become the
+    /// boundary nodes, and for stippling both the outline and the fill of a shape.
+    /// Boundary sampling is enabled for the generation so the builder has to support it.
+    pub fn generate_dual(&self) -> DualSamples<V> {
+        let mut generator = self.clone();
+        generator.poisson = generator.poisson.with_boundary_sampling(true);
+        let emitted = self.poisson.emitted_start_points().len();
+        let iter = generator.into_iter();
+        let boundary = iter.start.len() - emitted;
+        let mut interior = iter.collect::<Vec<_>>();
+        let boundary = interior.drain(emitted..emitted + boundary).collect();
+        DualSamples { boundary, interior }
+    }
+
+    /// Generates Poisson-disk distribution keeping the parameters it was generated with.
+    pub fn generate_set(&self) -> PointSet<F, V> {
+        let mut iter = self.clone().into_iter();
+        let points = iter.by_ref().collect();
+        self.set(points, &iter)
+    }
+
+    /// Generates Poisson-disk distribution reusing as many samples of the earlier distribution as is legal.
+    /// Samples of the earlier distribution are kept in order as long as they don't conflict with the ones kept before them,
+    /// and the gaps are then filled with new samples. This keeps most samples in place when parameters like the radius
+    /// change slightly, which is useful for editors regenerating the distribution on every change.
+    pub fn regenerate_from(&self, prior: &PointSet<F, V>) -> PointSet<F, V> {
+        let mut iter = self.clone().into_iter();
+        let mut points = vec![];
+        for p in prior {
+            if iter.stays_legal(p.clone()) {
+                iter.restrict(p.clone());
+                points.push(p.clone());
+            }
+        }
+        points.extend(iter.by_ref());
+        self.set(points, &iter)
+    }
+
+    /// Generates Poisson-disk distribution and counts the samples in each cell of the grid the algorithms use
+    /// to accelerate the checks, with the cells scaled as set by `Builder::with_cell_scale`.
+    /// This is meant for debugging density anomalies like empty cells in the middle of the distribution.
+    pub fn grid_occupancy_image(&self) -> OccupancyImage<F> {
+        let grid = Grid::<F, V>::scaled(
+            self.poisson.radius,
+            self.poisson.cell_scale,
+            self.poisson.domain,
+            self.poisson.precision,
+        );
+        OccupancyImage::new(&self.generate(), grid.side(), grid.cell(), self.poisson.domain)
+    }
+
+    /// Generates Poisson-disk distribution returning the trace of the generation alongside it,
+    /// whether or not the builder records traces.
+    pub fn generate_with_trace(&self) -> (PointSet<F, V>, Trace<F, V>) {
+        let mut generator = self.clone();
+        generator.poisson = generator.poisson.record_trace();
+        let mut iter = generator.into_iter();
+        let points = iter.by_ref().collect();
+        let trace = iter.trace().expect("Trace is recorded when the builder records it.");
+        (self.set(points, &iter), trace)
+    }
+
+    /// Generates Poisson-disk distribution returning statistics about the generation alongside it.
+    pub fn generate_with_stats(&self) -> (PointSet<F, V>, GenerationStats) {
+        let mut iter = self.clone().into_iter();
+        let points = iter.by_ref().collect();
+        (self.set(points, &iter), iter.stats())
+    }
+
+    fn set<I>(&self, points: Vec<V>, iter: &PoissonIter<F, V, R, I>) -> PointSet<F, V>
+    where
+        I: Algorithm<F, V>,
+    {
+        let algorithm = iter
+            .stats()
+            .algorithm
+            .unwrap_or_else(algorithm::name::<F, V, A>);
+        PointSet::new(points, self.radius(), self.domain()).with_algorithm(algorithm)
+    }
+}
+
+#[cfg(feature = "full")]
+impl<F, V, R, A> fmt::Debug for Generator<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng,
+    A: Creator<F, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Generator")
+            .field("algorithm", &std::any::type_name::<A>())
+            .field("radius", &self.poisson.radius)
+            .field("dimension", &V::dimension())
+            .field("domain", &self.poisson.domain)
+            .field("start_points", &self.poisson.start_points.len())
+            .finish_non_exhaustive()
+    }
 }
 
+#[cfg(feature = "full")]
 impl<F, V, R, A> IntoIterator for Generator<F, V, R, A>
 where
     F: Float,
@@ -259,19 +1402,97 @@ where
     R: Rng,
     A: Creator<F, V>,
 {
-    type IntoIter = PoissonIter<F, V, R, A::Algo>;
+    type IntoIter = PoissonIter<F, V, R, Selected<F, V, A::Algo>>;
     type Item = V;
 
-    fn into_iter(self) -> Self::IntoIter {
+    fn into_iter(mut self) -> Self::IntoIter {
+        let mut start = self.poisson.emitted_start_points().to_vec();
+        let mut elapsed = Duration::ZERO;
+        if self.poisson.boundary {
+            let begin = Instant::now();
+            let boundary = match self.poisson.shape {
+                Some(ref shape) => boundary::outline(&self.poisson, &*shape.0, &mut self.rng),
+                None => boundary::sample(&self.poisson, &mut self.rng),
+            };
+            start.extend(boundary.iter().cloned());
+            self.poisson.start_points.extend(boundary);
+            elapsed = begin.elapsed();
+        }
+        start.reverse();
         PoissonIter {
             rng: self.rng,
-            algo: A::create(&self.poisson),
+            algo: Selected::create::<A>(&self.poisson),
+            events: if self.poisson.trace { Some(vec![]) } else { None },
             poisson: self.poisson,
+            start,
+            emitted: 0,
+            elapsed,
+            finished: false,
         }
     }
 }
 
+#[cfg(feature = "full")]
+impl<F, V, R, A> PoissonIter<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng,
+    A: Algorithm<F, V>,
+{
+    /// Starts the generation with an algorithm already created for the builder without boundary sampling.
+    pub(crate) fn prepared(poisson: Builder<F, V>, rng: R, algo: A) -> Self {
+        let mut start = poisson.emitted_start_points().to_vec();
+        start.reverse();
+        PoissonIter {
+            rng,
+            algo,
+            events: if poisson.trace { Some(vec![]) } else { None },
+            poisson,
+            start,
+            emitted: 0,
+            elapsed: Duration::ZERO,
+            finished: false,
+        }
+    }
+}
+
+#[cfg(feature = "full")]
+impl<F, V, R, A> IntoIterator for &Generator<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + Clone,
+    A: Creator<F, V>,
+{
+    type IntoIter = PoissonIter<F, V, R, Selected<F, V, A::Algo>>;
+    type Item = V;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.clone().into_iter()
+    }
+}
+
+#[cfg(feature = "full")]
+impl<F, V, R, A> PoissonIter<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + Clone,
+    A: Algorithm<F, V> + Clone,
+{
+    /// Returns the next `n` samples that would be generated without advancing the generation,
+    /// so that editors can show them as ghosts before accepting them.
+    /// Restricting samples or changing blockers before advancing changes what is generated next.
+    pub fn preview_next(&self, n: usize) -> Vec<V> {
+        self.clone().take(n).collect()
+    }
+}
+
 /// Iterator for generating poisson-disk distribution.
+/// Cloning the iterator snapshots the generation so the clone continues from the same state,
+/// which allows exploring a branch of the generation speculatively.
+#[cfg(feature = "full")]
 #[derive(Clone)]
 pub struct PoissonIter<F, V, R, A>
 where
@@ -283,8 +1504,35 @@ where
     poisson: Builder<F, V>,
     rng: R,
     algo: A,
+    events: Option<Vec<TraceEvent<V>>>,
+    start: Vec<V>,
+    emitted: usize,
+    elapsed: Duration,
+    finished: bool,
 }
 
+#[cfg(feature = "full")]
+impl<F, V, R, A> fmt::Debug for PoissonIter<F, V, R, A>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng,
+    A: Algorithm<F, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoissonIter")
+            .field("radius", &self.poisson.radius)
+            .field("dimension", &V::dimension())
+            .field("domain", &self.poisson.domain)
+            .field("emitted", &self.emitted)
+            .field("pending_start_points", &self.start.len())
+            .field("finished", &self.finished)
+            .field("stats", &self.stats())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "full")]
 impl<F, V, R, A> Iterator for PoissonIter<F, V, R, A>
 where
     F: Float,
@@ -295,14 +1543,56 @@ where
     type Item = V;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.algo.next(&mut self.poisson, &mut self.rng)
+        if self
+            .poisson
+            .max_points
+            .is_some_and(|max| max <= self.emitted)
+        {
+            self.finished = true;
+            return None;
+        }
+        if let Some(s) = self.start.pop() {
+            self.emitted += 1;
+            if let Some(ref mut events) = self.events {
+                events.push(TraceEvent::Start(s.clone()));
+            }
+            return Some(s);
+        }
+        let rejected = self.algo.stats().rejected;
+        let start = Instant::now();
+        let sample = self.algo.next(&mut self.poisson, &mut self.rng);
+        self.elapsed += start.elapsed();
+        match sample {
+            Some(_) => self.emitted += 1,
+            None => self.finished = true,
+        }
+        if let Some(ref mut events) = self.events {
+            let rejected = self.algo.stats().rejected - rejected;
+            if rejected > 0 {
+                events.push(TraceEvent::Rejected(rejected));
+            }
+            if let Some(ref s) = sample {
+                events.push(TraceEvent::Accepted(s.clone()));
+            }
+        }
+        sample
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.algo.size_hint(&self.poisson)
+        let (lower, upper) = self.algo.size_hint(&self.poisson);
+        let start = self.start.len();
+        let (lower, upper) = (lower + start, upper.map(|u| u + start));
+        match self.poisson.max_points {
+            Some(max) => {
+                let left = max.saturating_sub(self.emitted);
+                (lower.min(left), Some(upper.map_or(left, |u| u.min(left))))
+            }
+            None => (lower, upper),
+        }
     }
 }
 
+#[cfg(feature = "full")]
 impl<F, V, R, A> PoissonIter<F, V, R, A>
 where
     F: Float,
@@ -310,18 +1600,54 @@ where
     R: Rng,
     A: Algorithm<F, V>,
 {
-    /// Returns the radius of the generator.
+    /// Returns the radius of the generator normalized to [0, 1)<sup>d</sup>.
     pub fn radius(&self) -> F {
         self.poisson.radius
     }
 
+    /// Returns the radius of the generator relative to the largest allowed radius √2 / 2.
+    pub fn relative_radius(&self) -> F {
+        self.radius() / max_radius()
+    }
+
+    /// Returns the radius of the generator scaled to a domain whose side is `extent` long.
+    pub fn absolute_radius(&self, extent: F) -> F {
+        self.radius() * extent
+    }
+
+    /// Returns the minimum distance between samples, which is twice the radius.
+    pub fn diameter(&self) -> F {
+        F::cast(2) * self.radius()
+    }
+
     /// Returns the type of the generator.
     pub fn poisson_type(&self) -> Type {
-        self.poisson.poisson_type
+        self.poisson.poisson_type()
+    }
+
+    /// Returns the domain of the generator.
+    pub fn domain(&self) -> Domain {
+        self.poisson.domain
     }
 
     /// Restricts the poisson algorithm with arbitary sample.
+    /// Samples outside the domain on the axes that wrap around are wrapped into it as set by `Builder::with_wrap`.
     pub fn restrict(&mut self, value: V) {
+        let value = self.poisson.wrapped(value);
+        self.algo.restrict(value);
+    }
+
+    /// Restricts the poisson algorithm with arbitary sample that keeps the generated samples at least the clearance
+    /// farther than their own radius from it, such as a building that needs more room than the samples do.
+    /// Clearance below the radius keeps the samples as far as `restrict` does.
+    /// The clearances are checked one by one so they are meant for obstacles, not as many as there are samples.
+    pub fn restrict_with_radius(&mut self, value: V, clearance: F) {
+        assert!(F::cast(0) <= clearance);
+        let value = self.poisson.wrapped(value);
+        self.poisson.clearances.add(Blocker {
+            position: value.clone(),
+            radius: clearance,
+        });
         self.algo.restrict(value);
     }
 
@@ -329,4 +1655,109 @@ where
     pub fn stays_legal(&self, value: V) -> bool {
         self.algo.stays_legal(&self.poisson, value)
     }
+
+    /// Explains why the sample isn't legal for current distribution, or returns `None` if it is.
+    pub fn explain(&self, value: V) -> Option<Rejection<F, V>> {
+        self.algo.explain(&self.poisson, value)
+    }
+
+    /// Returns the trace of the candidates so far if the builder records it with `Builder::record_trace`.
+    pub fn trace(&self) -> Option<Trace<F, V>> {
+        self.events.as_ref().map(|events| Trace {
+            radius: self.poisson.radius,
+            domain: self.poisson.domain,
+            algorithm: self.stats().algorithm.map(str::to_owned),
+            events: events.clone(),
+        })
+    }
+
+    /// Adds a blocker that occupies a ball with given radius around the position, such as a player or a vehicle.
+    /// New samples are generated so that their exclusion zones don't overlap the ball, until the blocker is removed.
+    /// Blockers restrict only the samples generated after adding them and space they leave free isn't necessarily
+    /// filled afterwards, as the algorithms don't return to space they have given up on.
+    pub fn add_blocker(&mut self, position: V, radius: F) -> BlockerId {
+        assert!(F::cast(0) <= radius);
+        self.poisson.blockers.add(Blocker { position, radius })
+    }
+
+    /// Moves the blocker to a new position returning whether it still existed.
+    pub fn move_blocker(&mut self, id: BlockerId, position: V) -> bool {
+        match self.poisson.blockers.get_mut(id) {
+            Some(blocker) => {
+                blocker.position = position;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the blocker returning its position if it still existed.
+    pub fn remove_blocker(&mut self, id: BlockerId) -> Option<V> {
+        self.poisson.blockers.remove(id).map(|b| b.position)
+    }
+
+    /// Removes every blocker.
+    pub fn clear_blockers(&mut self) {
+        self.poisson.blockers.clear();
+    }
+
+    /// Advances the generation by at most the budget returning the samples generated during it.
+    /// The state is kept between the steps so the generation can be spread over, for example, the frames of a game.
+    pub fn step(&mut self, budget: Budget) -> Vec<V> {
+        let begin = Instant::now();
+        let mut points = vec![];
+        while !self.finished {
+            let spent = match budget {
+                Budget::Points(max) => max <= points.len(),
+                Budget::Time(limit) => limit <= begin.elapsed(),
+            };
+            if spent {
+                break;
+            }
+            points.extend(self.next());
+        }
+        points
+    }
+
+    /// Returns the amount of samples returned so far, counting the emitted start points.
+    pub fn emitted(&self) -> usize {
+        self.emitted
+    }
+
+    /// Estimates the amount of samples left to generate, for progress reports and deciding when to stop early.
+    /// The estimate is the amount of samples a maximal distribution with the radius has in the volume of the domain
+    /// less the ones returned or restricted before the generation, kept within the bounds of `size_hint`.
+    /// Shapes, regions and other variations of the spacing make the estimate only a rough guess.
+    pub fn remaining_estimate(&self) -> usize {
+        if self.finished {
+            return 0;
+        }
+        let (lower, upper) = self.size_hint();
+        let dim = V::dimension();
+        let estimate = maximal_density(dim).map_or(lower, |density| {
+            let ball = sphere_volume(self.poisson.radius, dim as u64)
+                .to_f64()
+                .unwrap_or(f64::NAN);
+            let prefilled =
+                self.poisson.start_points.len() - self.poisson.emitted_start_points().len();
+            let done = (self.emitted + prefilled) as f64;
+            let total = density * self.poisson.domain.volume(dim) / ball;
+            // Saturating cast turns NaN into 0 which the lower bound then overrides.
+            (total - done).max(0.) as usize
+        });
+        estimate.max(lower).min(upper.unwrap_or(usize::MAX))
+    }
+
+    /// Checks if the generation has ended so that stepping it further generates no samples.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns statistics about the generation thus far.
+    pub fn stats(&self) -> GenerationStats {
+        GenerationStats {
+            elapsed: self.elapsed,
+            ..self.algo.stats()
+        }
+    }
 }
@@ -0,0 +1,164 @@
+//! Module that contains records for reproducing and validating generated distributions.
+
+use crate::{Float, PointSet, Type, Vector};
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Compact record of a generated distribution.
+///
+/// The manifest can be stored alongside data derived from the distribution, like saved games or papers,
+/// and later used to check that a regenerated distribution is the same one.
+/// It is written as a single line of `key=value` pairs which can be parsed back with `str::parse`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manifest {
+    /// Version of the crate that generated the distribution.
+    pub version: String,
+    /// Name of the algorithm that generated the distribution if it's known.
+    pub algorithm: Option<String>,
+    /// Seed of the random number generator if it was recorded.
+    pub seed: Option<u64>,
+    /// Radius of the distribution.
+    pub radius: f64,
+    /// Type of the distribution.
+    pub poisson_type: Type,
+    /// Dimension of the samples.
+    pub dimension: usize,
+    /// Amount of samples.
+    pub count: usize,
+    /// Hash of the samples in order.
+    pub hash: u64,
+}
+
+impl Manifest {
+    /// Records the seed the random number generator was created from.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// Hashes the exact bits of the coordinates with FNV-1a so that the hash is the same on every platform.
+fn hash<F, V>(points: &[V]) -> u64
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for p in points {
+        for n in 0..V::dimension() {
+            let bits = p[n]
+                .to_f64()
+                .expect("Coordinate should be castable to f64.")
+                .to_bits();
+            for byte in bits.to_le_bytes() {
+                hash ^= byte as u64;
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+    }
+    hash
+}
+
+impl<F, V> PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates manifest of the distribution.
+    pub fn manifest(&self) -> Manifest {
+        Manifest {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            algorithm: self.algorithm().map(str::to_owned),
+            seed: None,
+            radius: self
+                .radius()
+                .to_f64()
+                .expect("Radius should be castable to f64."),
+            poisson_type: self.poisson_type(),
+            dimension: V::dimension(),
+            count: self.len(),
+            hash: hash(self.points()),
+        }
+    }
+
+    /// Checks that the distribution matches the manifest.
+    /// Version, algorithm and seed are informative and not compared.
+    pub fn verify_manifest(&self, manifest: &Manifest) -> bool {
+        let own = self.manifest();
+        own.radius == manifest.radius
+            && own.poisson_type == manifest.poisson_type
+            && own.dimension == manifest.dimension
+            && own.count == manifest.count
+            && own.hash == manifest.hash
+    }
+}
+
+impl fmt::Display for Manifest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "poisson={}", self.version)?;
+        if let Some(ref algorithm) = self.algorithm {
+            write!(f, " algorithm={}", algorithm)?;
+        }
+        if let Some(seed) = self.seed {
+            write!(f, " seed={}", seed)?;
+        }
+        let poisson_type = match self.poisson_type {
+            Type::Normal => "normal",
+            Type::Perioditic => "perioditic",
+        };
+        write!(
+            f,
+            " radius={:?} type={} dimension={} count={} hash={:016x}",
+            self.radius, poisson_type, self.dimension, self.count, self.hash
+        )
+    }
+}
+
+impl FromStr for Manifest {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |key: &str| format!("Invalid or missing {} in manifest: {}", key, s);
+        let mut manifest = Manifest {
+            version: String::new(),
+            algorithm: None,
+            seed: None,
+            radius: f64::NAN,
+            poisson_type: Type::Normal,
+            dimension: 0,
+            count: 0,
+            hash: 0,
+        };
+        let mut found = 0;
+        for pair in s.split_whitespace() {
+            let (key, value) = pair.split_once('=').ok_or_else(|| invalid(pair))?;
+            match key {
+                "poisson" => manifest.version = value.to_owned(),
+                "algorithm" => manifest.algorithm = Some(value.to_owned()),
+                "seed" => manifest.seed = Some(value.parse().map_err(|_| invalid(key))?),
+                "radius" => manifest.radius = value.parse().map_err(|_| invalid(key))?,
+                "type" => {
+                    manifest.poisson_type = match value {
+                        "normal" => Type::Normal,
+                        "perioditic" => Type::Perioditic,
+                        _ => return Err(invalid(key)),
+                    }
+                }
+                "dimension" => manifest.dimension = value.parse().map_err(|_| invalid(key))?,
+                "count" => manifest.count = value.parse().map_err(|_| invalid(key))?,
+                "hash" => {
+                    manifest.hash = u64::from_str_radix(value, 16).map_err(|_| invalid(key))?
+                }
+                _ => return Err(invalid(key)),
+            }
+            if !matches!(key, "algorithm" | "seed") {
+                found += 1;
+            }
+        }
+        if found != 6 {
+            return Err(invalid("field"));
+        }
+        Ok(manifest)
+    }
+}
@@ -0,0 +1,114 @@
+//! Module that contains export of distributions as seeds for mesh generation.
+
+use crate::{Float, PointSet, Vector};
+
+use num_traits::Float as NumFloat;
+
+use std::fmt::Write;
+
+/// Vertices and constrained boundary edges of a two dimensional mesh.
+///
+/// The vertices can be given to a constrained Delaunay triangulation as is,
+/// for example with `spade::ConstrainedDelaunayTriangulation::bulk_load_cdt(vertices, segments)`,
+/// or written in the `.poly` format of Triangle with `to_poly`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MeshSeeds<V> {
+    /// Vertices of the mesh.
+    pub vertices: Vec<V>,
+    /// Edges along the boundary of the domain as indices to the vertices.
+    pub segments: Vec<[usize; 2]>,
+}
+
+impl<F, V> PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates seeds for meshing the unit square with the samples as vertices.
+    ///
+    /// Samples on the boundary of the domain are connected in counterclockwise order around it with segments
+    /// and the corners are added as vertices if they are missing so that the boundary is closed.
+    /// Generating with `Builder::with_boundary_sampling` gives a well distributed boundary without extra vertices.
+    /// Only supported for two dimensional distributions whose axes are both open and as long as the unit.
+    pub fn mesh_seeds(&self) -> MeshSeeds<V> {
+        assert_eq!(2, V::dimension());
+        assert!((0..2).all(|n| !self.domain().is_perioditic(n)));
+        assert!(self.domain().is_unit());
+        let top = F::cast(1) - <F as NumFloat>::epsilon();
+        let mut vertices = self.points().to_vec();
+        for &(x, y) in &[(0, 0), (1, 0), (1, 1), (0, 1)] {
+            let mut corner = V::zero();
+            corner[0] = if x == 0 { F::cast(0) } else { top };
+            corner[1] = if y == 0 { F::cast(0) } else { top };
+            if !vertices.contains(&corner) {
+                vertices.push(corner);
+            }
+        }
+        let mut boundary = vertices
+            .iter()
+            .enumerate()
+            .filter_map(|(i, v)| perimeter(v, top).map(|s| (s, i)))
+            .collect::<Vec<_>>();
+        boundary.sort_by(|a, b| {
+            a.0.partial_cmp(&b.0)
+                .expect("Coordinates should not be NaN.")
+        });
+        let segments = (0..boundary.len())
+            .map(|n| [boundary[n].1, boundary[(n + 1) % boundary.len()].1])
+            .collect();
+        MeshSeeds { vertices, segments }
+    }
+}
+
+/// Returns the position of the vertex along the boundary of the unit square going counterclockwise from the origin
+/// or `None` if the vertex is in the interior.
+fn perimeter<F, V>(v: &V, top: F) -> Option<F>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let (x, y) = (v[0], v[1]);
+    if y <= F::cast(0) {
+        Some(x)
+    } else if x >= top {
+        Some(F::cast(1) + y)
+    } else if y >= top {
+        Some(F::cast(3) - x)
+    } else if x <= F::cast(0) {
+        Some(F::cast(4) - y)
+    } else {
+        None
+    }
+}
+
+impl<V> MeshSeeds<V> {
+    /// Writes the seeds in the `.poly` format of Triangle with zero based indices and every segment marked as boundary.
+    pub fn to_poly<F>(&self) -> String
+    where
+        F: Float,
+        V: Vector<F>,
+    {
+        let mut poly = String::new();
+        writeln!(poly, "{} 2 0 1", self.vertices.len()).expect("Writing to string can't fail.");
+        for (i, v) in self.vertices.iter().enumerate() {
+            let marker = usize::from(self.segments.iter().any(|s| s.contains(&i)));
+            writeln!(
+                poly,
+                "{} {} {} {}",
+                i,
+                v[0].to_f64()
+                    .expect("Coordinate should be castable to f64."),
+                v[1].to_f64()
+                    .expect("Coordinate should be castable to f64."),
+                marker
+            )
+            .expect("Writing to string can't fail.");
+        }
+        writeln!(poly, "{} 1", self.segments.len()).expect("Writing to string can't fail.");
+        for (i, s) in self.segments.iter().enumerate() {
+            writeln!(poly, "{} {} {} 1", i, s[0], s[1]).expect("Writing to string can't fail.");
+        }
+        writeln!(poly, "0").expect("Writing to string can't fail.");
+        poly
+    }
+}
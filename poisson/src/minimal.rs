@@ -0,0 +1,199 @@
+//! Module that contains generation that depends only on `rand` and `num-traits`.
+//!
+//! Everything else in the crate needs the default `full` feature, so adding the crate with `default-features = false`
+//! builds only this, for embedding it in constrained or audit-sensitive environments.
+//! The samples are arrays so no linear algebra crate is needed either.
+//!
+//! The generation here is a separate and much smaller implementation of the algorithm of Bridson, not the one behind
+//! `algorithm::Bridson` with the options of `Builder` turned off, so fixes to one don't carry over to the other.
+
+use crate::Type;
+
+use num_traits::Float;
+
+use rand::distr::{Distribution, StandardUniform};
+use rand::Rng;
+
+/// Amount of samples tried around an active sample before deactivating it.
+const ATTEMPTS: usize = 30;
+
+/// Generates poisson-disk distribution in [0, 1)<sup>N</sup> with given disk radius using the algorithm of Bridson.
+/// It doesn't share code with `algorithm::Bridson` and for the same random number generator
+/// it generates different samples than it does.
+///
+/// ````rust
+/// # use poisson::{minimal, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// let samples: Vec<[f64; 2]> = minimal::generate(0.05, Type::Perioditic, &mut SmallRng::seed_from_u64(1));
+/// assert!(!samples.is_empty());
+/// ````
+pub fn generate<F, R, const N: usize>(radius: F, poisson_type: Type, rng: &mut R) -> Vec<[F; N]>
+where
+    F: Float,
+    R: Rng + ?Sized,
+    StandardUniform: Distribution<F>,
+{
+    assert!(N > 0);
+    assert!(F::zero() < radius);
+    let mut grid = Grid::new(radius, poisson_type);
+    let first = wrap([F::zero(); N].map(|_| rng.random::<F>()));
+    grid.insert(first);
+    let mut active = vec![0];
+    while !active.is_empty() {
+        let index = rng.random_range(0..active.len());
+        let current = grid.samples[active[index]];
+        let mut found = false;
+        for _ in 0..ATTEMPTS {
+            let mut candidate = annulus(rng, current, grid.diameter);
+            if grid.perioditic {
+                candidate = wrap(candidate);
+            } else if candidate.iter().any(|&c| c < F::zero() || F::one() <= c) {
+                continue;
+            }
+            if grid.is_free(&candidate) {
+                active.push(grid.samples.len());
+                grid.insert(candidate);
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            active.swap_remove(index);
+        }
+    }
+    grid.samples
+}
+
+/// Returns random point whose distance from the center is between the diameter and twice it.
+fn annulus<F, R, const N: usize>(rng: &mut R, center: [F; N], diameter: F) -> [F; N]
+where
+    F: Float,
+    R: Rng + ?Sized,
+    StandardUniform: Distribution<F>,
+{
+    let two = F::one() + F::one();
+    loop {
+        let offset = [F::zero(); N].map(|_| (rng.random::<F>() * two - F::one()) * two * diameter);
+        let sqnorm = offset.iter().fold(F::zero(), |sum, &o| sum + o * o);
+        if diameter * diameter <= sqnorm && sqnorm <= two * two * diameter * diameter {
+            let mut point = center;
+            for (p, o) in point.iter_mut().zip(offset) {
+                *p = *p + o;
+            }
+            return point;
+        }
+    }
+}
+
+/// Wraps the point into [0, 1)<sup>N</sup>.
+fn wrap<F, const N: usize>(point: [F; N]) -> [F; N]
+where
+    F: Float,
+{
+    point.map(|c| {
+        let c = c - c.floor();
+        // Rounding can land tiny negative values exactly on 1.
+        if c < F::one() {
+            c
+        } else {
+            F::zero()
+        }
+    })
+}
+
+/// Background grid whose cells hold at most one sample each.
+struct Grid<F, const N: usize> {
+    samples: Vec<[F; N]>,
+    cells: Vec<Option<usize>>,
+    side: usize,
+    reach: isize,
+    diameter: F,
+    perioditic: bool,
+}
+
+impl<F, const N: usize> Grid<F, N>
+where
+    F: Float,
+{
+    fn new(radius: F, poisson_type: Type) -> Self {
+        let diameter = radius + radius;
+        let cast = |n: usize| F::from(n).expect("Casting usize to float should always succeed.");
+        // Diagonal of the cells is at most the diameter, so two samples can't share a cell.
+        let side = (cast(N).sqrt() / diameter)
+            .ceil()
+            .to_usize()
+            .expect("Amount of cells should fit in usize.")
+            .max(1);
+        let reach = (diameter * cast(side))
+            .ceil()
+            .to_isize()
+            .expect("Reach of the disk should fit in isize.");
+        Grid {
+            samples: vec![],
+            cells: vec![None; side.pow(N as u32)],
+            side,
+            reach,
+            diameter,
+            perioditic: poisson_type == Type::Perioditic,
+        }
+    }
+
+    fn cell(&self, point: &[F; N]) -> [isize; N] {
+        let side = F::from(self.side).expect("Casting usize to float should always succeed.");
+        point.map(|c| {
+            (c * side)
+                .floor()
+                .to_isize()
+                .expect("Cell should fit in isize.")
+                .min(self.side as isize - 1)
+        })
+    }
+
+    fn index(&self, cell: &[isize; N]) -> usize {
+        cell.iter()
+            .rev()
+            .fold(0, |index, &c| index * self.side + c as usize)
+    }
+
+    fn insert(&mut self, point: [F; N]) {
+        let index = self.index(&self.cell(&point));
+        self.cells[index] = Some(self.samples.len());
+        self.samples.push(point);
+    }
+
+    fn is_free(&self, point: &[F; N]) -> bool {
+        let center = self.cell(point);
+        let side = self.side as isize;
+        let width = 2 * self.reach + 1;
+        // Goes through the cells within reach like an odometer with each axis as a digit.
+        'cells: for n in 0..width.pow(N as u32) {
+            let mut cell = center;
+            let mut rest = n;
+            for c in cell.iter_mut() {
+                *c += rest % width - self.reach;
+                rest /= width;
+                if self.perioditic {
+                    *c = c.rem_euclid(side);
+                } else if *c < 0 || side <= *c {
+                    continue 'cells;
+                }
+            }
+            if let Some(sample) = self.cells[self.index(&cell)] {
+                let sqdist = self.samples[sample]
+                    .iter()
+                    .zip(point)
+                    .fold(F::zero(), |sum, (&a, &b)| {
+                        let mut d = a - b;
+                        if self.perioditic {
+                            d = d - d.round();
+                        }
+                        sum + d * d
+                    });
+                if sqdist < self.diameter * self.diameter {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
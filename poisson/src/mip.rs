@@ -0,0 +1,172 @@
+//! Module that contains hierarchies of perioditic distributions for continuous level of detail.
+
+use crate::algorithm::Creator;
+use crate::utils::{is_disk_free, sample_to_index, Grid};
+use crate::{Builder, Float, PointSet, Precision, Type, Vector};
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Amount of times the radius of a level is bisected to get its amount of samples close to half of the level before it.
+const BISECTIONS: usize = 16;
+
+/// Hierarchy of perioditic distributions like the levels of a mip chain, where each level has about half of
+/// the samples of the level before it and its samples are a subset of the samples of that level.
+/// This gives blue-noise with continuous level of detail, as shaders can blend between the levels
+/// without samples popping in at new places.
+///
+/// Each level is a maximal subset of the level before it for its radius, with the radius picked so that the amount of
+/// samples is as close to half as it can be. The samples of every level are ordered so that the samples of the levels
+/// after it come first, which makes every level a prefix of the finest one.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, MipChain, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// let builder = Builder::<_, na::Vector2<f64>>::with_samples(1000, 0.8, Type::Perioditic);
+/// let chain = MipChain::generate(&builder, 4, SmallRng::seed_from_u64(3), algorithm::Bridson);
+/// let (finest, next) = (&chain.levels()[0], &chain.levels()[1]);
+/// assert_eq!(&finest.points()[..next.len()], next.points());
+/// ````
+#[derive(Clone, Debug)]
+pub struct MipChain<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    levels: Vec<PointSet<F, V>>,
+}
+
+impl<F, V> MipChain<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Generates the finest level with the builder and then the given amount of levels in total from it.
+    /// The builder has to be perioditic and the amount of levels larger than 0.
+    pub fn generate<R, A>(poisson: &Builder<F, V>, levels: usize, mut rng: R, algo: A) -> Self
+    where
+        R: Rng + Clone,
+        A: Creator<F, V>,
+    {
+        assert!(
+            Type::Perioditic == poisson.poisson_type(),
+            "Only perioditic distributions can be used as textures."
+        );
+        assert!(0 < levels);
+        let finest = poisson.clone().build(rng.clone(), algo).generate_set();
+        let mut chain = vec![finest];
+        // Indices of the samples of each level in the level before it.
+        let mut picked = vec![];
+        while chain.len() < levels {
+            let prev = chain.last().expect("There is always the finest level.");
+            let mut order = (0..prev.len()).collect::<Vec<_>>();
+            order.shuffle(&mut rng);
+            let (indices, radius) = halve(prev, &order);
+            let points = indices.iter().map(|&i| prev.points()[i].clone()).collect();
+            chain.push(PointSet::new(points, radius, prev.domain()));
+            picked.push(indices);
+        }
+        // Moves the samples kept by the coarser level to the front starting from the coarsest level,
+        // where `moved` tells which sample of the level before reordering is at each position.
+        let mut moved = (0..chain[chain.len() - 1].len()).collect::<Vec<_>>();
+        for k in (0..chain.len() - 1).rev() {
+            let mut order = moved.iter().map(|&j| picked[k][j]).collect::<Vec<_>>();
+            let mut kept = vec![false; chain[k].len()];
+            for &i in &order {
+                kept[i] = true;
+            }
+            order.extend((0..chain[k].len()).filter(|&i| !kept[i]));
+            let level = &chain[k];
+            let points = order.iter().map(|&i| level.points()[i].clone()).collect();
+            chain[k] = PointSet::new(points, level.radius(), level.domain());
+            moved = order;
+        }
+        MipChain { levels: chain }
+    }
+
+    /// Returns the levels from the finest to the coarsest.
+    pub fn levels(&self) -> &[PointSet<F, V>] {
+        &self.levels
+    }
+
+    /// Returns the amount of levels.
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Checks if there are no levels, which can't happen for generated chains.
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Writes the levels as layers of a little-endian texture array of `f32` texels with a channel for each axis.
+    /// Every layer is as wide as the amount of samples in the finest level and the texels after the samples of
+    /// a level are NaN. Because levels are prefixes of the finest one, texel `i` is the same sample
+    /// on every layer that has it.
+    pub fn to_texture_array(&self) -> Vec<u8> {
+        let dim = V::dimension();
+        let width = self.levels[0].len();
+        let mut texture = Vec::with_capacity(4 * dim * width * self.levels.len());
+        for level in &self.levels {
+            for p in level {
+                for n in 0..dim {
+                    let c = p[n].to_f32().expect("Coordinate should be castable to f32.");
+                    texture.extend_from_slice(&c.to_le_bytes());
+                }
+            }
+            for _ in 0..dim * (width - level.len()) {
+                texture.extend_from_slice(&f32::NAN.to_le_bytes());
+            }
+        }
+        texture
+    }
+}
+
+/// Picks the maximal subset of the level with the radius that keeps the amount of samples closest to half,
+/// returning the indices of the samples and the radius.
+/// Samples are picked in the given order so every radius tried sees them in the same order.
+fn halve<F, V>(level: &PointSet<F, V>, order: &[usize]) -> (Vec<usize>, F)
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let target = level.len().div_ceil(2);
+    let (mut low, mut high) = (level.radius(), F::cast(2) * level.radius());
+    let mut best = (subset(level, order, high), high);
+    for _ in 0..BISECTIONS {
+        let radius = (low + high) / F::cast(2);
+        let candidate = subset(level, order, radius);
+        let len = candidate.len();
+        if len.abs_diff(target) <= best.0.len().abs_diff(target) {
+            best = (candidate, radius);
+        }
+        if len > target {
+            low = radius;
+        } else {
+            high = radius;
+        }
+    }
+    best
+}
+
+/// Greedily picks the samples in order that don't conflict with the samples picked before them.
+fn subset<F, V>(level: &PointSet<F, V>, order: &[usize], radius: F) -> Vec<usize>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let poisson = Builder::with_disk_radius(radius, level.domain());
+    let mut grid = Grid::new(radius, level.domain(), Precision::Full);
+    let mut indices = vec![];
+    for &i in order {
+        let p = &level.points()[i];
+        let index = sample_to_index(p, grid.side());
+        if is_disk_free(&grid, &poisson, index.clone(), 0, p.clone(), &[]) {
+            grid.insert(index, p.clone())
+                .unwrap_or_else(|_| panic!("Because the sample is [0, 1) indexing it should work."));
+            indices.push(i);
+        }
+    }
+    indices
+}
@@ -0,0 +1,31 @@
+//! Mirrors of the vector types of other math crates that implement `Vector`.
+//!
+//! Traits of alga can't be implemented for vectors of other crates, so each mirror wraps the vector
+//! and has the same layout as it. Slices and vectors of samples can be reinterpreted between the mirror and
+//! the wrapped vector without copying them.
+
+use crate::utils::vector::mirror;
+
+#[cfg(feature = "cgmath")]
+mirror! {
+    /// Mirror of `cgmath::Vector2`.
+    CgVector2(cgmath::Vector2<F>) { 0 => x, 1 => y }
+}
+
+#[cfg(feature = "cgmath")]
+mirror! {
+    /// Mirror of `cgmath::Vector3`.
+    CgVector3(cgmath::Vector3<F>) { 0 => x, 1 => y, 2 => z }
+}
+
+#[cfg(feature = "euclid")]
+mirror! {
+    /// Mirror of `euclid::default::Vector2D`.
+    EuVector2(euclid::default::Vector2D<F>) { 0 => x, 1 => y }
+}
+
+#[cfg(feature = "euclid")]
+mirror! {
+    /// Mirror of `euclid::default::Vector3D`.
+    EuVector3(euclid::default::Vector3D<F>) { 0 => x, 1 => y, 2 => z }
+}
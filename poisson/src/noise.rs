@@ -0,0 +1,149 @@
+//! Module that contains fractal noise for modulating the density of distributions.
+
+use crate::{Float, SeedTree, Vector};
+
+use num_traits::{Float as NumFloat, NumCast};
+
+/// Fractal Brownian motion made of octaves of gradient (Perlin) noise in any dimension.
+///
+/// Each octave is gradient noise sampled at `lacunarity` times the frequency of the previous one and weighted
+/// `persistence` times its weight. The gradients at the lattice points are derived from the seed with `SeedTree`
+/// so the noise is the same on every platform.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fbm {
+    seed: u64,
+    octaves: usize,
+    frequency: f64,
+    lacunarity: f64,
+    persistence: f64,
+}
+
+impl Default for Fbm {
+    fn default() -> Self {
+        Fbm::new(0)
+    }
+}
+
+impl Fbm {
+    /// New noise with given seed, 4 octaves, frequency 4, lacunarity 2 and persistence 0.5.
+    pub fn new(seed: u64) -> Self {
+        Fbm {
+            seed,
+            octaves: 4,
+            frequency: 4.,
+            lacunarity: 2.,
+            persistence: 0.5,
+        }
+    }
+
+    /// Sets the amount of octaves.
+    pub fn with_octaves(mut self, octaves: usize) -> Self {
+        assert!(octaves > 0);
+        self.octaves = octaves;
+        self
+    }
+
+    /// Returns the amount of octaves.
+    pub fn octaves(&self) -> usize {
+        self.octaves
+    }
+
+    /// Sets the frequency of the first octave as lattice cells along the side of [0, 1)<sup>d</sup>.
+    pub fn with_frequency(mut self, frequency: f64) -> Self {
+        assert!(frequency > 0.);
+        self.frequency = frequency;
+        self
+    }
+
+    /// Returns the frequency of the first octave.
+    pub fn frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    /// Sets the multiplier of the frequency between octaves.
+    pub fn with_lacunarity(mut self, lacunarity: f64) -> Self {
+        assert!(lacunarity > 0.);
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Returns the multiplier of the frequency between octaves.
+    pub fn lacunarity(&self) -> f64 {
+        self.lacunarity
+    }
+
+    /// Sets the multiplier of the weight between octaves.
+    pub fn with_persistence(mut self, persistence: f64) -> Self {
+        assert!(persistence > 0.);
+        self.persistence = persistence;
+        self
+    }
+
+    /// Returns the multiplier of the weight between octaves.
+    pub fn persistence(&self) -> f64 {
+        self.persistence
+    }
+
+    /// Returns the value of the noise at the position in [0, 1].
+    pub fn get<F, V>(&self, position: &V) -> F
+    where
+        F: Float,
+        V: Vector<F>,
+    {
+        let point = (0..V::dimension())
+            .map(|n| {
+                position[n]
+                    .to_f64()
+                    .expect("Float should be castable to f64.")
+            })
+            .collect::<Vec<_>>();
+        let tree = SeedTree::new(self.seed);
+        let (mut sum, mut total, mut weight, mut frequency) = (0., 0., 1., self.frequency);
+        for octave in 0..self.octaves {
+            let scaled = point.iter().map(|p| p * frequency).collect::<Vec<_>>();
+            sum += weight * gradient_noise(tree.child(octave as u64), &scaled);
+            total += weight;
+            weight *= self.persistence;
+            frequency *= self.lacunarity;
+        }
+        let value = (sum / total + 1.) / 2.;
+        NumCast::from(value.clamp(0., 1.)).expect("f64 should be castable to float.")
+    }
+}
+
+/// Gradient noise in [-1, 1] interpolating the dot products with the gradients at the corners of the lattice cell.
+fn gradient_noise(tree: SeedTree, point: &[f64]) -> f64 {
+    let dim = point.len();
+    let base = point.iter().map(|p| p.floor()).collect::<Vec<_>>();
+    let mut value = 0.;
+    for corner in 0..1usize << dim {
+        let mut lattice = tree;
+        let mut weight = 1.;
+        let mut offsets = Vec::with_capacity(dim);
+        for n in 0..dim {
+            let upper = corner & 1 << n != 0;
+            let offset = point[n] - base[n] - if upper { 1. } else { 0. };
+            let t = point[n] - base[n];
+            weight *= if upper { fade(t) } else { 1. - fade(t) };
+            lattice = lattice.child((base[n] as i64 as u64).wrapping_add(upper as u64));
+            offsets.push(offset);
+        }
+        let (mut dot, mut length) = (0., 0.);
+        for (n, offset) in offsets.iter().enumerate() {
+            let g = (lattice.child(n as u64).seed() >> 11) as f64 / (1u64 << 52) as f64 - 1.;
+            length += g * g;
+            dot += g * offset;
+        }
+        if length > 0. {
+            dot /= NumFloat::sqrt(length);
+        }
+        value += weight * dot;
+    }
+    // Dot product with unit gradient is at most half of the diagonal of the cell away from the middle.
+    (value * 2. / NumFloat::sqrt(dim as f64)).clamp(-1., 1.)
+}
+
+/// Quintic smoothstep with zero first and second derivatives at the ends.
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6. - 15.) + 10.)
+}
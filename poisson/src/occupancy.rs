@@ -0,0 +1,106 @@
+//! Module that contains images of how samples occupy the cells of the acceleration grid.
+
+use crate::utils::sample_to_index;
+use crate::{Domain, Float, Vector};
+
+/// Amount of samples in each cell of the grid the algorithms use to accelerate the checks, laid out as an image
+/// for debugging density anomalies such as holes or clumps along cell boundaries.
+///
+/// The first axis is the width and the second one the height of the image. Distributions with more than two
+/// dimensions are split into slices along the rest of the axes, and one dimensional ones have the height of one cell.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// let generator = Builder::<_, na::Vector2<f64>>::with_disk_radius(0.05, Type::Normal)
+///     .build(SmallRng::seed_from_u64(2), algorithm::Bridson);
+/// let image = generator.grid_occupancy_image();
+/// let occupied = image.slice(0).iter().filter(|&&c| c > 0).count();
+/// assert_eq!(generator.generate().len(), occupied);
+/// ````
+#[derive(Clone, Debug, PartialEq)]
+pub struct OccupancyImage<F>
+where
+    F: Float,
+{
+    cells: Vec<usize>,
+    counts: Vec<u32>,
+    cell: F,
+}
+
+impl<F> OccupancyImage<F>
+where
+    F: Float,
+{
+    pub(crate) fn new<V>(points: &[V], side: usize, cell: F, domain: Domain) -> Self
+    where
+        V: Vector<F>,
+    {
+        let dim = V::dimension();
+        let cells = (0..dim).map(|n| domain.cells(n, side)).collect::<Vec<_>>();
+        let mut counts = vec![0; cells.iter().product()];
+        for p in points {
+            let index = sample_to_index(p, side);
+            // The first axis varies fastest so that rows of the image are contiguous.
+            let mut t = 0;
+            for n in (0..dim).rev() {
+                let cur = index[n]
+                    .to_usize()
+                    .unwrap_or(0)
+                    .min(cells[n] - 1);
+                t = t * cells[n] + cur;
+            }
+            counts[t] += 1;
+        }
+        OccupancyImage {
+            cells,
+            counts,
+            cell,
+        }
+    }
+
+    /// Returns the amount of cells along the first axis.
+    pub fn width(&self) -> usize {
+        self.cells[0]
+    }
+
+    /// Returns the amount of cells along the second axis, or one if there is only one axis.
+    pub fn height(&self) -> usize {
+        self.cells.get(1).copied().unwrap_or(1)
+    }
+
+    /// Returns the amount of slices along the axes after the second one, or one if there are at most two axes.
+    pub fn slices(&self) -> usize {
+        self.cells.iter().skip(2).product()
+    }
+
+    /// Returns the amount of cells along each axis.
+    pub fn cells(&self) -> &[usize] {
+        &self.cells
+    }
+
+    /// Returns the length of the edges of the cells.
+    /// The cells at the end of axes with extent that isn't a whole amount of cells are cut short.
+    pub fn cell(&self) -> F {
+        self.cell
+    }
+
+    /// Returns the amount of samples in the cell of the slice.
+    /// Slices are ordered so that the third axis varies fastest.
+    pub fn get(&self, x: usize, y: usize, slice: usize) -> u32 {
+        assert!(x < self.width() && y < self.height() && slice < self.slices());
+        self.counts[(slice * self.height() + y) * self.width() + x]
+    }
+
+    /// Returns the amounts of samples in the cells of the slice row by row.
+    pub fn slice(&self, slice: usize) -> &[u32] {
+        let area = self.width() * self.height();
+        &self.counts[slice * area..(slice + 1) * area]
+    }
+
+    /// Returns the largest amount of samples in a single cell.
+    pub fn max(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+}
@@ -0,0 +1,138 @@
+//! Module that contains generation prepared once and executed with many random number generators.
+
+use crate::algorithm::{Algorithm, Creator, Selected};
+use crate::utils::Grid;
+use crate::{Builder, Float, PoissonIter, Precision, Vector};
+
+use rand::Rng;
+
+use std::fmt;
+use std::mem;
+
+/// Generation split into the setup, which is done once by `Builder::plan`, and `execute`,
+/// which is done for each random number generator.
+///
+/// The plan tells the size of the acceleration grid, the bounds for the amount of samples and the memory the grid
+/// takes before anything is generated, so costly parameters can be caught and adjusted.
+/// Executing reuses the algorithm created by the setup, with its grid and the start points already in it,
+/// so generating many distributions with different seeds doesn't repeat the work.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// let plan = Builder::<_, na::Vector2<f64>>::with_disk_radius(0.01, Type::Normal).plan(algorithm::Ebeida);
+/// assert!(plan.memory() < 1 << 20);
+/// for seed in 0..4 {
+///     let samples = plan.execute(SmallRng::seed_from_u64(seed)).collect::<Vec<_>>();
+///     assert!(!samples.is_empty());
+/// }
+/// ````
+pub struct Plan<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    poisson: Builder<F, V>,
+    creator: A,
+    algo: Selected<F, V, A::Algo>,
+    grid_side: usize,
+    grid_cells: usize,
+}
+
+impl<F, V, A> Plan<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    pub(crate) fn new(poisson: Builder<F, V>, creator: A) -> Self {
+        let grid = Grid::<F, V>::scaled(
+            poisson.radius,
+            poisson.cell_scale,
+            poisson.domain,
+            poisson.precision,
+        );
+        Plan {
+            algo: Selected::create::<A>(&poisson),
+            poisson,
+            creator,
+            grid_side: grid.side(),
+            grid_cells: grid.cells(),
+        }
+    }
+
+    /// Returns the builder the plan was made from.
+    pub fn builder(&self) -> &Builder<F, V> {
+        &self.poisson
+    }
+
+    /// Returns the amount of cells of the acceleration grid along an axis of the unit length.
+    pub fn grid_side(&self) -> usize {
+        self.grid_side
+    }
+
+    /// Returns the amount of cells of the acceleration grid in the domain.
+    pub fn grid_cells(&self) -> usize {
+        self.grid_cells
+    }
+
+    /// Returns the bounds for the amount of samples generated after the start points,
+    /// like the size hint of a fresh iterator.
+    pub fn size_hint(&self) -> (usize, Option<usize>) {
+        self.algo.size_hint(&self.poisson)
+    }
+
+    /// Estimates the bytes the acceleration grid takes at the end of the generation.
+    /// The amount of samples is the upper bound of `size_hint`, or one sample for each cell if there is none.
+    pub fn memory(&self) -> usize {
+        let coordinate = match self.poisson.precision {
+            Precision::Full => mem::size_of::<F>(),
+            Precision::Single => mem::size_of::<f32>(),
+            #[cfg(feature = "half")]
+            Precision::Half => mem::size_of::<half::f16>(),
+        };
+        let samples = self
+            .size_hint()
+            .1
+            .unwrap_or(self.grid_cells)
+            .saturating_add(self.poisson.start_points.len());
+        let sample = V::dimension() * coordinate + mem::size_of::<usize>();
+        self.grid_cells
+            .saturating_mul(mem::size_of::<usize>())
+            .saturating_add(samples.saturating_mul(sample))
+    }
+
+    /// Starts generating with the random number generator from the state prepared by the setup.
+    /// The samples are the same as `Builder::build` with the same random number generator and algorithm generates.
+    /// Boundary sampling uses the random number generator so with it the setup is repeated.
+    pub fn execute<R>(&self, rng: R) -> PoissonIter<F, V, R, Selected<F, V, A::Algo>>
+    where
+        R: Rng,
+        A::Algo: Clone,
+    {
+        if self.poisson.boundary {
+            return self.poisson.clone().build(rng, self.creator).into_iter();
+        }
+        PoissonIter::prepared(self.poisson.clone(), rng, self.algo.clone())
+    }
+}
+
+impl<F, V, A> fmt::Debug for Plan<F, V, A>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Creator<F, V>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Plan")
+            .field("radius", &self.poisson.radius)
+            .field("dimension", &V::dimension())
+            .field("domain", &self.poisson.domain)
+            .field("algorithm", &self.creator)
+            .field("grid_side", &self.grid_side)
+            .field("grid_cells", &self.grid_cells)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,167 @@
+//! Ready-made builders tuned for common uses.
+//!
+//! Each preset returns a normal builder so it can be tuned further before building the generator.
+
+use crate::shape::Ball;
+use crate::{algorithm, Builder, Float, Type, Vector};
+
+use rand::distr::StandardUniform;
+use rand::Rng;
+use rand_distr::Distribution;
+
+/// Dense and even distribution for digital stippling with approximately the given amount of dots.
+/// Works best with `algorithm::Ebeida` which generates maximal distribution without visible gaps.
+///
+/// ````rust
+/// # extern crate nalgebra as na;
+/// use poisson::{algorithm, presets};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let dots = presets::stippling::<f64, na::Vector2<f64>>(500)
+///     .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+///     .generate();
+/// assert!(!dots.is_empty());
+/// ````
+pub fn stippling<F, V>(dots: usize) -> Builder<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    Builder::with_samples(dots, F::cast(9) / F::cast(10), Type::Normal)
+}
+
+/// Looser distribution for scattering trees or other props with approximately the given amount of them.
+/// Works best with `algorithm::Bridson` whose non-maximal result looks more natural than a perfectly even one.
+///
+/// ````rust
+/// # extern crate nalgebra as na;
+/// use poisson::{algorithm, presets};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let trees = presets::forest_scatter::<f32, na::Vector2<f32>>(200)
+///     .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+///     .generate();
+/// assert!(!trees.is_empty());
+/// ````
+pub fn forest_scatter<F, V>(trees: usize) -> Builder<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    Builder::with_samples(trees, F::cast(7) / F::cast(10), Type::Normal)
+}
+
+/// Sparse and irregular distribution for star fields with at most the given amount of stars.
+/// Works best with `algorithm::DartThrowing` whose low rejection limit leaves uneven gaps between the stars.
+///
+/// ````rust
+/// # extern crate nalgebra as na;
+/// use poisson::{algorithm, presets};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let stars = presets::star_field::<f64, na::Vector3<f64>>(100)
+///     .build(SmallRng::seed_from_u64(1), algorithm::DartThrowing)
+///     .generate();
+/// assert!(stars.len() <= 100);
+/// ````
+pub fn star_field<F, V>(stars: usize) -> Builder<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    Builder::with_samples(stars, F::cast(1) / F::cast(2), Type::Normal)
+        .with_rejection_limit(30)
+        .with_max_points(stars)
+}
+
+/// Perioditic distribution for seamlessly tiling textures with approximately the given amount of samples per tile.
+/// Works with every algorithm.
+///
+/// ````rust
+/// # extern crate nalgebra as na;
+/// use poisson::{algorithm, presets};
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let tile = presets::texture_tile::<f64, na::Vector2<f64>>(300)
+///     .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+///     .generate_set();
+/// let (texture, _) = tile.tile(&[2, 2]);
+/// assert_eq!(4 * tile.len(), texture.len());
+/// ````
+pub fn texture_tile<F, V>(samples: usize) -> Builder<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    Builder::with_samples(samples, F::cast(85) / F::cast(100), Type::Perioditic)
+}
+
+/// Kernel of exactly the given amount of samples inside the unit disk centered at the origin sorted by their distance
+/// to the origin, like the ones used for percentage-closer filtering of shadows or ambient occlusion.
+/// In higher dimensions the samples are inside the unit ball instead.
+/// The radius is bisected to the largest one whose maximal distribution has at least the given amount of samples
+/// inside the disk, and the samples generated last are dropped to get the exact amount.
+///
+/// ````rust
+/// # extern crate nalgebra as na;
+/// use poisson::presets;
+/// use rand::{rngs::SmallRng, SeedableRng};
+///
+/// let kernel = presets::disk_kernel::<f32, na::Vector2<f32>, _>(16, SmallRng::seed_from_u64(1));
+/// assert_eq!(16, kernel.len());
+/// assert!(kernel.iter().all(|p| p.norm() <= 1.));
+/// assert!(kernel.windows(2).all(|w| w[0].norm() <= w[1].norm()));
+/// ````
+pub fn disk_kernel<F, V, R>(count: usize, rng: R) -> Vec<V>
+where
+    F: Float,
+    V: Vector<F>,
+    R: Rng + Clone,
+    StandardUniform: Distribution<F> + Distribution<V>,
+{
+    assert!(count > 0);
+    let half = F::cast(1) / F::cast(2);
+    let mut center = V::zero();
+    for n in 0..V::dimension() {
+        center[n] = half;
+    }
+    let disk = Ball {
+        center: center.clone(),
+        radius: half,
+    };
+    let generate = |radius: F| {
+        Builder::<F, V>::with_disk_radius(radius, Type::Normal)
+            .build(rng.clone(), algorithm::Ebeida)
+            .generate_set()
+            .retain_within(&disk)
+            .into_points()
+    };
+    let (mut low, mut high) = (half / F::cast(2), half);
+    let mut best = generate(low);
+    while best.len() < count {
+        high = low;
+        low /= F::cast(2);
+        best = generate(low);
+    }
+    for _ in 0..32 {
+        let middle = (low + high) / F::cast(2);
+        let samples = generate(middle);
+        if samples.len() >= count {
+            low = middle;
+            best = samples;
+        } else {
+            high = middle;
+        }
+    }
+    best.truncate(count);
+    let mut kernel = best
+        .into_iter()
+        .map(|p| (p - center.clone()) * F::cast(2))
+        .collect::<Vec<_>>();
+    kernel.sort_by(|a, b| {
+        a.norm_squared()
+            .partial_cmp(&b.norm_squared())
+            .expect("Distances should not be NaN.")
+    });
+    kernel
+}
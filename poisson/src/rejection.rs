@@ -0,0 +1,36 @@
+//! Module that contains explanations for why candidates are rejected.
+
+use crate::{Float, Vector};
+
+/// Rule that blocks a candidate sample from the distribution.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Rejection<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// The candidate is inside the exclusion zone of an existing sample, or the sample inside the zone of the candidate.
+    /// When there are several such samples this is the closest one.
+    Conflict {
+        /// The existing sample.
+        sample: V,
+        /// Distance between the samples, wrapping around the perioditic axes of the domain.
+        distance: F,
+    },
+    /// The candidate is outside of the shape the samples are restricted inside of.
+    OutsideShape,
+    /// The exclusion zone of the candidate overlaps a dynamic blocker added with `PoissonIter::add_blocker`
+    /// or a segment obstacle added with `Builder::with_segment_obstacle`.
+    /// When there are several such blockers this is the closest one.
+    Blocked {
+        /// Position of the blocker, or the point of the segment closest to the candidate.
+        position: V,
+        /// Distance between the candidate and the blocker, wrapping around the perioditic axes of the domain.
+        distance: F,
+    },
+    /// The candidate is rejected by a custom check added with `Builder::with_legality_check`.
+    Illegal {
+        /// Index of the first check that rejects the candidate in the order the checks were added.
+        check: usize,
+    },
+}
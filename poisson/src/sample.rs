@@ -0,0 +1,51 @@
+//! Module that contains samples carrying their own exclusion radius.
+
+use crate::{Float, Vector};
+
+/// Sample of a distribution together with the radius of its exclusion zone.
+/// With uniform radius every sample has the radius of the distribution,
+/// while with regions each sample has the radius of the region it's in.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sample<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    point: V,
+    radius: F,
+}
+
+impl<F, V> Sample<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates sample at the point with given radius.
+    pub fn new(point: V, radius: F) -> Self {
+        assert!(F::cast(0) < radius);
+        Sample { point, radius }
+    }
+
+    /// Returns the position of the sample.
+    pub fn point(&self) -> &V {
+        &self.point
+    }
+
+    /// Returns the radius of the exclusion zone of the sample.
+    pub fn radius(&self) -> F {
+        self.radius
+    }
+
+    /// Consumes the sample returning its position.
+    pub fn into_point(self) -> V {
+        self.point
+    }
+
+    /// Converts the samples into their positions.
+    pub fn into_points<I>(samples: I) -> Vec<V>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        samples.into_iter().map(Sample::into_point).collect()
+    }
+}
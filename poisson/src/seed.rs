@@ -0,0 +1,122 @@
+//! Module that contains splitting of random number generator seeds.
+
+use rand::{Rng, SeedableRng};
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Tree of seeds where every node derives its children from its own seed alone.
+///
+/// Parallel and tiled generators give each task the child with the index of the task,
+/// so their results don't depend on the amount of threads or the order tasks are scheduled in.
+/// Drivers that split the work in their own way can do the same to stay reproducible.
+///
+/// The child with index i is seeded with the (i + 1)th output of SplitMix64 started from the SplitMix64 hash of
+/// the parent seed. The scheme uses only integer arithmetic so the seeds are the same on every platform and
+/// will stay the same between versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SeedTree {
+    seed: u64,
+}
+
+impl SeedTree {
+    /// Creates tree with given seed at its root.
+    pub fn new(seed: u64) -> Self {
+        SeedTree { seed }
+    }
+
+    /// Creates tree with root seed drawn from the random number generator.
+    pub fn from_rng<R>(rng: &mut R) -> Self
+    where
+        R: Rng,
+    {
+        SeedTree::new(rng.random())
+    }
+
+    /// Returns the seed of the node.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the child with given index.
+    pub fn child(&self, i: u64) -> Self {
+        let state = mix(self.seed).wrapping_add(i.wrapping_add(1).wrapping_mul(GOLDEN_GAMMA));
+        SeedTree::new(mix(state))
+    }
+
+    /// Creates random number generator seeded with the seed of the node.
+    pub fn rng<R>(&self) -> R
+    where
+        R: SeedableRng,
+    {
+        R::seed_from_u64(self.seed)
+    }
+}
+
+/// Master seed of a world from which the seeds of its distributions are derived by labels.
+///
+/// Trees, rocks and towns can all stem from one seed with `seed.derive("trees")`, `seed.derive("rocks")` and
+/// so on. The label is hashed with 64 bit FNV-1a, added to the SplitMix64 hash of the parent seed and hashed again,
+/// so different labels give seeds as unrelated as independent random numbers and adding a new label doesn't change
+/// the seeds of the others. The scheme uses only integer arithmetic on the bytes of the label so the seeds are the
+/// same on every platform and will stay the same between versions.
+///
+/// ````rust
+/// # use poisson::Seed;
+/// # use rand::rngs::SmallRng;
+/// let world = Seed::new(2024);
+/// let trees = world.derive("trees");
+/// assert_eq!(trees, Seed::new(2024).derive("trees"));
+/// assert_ne!(trees, world.derive("rocks"));
+/// let rng = trees.rng::<SmallRng>();
+/// ````
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Seed(u64);
+
+impl Seed {
+    /// Creates seed with given value.
+    pub fn new(seed: u64) -> Self {
+        Seed(seed)
+    }
+
+    /// Returns the value of the seed.
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Returns the seed derived from this one with the label.
+    /// Labels can be chained, such as `seed.derive("forest").derive("oaks")`.
+    pub fn derive(&self, label: &str) -> Self {
+        let hash = label.bytes().fold(FNV_OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+        Seed(mix(mix(self.0).wrapping_add(hash)))
+    }
+
+    /// Returns tree of seeds rooted at this seed for splitting it by index instead of by label.
+    pub fn tree(&self) -> SeedTree {
+        SeedTree::new(self.0)
+    }
+
+    /// Creates random number generator seeded with the seed.
+    pub fn rng<R>(&self) -> R
+    where
+        R: SeedableRng,
+    {
+        R::seed_from_u64(self.0)
+    }
+}
+
+impl From<u64> for Seed {
+    fn from(seed: u64) -> Self {
+        Seed(seed)
+    }
+}
+
+/// SplitMix64 finalizer.
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
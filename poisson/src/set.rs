@@ -0,0 +1,1156 @@
+//! Module that contains generated poisson-disk distributions and operations on them.
+
+use crate::seed::mix;
+use crate::shape::Shape;
+use crate::utils::math::max_radius;
+use crate::utils::*;
+use crate::verify::{verify_separation, Violation};
+use crate::{Builder, Domain, Float, Precision, Sample, Type, Vector};
+
+use num_traits::{Float as NumFloat, NumCast};
+
+use rand::distr::StandardUniform;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_distr::Distribution;
+
+use sphere::sphere_volume;
+
+use std::error::Error;
+use std::fmt;
+
+/// Amount of fractional bits kept of the coordinates when hashing the content of point sets.
+const QUANTUM_BITS: usize = 20;
+
+/// Difference between two point sets.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SetDiff<V> {
+    /// Samples that are only in the other set.
+    pub added: Vec<V>,
+    /// Samples that are only in the original set.
+    pub removed: Vec<V>,
+    /// Samples that moved within the tolerance as pairs of the original and the new position.
+    pub moved: Vec<(V, V)>,
+}
+
+impl<V> SetDiff<V> {
+    /// Checks if the sets had exactly the same samples.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+/// How conflicts between the samples of merged sets are resolved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Keeps every sample of the first set and the samples of the other set that don't conflict with them.
+    KeepFirst,
+    /// Keeps the conflicting samples that are farthest from the samples without conflicts, one at a time,
+    /// as long as they don't conflict with the ones kept before them.
+    KeepFarthest,
+    /// Drops every sample that conflicts with a sample of the other set.
+    DropBoth,
+}
+
+/// Reason a position can't be inserted into a point set.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Conflict<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// The position is outside the domain.
+    OutsideDomain,
+    /// The position is closer than the diameter to a sample of the set.
+    /// When there are several such samples this is the closest one.
+    Sample {
+        /// The existing sample.
+        sample: V,
+        /// Distance between the position and the sample, wrapping around the perioditic axes of the domain.
+        distance: F,
+    },
+}
+
+impl<F, V> fmt::Display for Conflict<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Conflict::OutsideDomain => write!(f, "Position is outside the domain"),
+            Conflict::Sample { distance, .. } => {
+                write!(f, "Position is {} from a sample, closer than the diameter", distance)
+            }
+        }
+    }
+}
+
+impl<F, V> Error for Conflict<F, V>
+where
+    F: Float,
+    V: Vector<F> + fmt::Debug,
+{
+}
+
+/// Generated poisson-disk distribution together with the parameters it was generated with.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    points: Vec<V>,
+    radius: F,
+    domain: Domain,
+    algorithm: Option<&'static str>,
+    index: Cache<F, V>,
+}
+
+impl<F, V> PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Creates point set from samples and the parameters they were generated with.
+    pub fn new<D>(points: Vec<V>, radius: F, domain: D) -> Self
+    where
+        D: Into<Domain>,
+    {
+        PointSet {
+            points,
+            radius,
+            domain: domain.into(),
+            algorithm: None,
+            index: Cache::default(),
+        }
+    }
+
+    /// Records the name of the algorithm that generated the samples.
+    pub fn with_algorithm(mut self, algorithm: &'static str) -> Self {
+        self.algorithm = Some(algorithm);
+        self
+    }
+
+    /// Returns the name of the algorithm that generated the samples if it's known.
+    pub fn algorithm(&self) -> Option<&'static str> {
+        self.algorithm
+    }
+
+    /// Returns the samples of the distribution.
+    pub fn points(&self) -> &[V] {
+        &self.points
+    }
+
+    /// Consumes the point set returning the samples of the distribution.
+    pub fn into_points(self) -> Vec<V> {
+        self.points
+    }
+
+    /// Returns the samples of the distribution each carrying the radius of the distribution.
+    pub fn samples(&self) -> Vec<Sample<F, V>> {
+        self.points
+            .iter()
+            .map(|p| Sample::new(p.clone(), self.radius))
+            .collect()
+    }
+
+    /// Returns iterator over the samples of the distribution.
+    pub fn iter(&self) -> std::slice::Iter<'_, V> {
+        self.points.iter()
+    }
+
+    /// Returns iterator over the samples of the distribution in Z-order, where samples close to each other
+    /// in the domain are mostly close to each other in the iteration too.
+    /// This keeps consecutive samples in the same region when instantiating or batching things at their positions.
+    pub fn iter_morton(&self) -> std::vec::IntoIter<&V> {
+        let mut keyed = self
+            .points
+            .iter()
+            .map(|p| (morton(p, self.domain), p))
+            .collect::<Vec<_>>();
+        keyed.sort_by_key(|&(key, _)| key);
+        keyed
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns iterator over the samples of the distribution in increasing order of the coordinate along the axis,
+    /// as needed for scanline processing. Samples with the same coordinate keep their order.
+    pub fn sorted_by_axis(&self, axis: usize) -> std::vec::IntoIter<&V> {
+        assert!(axis < V::dimension());
+        let mut sorted = self.points.iter().collect::<Vec<_>>();
+        sorted.sort_by(|a, b| a[axis].partial_cmp(&b[axis]).expect("Coordinates should be comparable."));
+        sorted.into_iter()
+    }
+
+    /// Returns iterator over the samples of the distribution from the nearest to the origin to the farthest,
+    /// measuring the distance across the edges of perioditic domains. Samples as far away keep their order.
+    pub fn sorted_by_distance(&self, origin: &V) -> std::vec::IntoIter<&V> {
+        let mut keyed = self
+            .points
+            .iter()
+            .map(|p| (sqdist(origin.clone(), p.clone(), self.domain), p))
+            .collect::<Vec<_>>();
+        keyed.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Distances should be comparable."));
+        keyed
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Returns the amount of samples in the distribution.
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Checks if there are no samples in the distribution.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Returns the radius the distribution was generated with normalized to [0, 1)<sup>d</sup>.
+    pub fn radius(&self) -> F {
+        self.radius
+    }
+
+    /// Returns the radius relative to the largest allowed radius √2 / 2.
+    pub fn relative_radius(&self) -> F {
+        self.radius / max_radius()
+    }
+
+    /// Returns the radius scaled to a domain whose side is `extent` long.
+    pub fn absolute_radius(&self, extent: F) -> F {
+        self.radius * extent
+    }
+
+    /// Returns the minimum distance between samples, which is twice the radius.
+    pub fn diameter(&self) -> F {
+        F::cast(2) * self.radius
+    }
+
+    /// Returns the type of the distribution.
+    pub fn poisson_type(&self) -> Type {
+        self.domain.poisson_type(V::dimension())
+    }
+
+    /// Returns the domain of the distribution.
+    pub fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    /// Returns the fraction of the domain covered by the disks of the samples.
+    /// Samples are at least the diameter apart so the disks don't overlap and the fraction is at most
+    /// the densest packing possible, about 0.91 in two and 0.74 in three dimensions.
+    /// Parts of the disks past the boundary of a non-perioditic axis don't cover the domain and are integrated
+    /// out numerically, while perioditic axes wrap the disks around so they are counted whole.
+    pub fn packing_fraction(&self) -> F {
+        let dim = V::dimension();
+        let mut covered = F::cast(0);
+        for p in &self.points {
+            let (mut lows, mut highs) = (vec![], vec![]);
+            for n in 0..dim {
+                if self.domain.is_perioditic(n) {
+                    continue;
+                }
+                let (low, high) = (-p[n], self.domain.extent_of::<F>(n) - p[n]);
+                if low > -self.radius || high < self.radius {
+                    lows.push(low);
+                    highs.push(high);
+                }
+            }
+            covered += clipped_volume(self.radius, &lows, &highs, dim - lows.len());
+        }
+        let volume: F =
+            NumCast::from(self.domain.volume(dim)).expect("f64 should be castable to float.");
+        covered / volume
+    }
+
+    /// Returns the distance between the positions, measured across the edges of perioditic axes
+    /// to the closest image of the other position.
+    pub fn distance(&self, a: &V, b: &V) -> F {
+        NumFloat::sqrt(sqdist(a.clone(), b.clone(), self.domain))
+    }
+
+    /// Returns the index of the sample closest to the position and the distance to it, measured across the edges
+    /// of perioditic axes, or `None` if there are no samples.
+    pub fn nearest(&self, position: &V) -> Option<(usize, F)> {
+        self.points
+            .iter()
+            .map(|p| sqdist(p.clone(), position.clone(), self.domain))
+            .enumerate()
+            .fold(None, |closest: Option<(usize, F)>, (i, d)| match closest {
+                Some((_, c)) if c <= d => closest,
+                _ => Some((i, d)),
+            })
+            .map(|(i, d)| (i, NumFloat::sqrt(d)))
+    }
+
+    /// Returns the distance from each sample to the closest other sample, measured across the edges of
+    /// perioditic axes, or infinity for a lone sample.
+    pub fn nearest_distances(&self) -> Vec<F> {
+        let index = Index::new(&self.points, self.radius, self.domain);
+        // Samples closer than the reach of the grid are always in the surrounding cells.
+        let sqreach = NumFloat::powi(index.grid.reached(), 2);
+        let min = |a: F, b: F| if b < a { b } else { a };
+        self.points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let mut sqdistance = index
+                    .near(p)
+                    .into_iter()
+                    .filter(|q| q != p)
+                    .map(|q| sqdist(q, p.clone(), self.domain))
+                    .fold(NumFloat::infinity(), min);
+                if sqdistance > sqreach {
+                    sqdistance = self
+                        .points
+                        .iter()
+                        .enumerate()
+                        .filter(|&(j, _)| j != i)
+                        .map(|(_, q)| sqdist(q.clone(), p.clone(), self.domain))
+                        .fold(sqdistance, min);
+                }
+                NumFloat::sqrt(sqdistance)
+            })
+            .collect()
+    }
+
+    /// Returns the radial distribution function of the samples in bins of equal width up to the distance,
+    /// which is the density of samples at each distance from a sample relative to that of uniformly random samples.
+    /// Distances are measured across the edges of perioditic axes, so the function is exact for perioditic
+    /// distributions up to half the extent of the domain, while it falls off near the maximum distance for others
+    /// as the shells of samples near the boundaries are partly outside the domain.
+    pub fn radial_distribution(&self, max_distance: F, bins: usize) -> Vec<F> {
+        assert!(bins > 0);
+        let dim = V::dimension();
+        let mut counts = vec![0usize; bins];
+        let width = max_distance / F::cast(bins);
+        for (i, p) in self.points.iter().enumerate() {
+            for q in &self.points[i + 1..] {
+                let bin = NumFloat::floor(self.distance(p, q) / width)
+                    .to_usize()
+                    .expect("Distance should be castable to usize.");
+                if bin < bins {
+                    counts[bin] += 2;
+                }
+            }
+        }
+        let volume: F =
+            NumCast::from(self.domain.volume(dim)).expect("f64 should be castable to float.");
+        let n = F::cast(self.points.len());
+        let density = n / volume;
+        let ball = |r: F| sphere_volume(r, dim as u64);
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(k, count)| {
+                let shell = ball(width * F::cast(k + 1)) - ball(width * F::cast(k));
+                F::cast(count) / (n * density * shell)
+            })
+            .collect()
+    }
+
+    /// Returns the radius of the largest empty ball centered inside the domain, which is the farthest any position
+    /// of the domain is from its closest sample, or infinity if there are no samples.
+    /// Maximal distributions leave no room for another sample so the coverage radius is at most the diameter.
+    /// The domain is subdivided around the farthest positions until the radius is known to within a millionth of the radius
+    /// of the samples.
+    pub fn coverage_radius(&self) -> F {
+        if self.points.is_empty() {
+            return NumFloat::infinity();
+        }
+        let dim = V::dimension();
+        let index = Index::new(&self.points, self.radius, self.domain);
+        let side = index.grid.side();
+        // Samples closer than the reach of the grid are always in the surrounding cells.
+        let sqreach = NumFloat::powi(index.grid.reached(), 2);
+        let distance = |position: &V| {
+            let mut sqdistance = index.nearest(position);
+            if sqdistance > sqreach {
+                sqdistance = self
+                    .points
+                    .iter()
+                    .map(|q| sqdist(q.clone(), position.clone(), self.domain))
+                    .fold(sqdistance, |a, b| if b < a { b } else { a });
+            }
+            NumFloat::sqrt(sqdistance)
+        };
+        let mut size = V::zero();
+        let mut counts = vec![];
+        for n in 0..dim {
+            let extent = self.domain.extent_of::<F>(n);
+            let count = NumFloat::ceil(extent * F::cast(side))
+                .to_usize()
+                .expect("Extent multiplied by the side should be always castable to usize.")
+                .max(1);
+            size[n] = extent / F::cast(count);
+            counts.push(count);
+        }
+        let choices = (0..counts.iter().cloned().max().unwrap_or(1)).collect::<Vec<_>>();
+        let mut cells = each_combination(&choices)
+            .filter(|i: &V| (0..dim).all(|n| i[n] < F::cast(counts[n])))
+            .map(|mut i: V| {
+                for n in 0..dim {
+                    i[n] *= size[n];
+                }
+                i
+            })
+            .collect::<Vec<_>>();
+        let tolerance = self.radius / F::cast(1_000_000);
+        let mut farthest = F::cast(0);
+        // Cells are subdivided a level at a time so that the farthest distance is known from the whole level
+        // before deciding which cells can't contain anything farther.
+        while !cells.is_empty() {
+            let half = size.clone() * (F::cast(1) / F::cast(2));
+            let distances = cells
+                .iter()
+                .map(|c| distance(&(c.clone() + half.clone())))
+                .collect::<Vec<_>>();
+            for &d in &distances {
+                if d > farthest {
+                    farthest = d;
+                }
+            }
+            // Distance to the closest sample changes at most as much as the position does,
+            // so positions of the cell are at most half of its diagonal farther than its center.
+            let reach = half.norm();
+            cells = cells
+                .into_iter()
+                .zip(distances)
+                .filter(|&(_, d)| d + reach > farthest + tolerance)
+                .flat_map(|(corner, _)| {
+                    let half = half.clone();
+                    each_combination(&[0, 1]).map(move |t: V| {
+                        let mut child = corner.clone();
+                        for n in 0..dim {
+                            child[n] += t[n] * half[n];
+                        }
+                        child
+                    })
+                })
+                .collect();
+            size = half;
+        }
+        farthest
+    }
+
+    /// Checks which of the positions could be added to the distribution, which are the ones inside the domain
+    /// at least the diameter away from every sample.
+    /// Positions are grouped by the cell of the grid they are in and the samples around each cell are gathered once,
+    /// so checking thousands of positions at a time, like the spawn positions proposed during a frame, stays cheap.
+    pub fn filter_legal(&self, positions: &[V]) -> Vec<bool> {
+        let index = Index::new(&self.points, self.radius, self.domain);
+        let side = index.grid.side();
+        let mut cells = positions
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| self.domain.contains(*p))
+            .map(|(i, p)| {
+                let cell = encode(&sample_to_index(p, side), side, self.domain)
+                    .expect("Because the position is inside the domain encoding its index should work.");
+                (cell, i)
+            })
+            .collect::<Vec<_>>();
+        cells.sort_unstable();
+        let mut legal = vec![false; positions.len()];
+        for group in cells.chunk_by(|a, b| a.0 == b.0) {
+            let near = index.near(&positions[group[0].1]);
+            for &(_, i) in group {
+                legal[i] = near
+                    .iter()
+                    .all(|q| sqdist(q.clone(), positions[i].clone(), self.domain) >= index.sqdiameter);
+            }
+        }
+        legal
+    }
+
+    /// Adds the position as a sample if it's inside the domain at least the diameter away from every sample,
+    /// such as an exclusion point added at runtime when a player builds a structure.
+    /// The grid of the samples is built on the first insertion and kept up to date after that,
+    /// so each insertion only checks the samples around the position.
+    pub fn try_insert(&mut self, position: V) -> Result<(), Conflict<F, V>> {
+        if !self.domain.contains(&position) {
+            return Err(Conflict::OutsideDomain);
+        }
+        let (points, radius, domain) = (&self.points, self.radius, self.domain);
+        let index = self
+            .index
+            .0
+            .get_or_insert_with(|| Index::new(points, radius, domain));
+        match index.closest(&position) {
+            Some((sample, sqdist)) if sqdist < index.sqdiameter => Err(Conflict::Sample {
+                sample,
+                distance: NumFloat::sqrt(sqdist),
+            }),
+            _ => {
+                index.insert(position.clone());
+                self.points.push(position);
+                Ok(())
+            }
+        }
+    }
+
+    /// Compares the samples to the samples of the other set.
+    /// Each sample is paired with the closest unpaired sample of the other set that is at most `tolerance` away from it.
+    /// Paired samples that are not at the exact same position are reported as moved and unpaired ones as removed or added.
+    pub fn diff(&self, other: &PointSet<F, V>, tolerance: F) -> SetDiff<V> {
+        let sqtolerance = tolerance * tolerance;
+        let mut paired = vec![false; other.points.len()];
+        let mut diff = SetDiff {
+            added: vec![],
+            removed: vec![],
+            moved: vec![],
+        };
+        for p in &self.points {
+            let closest = other
+                .points
+                .iter()
+                .enumerate()
+                .filter(|&(i, _)| !paired[i])
+                .map(|(i, q)| (i, sqdist(p.clone(), q.clone(), self.domain)))
+                .filter(|&(_, d)| d <= sqtolerance)
+                .fold(None, |closest: Option<(usize, F)>, (i, d)| match closest {
+                    Some((_, c)) if c <= d => closest,
+                    _ => Some((i, d)),
+                });
+            match closest {
+                Some((i, _)) => {
+                    paired[i] = true;
+                    if *p != other.points[i] {
+                        diff.moved.push((p.clone(), other.points[i].clone()));
+                    }
+                }
+                None => diff.removed.push(p.clone()),
+            }
+        }
+        diff.added = other
+            .points
+            .iter()
+            .zip(paired)
+            .filter(|&(_, paired)| !paired)
+            .map(|(q, _)| q.clone())
+            .collect();
+        diff
+    }
+
+    /// Checks if the sets have the same samples and radius within the tolerance, in any order.
+    /// Each sample has to pair with a sample of the other set at most `tolerance` away as `diff` pairs them,
+    /// so floating point noise doesn't make otherwise equal sets differ like comparing the points does.
+    pub fn approx_eq(&self, other: &PointSet<F, V>, tolerance: F) -> bool {
+        if self.points.len() != other.points.len()
+            || NumFloat::abs(self.radius - other.radius) > tolerance
+            || self.domain != other.domain
+        {
+            return false;
+        }
+        let diff = self.diff(other, tolerance);
+        diff.added.is_empty() && diff.removed.is_empty()
+    }
+
+    /// Returns hash of the samples and the radius that doesn't depend on the order of the samples,
+    /// for keying caches on the generated content.
+    /// Coordinates are quantized to multiples of 2<sup>-20</sup> first so that noise much smaller than that
+    /// rarely changes the hash, though coordinates near the middle of two multiples still round apart.
+    /// The hash is computed from integers only so it's the same on every platform.
+    pub fn content_hash(&self) -> u64 {
+        let quantize = |c: F| {
+            NumFloat::round(c * F::cast(1 << QUANTUM_BITS))
+                .to_i64()
+                .unwrap_or(0) as u64
+        };
+        // Hashes of the samples are summed so their order doesn't matter.
+        let samples = self.points.iter().fold(0u64, |sum, p| {
+            let hash = (0..V::dimension()).fold(0u64, |hash, n| mix(hash ^ quantize(p[n])));
+            sum.wrapping_add(mix(hash))
+        });
+        let size = mix(self.points.len() as u64 ^ mix(quantize(self.radius)));
+        mix(samples ^ size)
+    }
+
+    /// Combines the samples of both sets resolving the conflicts between them with the policy.
+    /// Samples of the same set are assumed not to conflict with each other, so this is useful for
+    /// stitching chunks or layering independently generated distributions with the same radius.
+    pub fn merge(self, other: PointSet<F, V>, policy: MergePolicy) -> Self {
+        assert!(
+            self.radius == other.radius && self.domain == other.domain,
+            "Only sets with the same radius and domain can be merged."
+        );
+        let algorithm = if self.algorithm == other.algorithm {
+            self.algorithm
+        } else {
+            None
+        };
+        let (radius, domain) = (self.radius, self.domain);
+        let first = Index::new(&self.points, radius, domain);
+        let second = Index::new(&other.points, radius, domain);
+        let points = match policy {
+            MergePolicy::KeepFirst => {
+                let mut index = first;
+                let mut points = self.points;
+                for p in other.points {
+                    if !index.conflicts(&p) {
+                        index.insert(p.clone());
+                        points.push(p);
+                    }
+                }
+                points
+            }
+            MergePolicy::DropBoth => {
+                let mut points = self.points;
+                points.retain(|p| !second.conflicts(p));
+                points.extend(other.points.into_iter().filter(|p| !first.conflicts(p)));
+                points
+            }
+            MergePolicy::KeepFarthest => {
+                let (mut points, mut conflicting) = (vec![], vec![]);
+                for (p, against) in self
+                    .points
+                    .into_iter()
+                    .map(|p| (p, &second))
+                    .chain(other.points.into_iter().map(|p| (p, &first)))
+                {
+                    if against.conflicts(&p) {
+                        conflicting.push(p);
+                    } else {
+                        points.push(p);
+                    }
+                }
+                let mut index = Index::new(&points, radius, domain);
+                let mut scored = conflicting
+                    .into_iter()
+                    .map(|p| (index.nearest(&p), p))
+                    .collect::<Vec<_>>();
+                scored.sort_by(|a, b| b.0.partial_cmp(&a.0).expect("Distances should not be NaN."));
+                for (_, p) in scored {
+                    if !index.conflicts(&p) {
+                        index.insert(p.clone());
+                        points.push(p);
+                    }
+                }
+                points
+            }
+        };
+        PointSet {
+            points,
+            radius,
+            domain,
+            algorithm,
+            index: Cache::default(),
+        }
+    }
+
+    /// Keeps only the samples that are inside the shape.
+    pub fn retain_within<S>(mut self, shape: &S) -> Self
+    where
+        S: Shape<F, V>,
+    {
+        self.points.retain(|p| shape.contains(p));
+        self.index = Cache::default();
+        self
+    }
+
+    /// Keeps only the samples whose distance to the boundary of the domain is at least the distance given.
+    /// Perioditic axes have no boundary so they don't restrict the samples.
+    pub fn retain_far_from_boundary(mut self, distance: F) -> Self {
+        let domain = self.domain;
+        self.points.retain(|p| {
+            (0..V::dimension()).all(|n| {
+                domain.is_perioditic(n)
+                    || (distance <= p[n] && distance <= domain.extent_of::<F>(n) - p[n])
+            })
+        });
+        self.index = Cache::default();
+        self
+    }
+
+    /// Removes samples at random so that each is kept with probability given by its importance clamped to [0, 1],
+    /// shaping the density afterwards without regenerating the distribution with a varying radius.
+    /// Samples are decided in random order and the probability is nudged by how many of the nearby samples decided
+    /// before were kept compared to their importances, like error diffusion in dithering, so that removals don't clump
+    /// into holes and the remaining samples stay spread out.
+    pub fn thin_by_importance<I, R>(mut self, importance: I, rng: &mut R) -> Self
+    where
+        I: Fn(&V) -> F,
+        R: Rng,
+    {
+        let clamped = |p: &V| NumFloat::max(F::cast(0), NumFloat::min(F::cast(1), importance(p)));
+        let reach = NumFloat::powi(F::cast(2) * self.diameter(), 2);
+        // Cells of twice the size reach the samples within twice the diameter.
+        let mut kept = Index::new(&[], F::cast(2) * self.radius, self.domain);
+        let mut removed = Index::new(&[], F::cast(2) * self.radius, self.domain);
+        let mut order = (0..self.points.len()).collect::<Vec<_>>();
+        order.shuffle(rng);
+        let mut keep = vec![false; self.points.len()];
+        for i in order {
+            let p = &self.points[i];
+            let near = |index: &Index<F, V>| {
+                index
+                    .near(p)
+                    .into_iter()
+                    .filter(|q| sqdist(q.clone(), p.clone(), self.domain) <= reach)
+                    .map(|q| clamped(&q))
+                    .collect::<Vec<_>>()
+            };
+            let (near_kept, near_removed) = (near(&kept), near(&removed));
+            let expected = near_kept.iter().chain(&near_removed).fold(F::cast(0), |a, &b| a + b);
+            let decided = F::cast(near_kept.len() + near_removed.len());
+            let probability = clamped(p) + (expected - F::cast(near_kept.len())) / (decided + F::cast(1));
+            if unit_from_bits::<F>(rng.random()) < probability {
+                keep[i] = true;
+                kept.insert(p.clone());
+            } else {
+                removed.insert(p.clone());
+            }
+        }
+        let mut keep = keep.into_iter();
+        self.points.retain(|_| keep.next().unwrap_or(false));
+        self.index = Cache::default();
+        self
+    }
+
+    /// Snaps the samples to the lattice whose points are `grid_step` apart along each axis, like the tiles of a map.
+    /// Samples that would snap too close to the samples snapped before them are nudged to the closest lattice point
+    /// around the snapped one that keeps them legal and dropped if there is none, so the samples are still more than
+    /// the diameter apart. Samples that would snap past the end of an axis snap to the last lattice point inside the domain.
+    pub fn quantize(self, grid_step: F) -> Self {
+        assert!(grid_step > F::cast(0));
+        let (radius, domain) = (self.radius, self.domain);
+        let mut index = Index::new(&[], radius, domain);
+        // Lattice points are often exactly the diameter apart and rounding makes such distances ambiguous,
+        // so they are treated as conflicting.
+        let limit = index.sqdiameter * (F::cast(1) + F::cast(64) * NumFloat::epsilon());
+        let mut points = Vec::with_capacity(self.points.len());
+        for p in self.points {
+            let mut snapped = V::zero();
+            for n in 0..V::dimension() {
+                snapped[n] = NumFloat::round(p[n] / grid_step);
+            }
+            // Lattice points are computed from whole steps so that they stay exactly on the lattice.
+            let mut candidates = each_combination(&[-1, 0, 1])
+                .map(|offset: V| {
+                    let mut candidate = snapped.clone() + offset;
+                    for n in 0..V::dimension() {
+                        candidate[n] *= grid_step;
+                    }
+                    candidate
+                })
+                .filter(|c| domain.contains(c))
+                .map(|c| (sqdist(c.clone(), p.clone(), domain), c))
+                .collect::<Vec<_>>();
+            candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("Distances should not be NaN."));
+            if let Some((_, c)) = candidates
+                .into_iter()
+                .find(|(_, c)| index.nearest(c) > limit)
+            {
+                index.insert(c.clone());
+                points.push(c);
+            }
+        }
+        PointSet {
+            points,
+            radius,
+            domain,
+            algorithm: self.algorithm,
+            index: Cache::default(),
+        }
+    }
+
+    /// Tiles perioditic distribution given amount of times along each axis.
+    /// The tile with origin at zero is the original distribution and other tiles are offset by whole extents of the domain.
+    /// Returns the points with the extent of the tiled area along each axis, which can be larger than the unit
+    /// so it doesn't fit into a domain.
+    pub fn tile(&self, repeats: &[usize]) -> (Vec<V>, V) {
+        assert!(
+            Type::Perioditic == self.poisson_type(),
+            "Only perioditic distributions can be tiled seamlessly."
+        );
+        assert_eq!(V::dimension(), repeats.len());
+        let tiles = repeats.iter().product::<usize>();
+        let mut points = Vec::with_capacity(self.points.len() * tiles);
+        for t in 0..tiles {
+            let mut offset = V::zero();
+            let mut div = t;
+            for (n, &r) in repeats.iter().enumerate() {
+                offset[n] = F::cast(div % r) * self.domain.extent_of::<F>(n);
+                div /= r;
+            }
+            points.extend(self.points.iter().map(|p| p.clone() + offset.clone()));
+        }
+        let mut extent = V::zero();
+        for (n, &r) in repeats.iter().enumerate() {
+            extent[n] = F::cast(r) * self.domain.extent_of::<F>(n);
+        }
+        (points, extent)
+    }
+
+    /// Returns the 8 symmetries of the square applied to perioditic distribution on the plane of the first two axes,
+    /// which are the rotations by quarter turns followed by the same rotations of the distribution mirrored
+    /// across the first axis, starting with the distribution itself.
+    /// Texture bombing with a random variant for each tile gives more variety than repeating the same tile.
+    /// The first two axes have to wrap around and be equally long so that the symmetries map the domain onto itself,
+    /// which keeps the variants as legal as the distribution is. Each variant is checked under wrapping,
+    /// returning the first violation if rounding or an illegal distribution brings samples too close.
+    pub fn symmetry_variants(&self) -> Result<Vec<PointSet<F, V>>, Violation<F>> {
+        assert!(V::dimension() >= 2);
+        assert!(
+            self.domain.is_perioditic(0) && self.domain.is_perioditic(1),
+            "Only the symmetries of perioditic distributions are seamless."
+        );
+        let extent = self.domain.extent_of::<F>(0);
+        assert!(
+            extent == self.domain.extent_of::<F>(1),
+            "First two axes should be equally long to be rotated."
+        );
+        // Negated coordinates are wrapped back into the domain, where zero stays zero.
+        let wrap = |c: F| {
+            let c = if c < F::cast(0) { c + extent } else { c };
+            if c < extent { c } else { F::cast(0) }
+        };
+        let mut variants = Vec::with_capacity(8);
+        for variant in 0..8 {
+            let points = self
+                .points
+                .iter()
+                .map(|p| {
+                    let (mut x, mut y) = (p[0], p[1]);
+                    if variant >= 4 {
+                        y = -y;
+                    }
+                    for _ in 0..variant % 4 {
+                        (x, y) = (-y, x);
+                    }
+                    let mut p = p.clone();
+                    p[0] = wrap(x);
+                    p[1] = wrap(y);
+                    p
+                })
+                .collect::<Vec<_>>();
+            verify_separation(&points, self.radius, self.domain)?;
+            variants.push(PointSet {
+                points,
+                radius: self.radius,
+                domain: self.domain,
+                algorithm: self.algorithm,
+                index: Cache::default(),
+            });
+        }
+        Ok(variants)
+    }
+
+    /// Generates a finer distribution with the smaller radius into the gaps between the samples,
+    /// like rocks between trees, returning only the new samples.
+    /// The disks of the new samples don't overlap the disks of the samples of this set or each other,
+    /// so they are at least the sum of the radii away from these samples and twice the smaller radius from each other.
+    /// New samples are thrown until the amount of consecutive rejected samples reaches the limit.
+    pub fn subsample_gaps<R>(
+        &self,
+        smaller_radius: F,
+        rng: &mut R,
+        rejection_limit: usize,
+    ) -> PointSet<F, V>
+    where
+        R: Rng,
+        StandardUniform: Distribution<V>,
+    {
+        assert!(F::cast(0) < smaller_radius && smaller_radius <= self.radius);
+        let coarse = Index::new(&self.points, self.radius, self.domain);
+        let reach = NumFloat::powi(self.radius + smaller_radius, 2);
+        let poisson = Builder::with_disk_radius(smaller_radius, self.domain);
+        let mut grid = Grid::new(smaller_radius, self.domain, Precision::Full);
+        let mut points = vec![];
+        let mut rejections = 0;
+        while rejections < rejection_limit {
+            let mut sample: V = rng.sample(StandardUniform);
+            for n in 0..V::dimension() {
+                sample[n] *= self.domain.extent_of::<F>(n);
+            }
+            let index = sample_to_index(&sample, grid.side());
+            if reach <= coarse.nearest(&sample)
+                && is_disk_free(&grid, &poisson, index.clone(), 0, sample.clone(), &[])
+            {
+                grid.insert(index, sample.clone()).unwrap_or_else(|_| {
+                    panic!("Because the sample is [0, 1) indexing it should work.")
+                });
+                points.push(sample);
+                rejections = 0;
+            } else {
+                rejections += 1;
+            }
+        }
+        PointSet::new(points, smaller_radius, self.domain)
+    }
+
+    /// Fills the gaps inside the shape with new samples so that the filtered distribution is again close to maximal inside it.
+    /// New samples are thrown until the amount of consecutive rejected samples reaches the limit.
+    pub fn refill_within<S, R>(mut self, shape: &S, rng: &mut R, rejection_limit: usize) -> Self
+    where
+        S: Shape<F, V>,
+        R: Rng,
+        StandardUniform: Distribution<V>,
+    {
+        let poisson = Builder::with_disk_radius(self.radius, self.domain);
+        let mut grid = Grid::new(self.radius, self.domain, Precision::Full);
+        let mut outside = vec![];
+        for p in &self.points {
+            let index = sample_to_index(p, grid.side());
+            if let Err(p) = grid.insert(index, p.clone()) {
+                outside.push(p);
+            }
+        }
+        let mut rejections = 0;
+        while rejections < rejection_limit {
+            let mut sample: V = rng.sample(StandardUniform);
+            for n in 0..V::dimension() {
+                sample[n] *= self.domain.extent_of::<F>(n);
+            }
+            let index = sample_to_index(&sample, grid.side());
+            if shape.contains(&sample)
+                && is_disk_free(&grid, &poisson, index.clone(), 0, sample.clone(), &outside)
+            {
+                grid.insert(index, sample.clone()).unwrap_or_else(|_| {
+                    panic!("Because the sample is [0, 1) indexing it should work.")
+                });
+                self.points.push(sample);
+                rejections = 0;
+            } else {
+                rejections += 1;
+            }
+        }
+        self.index = Cache::default();
+        self
+    }
+}
+
+impl<F, V> IntoIterator for PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    type IntoIter = std::vec::IntoIter<V>;
+    type Item = V;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.into_iter()
+    }
+}
+
+impl<'a, F, V> IntoIterator for &'a PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    type IntoIter = std::slice::Iter<'a, V>;
+    type Item = &'a V;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.iter()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<F, V> rayon::iter::IntoParallelIterator for PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F> + Send,
+{
+    type Iter = rayon::vec::IntoIter<V>;
+    type Item = V;
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.points.into_par_iter()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, F, V> rayon::iter::IntoParallelIterator for &'a PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F> + Sync,
+{
+    type Iter = rayon::slice::Iter<'a, V>;
+    type Item = &'a V;
+
+    fn into_par_iter(self) -> Self::Iter {
+        use rayon::prelude::*;
+        self.points.par_iter()
+    }
+}
+
+impl<F, V> From<PointSet<F, V>> for Vec<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn from(set: PointSet<F, V>) -> Self {
+        set.points
+    }
+}
+
+/// Grid of samples for finding the ones near a position.
+#[derive(Clone)]
+struct Index<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    grid: Grid<F, V>,
+    outside: Vec<V>,
+    sqdiameter: F,
+    domain: Domain,
+}
+
+impl<F, V> Index<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn new(points: &[V], radius: F, domain: Domain) -> Self {
+        let mut index = Index {
+            grid: Grid::new(radius, domain, Precision::Full),
+            outside: vec![],
+            sqdiameter: NumFloat::powi(F::cast(2) * radius, 2),
+            domain,
+        };
+        for p in points {
+            index.insert(p.clone());
+        }
+        index
+    }
+
+    fn insert(&mut self, sample: V) {
+        let index = sample_to_index(&sample, self.grid.side());
+        if let Err(sample) = self.grid.insert(index, sample) {
+            self.outside.push(sample);
+        }
+    }
+
+    /// Returns the samples in the cells around the position and the ones outside the grid.
+    fn near(&self, position: &V) -> Neighbours<V> {
+        let index = sample_to_index(position, self.grid.side());
+        let mut near = self.grid.neighbours(index);
+        near.extend(self.outside.iter().cloned());
+        near
+    }
+
+    /// Returns the squared distance to the closest sample near the position,
+    /// or the largest float if there are none in the surrounding cells.
+    fn nearest(&self, position: &V) -> F {
+        self.closest(position)
+            .map_or(NumFloat::max_value(), |(_, sqdist)| sqdist)
+    }
+
+    /// Returns the closest sample near the position and the squared distance to it.
+    fn closest(&self, position: &V) -> Option<(V, F)> {
+        self.near(position)
+            .into_iter()
+            .map(|q| (q.clone(), sqdist(q, position.clone(), self.domain)))
+            .fold(None, |closest, (q, d)| match closest {
+                Some((_, c)) if c <= d => closest,
+                _ => Some((q, d)),
+            })
+    }
+
+    fn conflicts(&self, position: &V) -> bool {
+        self.nearest(position) < self.sqdiameter
+    }
+}
+
+/// Grid of the samples that is kept between insertions, built the first time it's needed.
+/// It's derived from the samples so it's left out when comparing and printing point sets.
+#[derive(Clone)]
+struct Cache<F, V>(Option<Index<F, V>>)
+where
+    F: Float,
+    V: Vector<F>;
+
+impl<F, V> Default for Cache<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn default() -> Self {
+        Cache(None)
+    }
+}
+
+impl<F, V> fmt::Debug for Cache<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Index")
+    }
+}
+
+impl<F, V> PartialEq for Cache<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+/// Returns the position along the Z-order curve by interleaving the bits of the coordinates,
+/// with the first axis being the least significant.
+fn morton<F, V>(position: &V, domain: Domain) -> u64
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let dim = V::dimension();
+    let bits = (64 / dim).min(32);
+    let cells = F::cast(1 << bits);
+    let coords = (0..dim)
+        .map(|n| {
+            let cell = NumFloat::floor(position[n] / domain.extent_of::<F>(n) * cells);
+            // Samples outside of the domain are ordered as if they were on its boundary.
+            NumFloat::max(F::cast(0), NumFloat::min(cell, cells - F::cast(1)))
+                .to_u64()
+                .expect("Cell of the sample should be always castable to u64.")
+        })
+        .collect::<Vec<_>>();
+    let mut key = 0;
+    for bit in (0..bits).rev() {
+        for coord in coords.iter().rev() {
+            key = (key << 1) | ((coord >> bit) & 1);
+        }
+    }
+    key
+}
+
+/// Returns the volume of the ball that is between the bounds relative to its center along the clipped axes,
+/// with `free` axes left unclipped.
+fn clipped_volume<F>(radius: F, lows: &[F], highs: &[F], free: usize) -> F
+where
+    F: Float,
+{
+    if lows.is_empty() {
+        return sphere_volume(radius, free as u64);
+    }
+    let low = NumFloat::max(lows[0], -radius);
+    let high = NumFloat::min(highs[0], radius);
+    if high <= low {
+        return F::cast(0);
+    }
+    // Slices along the axis are balls of one dimension less, and integrating over the angle
+    // instead of the position avoids the steep edges of the ball.
+    // Fewer slices are taken when many axes are clipped to keep the amount of slices bounded.
+    let slices = (4096f64.powf(1. / lows.len() as f64) as usize).clamp(4, 64);
+    let (start, end) = (NumFloat::asin(low / radius), NumFloat::asin(high / radius));
+    let step = (end - start) / F::cast(slices);
+    (0..slices)
+        .map(|i| {
+            let angle = start + (F::cast(i) + F::cast(1) / F::cast(2)) * step;
+            let slice = radius * NumFloat::cos(angle);
+            clipped_volume(slice, &lows[1..], &highs[1..], free) * slice * step
+        })
+        .fold(F::cast(0), |a, b| a + b)
+}
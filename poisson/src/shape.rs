@@ -0,0 +1,939 @@
+//! Module that contains shapes that describe regions of the sampling space.
+
+use crate::{Float, Vector};
+
+use num_traits::{Float as NumFloat, NumCast};
+
+use std::fmt;
+use std::sync::Arc;
+
+/// Describes region of the sampling space.
+pub trait Shape<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Checks if the sample is inside the region.
+    fn contains(&self, sample: &V) -> bool;
+}
+
+impl<F, V, T> Shape<F, V> for T
+where
+    F: Float,
+    V: Vector<F>,
+    T: Fn(&V) -> bool,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self(sample)
+    }
+}
+
+/// Ball with center and radius.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ball<F, V> {
+    /// Center of the ball.
+    pub center: V,
+    /// Radius of the ball.
+    pub radius: F,
+}
+
+impl<F, V> Shape<F, V> for Ball<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        (sample.clone() - self.center.clone()).norm_squared() <= self.radius * self.radius
+    }
+}
+
+impl<F, V> Parametric<F, V> for Ball<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn distance(&self, position: &V) -> F {
+        max((position.clone() - self.center.clone()).norm() - self.radius, F::cast(0))
+    }
+
+    fn perimeter(&self) -> F {
+        tau::<F>() * self.radius
+    }
+
+    fn outline(&self, t: F) -> V {
+        circle(&self.center, self.radius, self.radius, t)
+    }
+
+    fn depth(&self, position: &V) -> F {
+        max(self.radius - (position.clone() - self.center.clone()).norm(), F::cast(0))
+    }
+}
+
+/// Axis aligned box from minimum corner (inclusive) to maximum corner (exclusive).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cuboid<V> {
+    /// Minimum corner of the box.
+    pub min: V,
+    /// Maximum corner of the box.
+    pub max: V,
+}
+
+impl<F, V> Shape<F, V> for Cuboid<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        (0..V::dimension()).all(|n| self.min[n] <= sample[n] && sample[n] < self.max[n])
+    }
+}
+
+impl<F, V> Parametric<F, V> for Cuboid<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn distance(&self, position: &V) -> F {
+        let mut outside = V::zero();
+        for n in 0..V::dimension() {
+            outside[n] = max(max(self.min[n] - position[n], position[n] - self.max[n]), F::cast(0));
+        }
+        outside.norm()
+    }
+
+    fn perimeter(&self) -> F {
+        F::cast(2) * (self.max[0] - self.min[0] + self.max[1] - self.min[1])
+    }
+
+    /// The outline goes counterclockwise from the minimum corner on the plane through it.
+    fn outline(&self, t: F) -> V {
+        let corners = [
+            (self.min[0], self.min[1]),
+            (self.max[0], self.min[1]),
+            (self.max[0], self.max[1]),
+            (self.min[0], self.max[1]),
+        ];
+        walk(&corners, self.min.clone(), t)
+    }
+
+    fn depth(&self, position: &V) -> F {
+        let depth = (0..V::dimension())
+            .map(|n| min(position[n] - self.min[n], self.max[n] - position[n]))
+            .fold(NumFloat::max_value(), min);
+        max(depth, F::cast(0))
+    }
+}
+
+/// Polygon on the plane of the first two axes, extending along the rest of the axes.
+/// The edges go from each vertex to the next one and from the last vertex back to the first one.
+/// Positions inside of it are found with the even-odd rule, so the edges may cross each other.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Polygon<V> {
+    /// Vertices of the polygon, of which only the first two coordinates are used.
+    pub vertices: Vec<V>,
+}
+
+impl<V> Polygon<V> {
+    /// Returns the distance from the position to the closest edge on the plane of the first two axes.
+    fn edge_distance<F>(&self, position: &V) -> F
+    where
+        F: Float,
+        V: Vector<F>,
+    {
+        let (x, y) = (position[0], position[1]);
+        self.edges()
+            .map(|(a, b)| {
+                let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+                let length = dx * dx + dy * dy;
+                let t = if length > F::cast(0) {
+                    min(max(((x - a.0) * dx + (y - a.1) * dy) / length, F::cast(0)), F::cast(1))
+                } else {
+                    F::cast(0)
+                };
+                let (ex, ey) = (a.0 + dx * t - x, a.1 + dy * t - y);
+                NumFloat::sqrt(ex * ex + ey * ey)
+            })
+            .fold(NumFloat::max_value(), min)
+    }
+
+    fn edges<F>(&self) -> impl Iterator<Item = ((F, F), (F, F))> + '_
+    where
+        F: Float,
+        V: Vector<F>,
+    {
+        let count = self.vertices.len();
+        (0..count).map(move |i| {
+            let (a, b) = (&self.vertices[i], &self.vertices[(i + 1) % count]);
+            ((a[0], a[1]), (b[0], b[1]))
+        })
+    }
+}
+
+impl<F, V> Shape<F, V> for Polygon<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        let (x, y) = (sample[0], sample[1]);
+        self.edges()
+            .filter(|&(a, b)| {
+                (a.1 > y) != (b.1 > y) && x < a.0 + (y - a.1) / (b.1 - a.1) * (b.0 - a.0)
+            })
+            .count()
+            % 2
+            == 1
+    }
+}
+
+impl<F, V> Parametric<F, V> for Polygon<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn distance(&self, position: &V) -> F {
+        if self.contains(position) {
+            F::cast(0)
+        } else {
+            self.edge_distance(position)
+        }
+    }
+
+    fn perimeter(&self) -> F {
+        self.edges()
+            .map(|(a, b)| {
+                let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+                NumFloat::sqrt(dx * dx + dy * dy)
+            })
+            .fold(F::cast(0), |a, b| a + b)
+    }
+
+    /// The outline walks the edges from the first vertex with constant speed.
+    fn outline(&self, t: F) -> V {
+        let corners = self.edges().map(|(a, _)| a).collect::<Vec<_>>();
+        walk(&corners, self.vertices[0].clone(), t)
+    }
+
+    fn depth(&self, position: &V) -> F {
+        if self.contains(position) {
+            self.edge_distance(position)
+        } else {
+            F::cast(0)
+        }
+    }
+}
+
+/// Shape given by a signed distance function, which is negative inside the shape.
+/// The function should be a lower bound for the actual distance to the boundary on both sides of it,
+/// as exact signed distance functions are.
+/// The outline of the shape isn't known so boundary sampling skips it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sdf<T>(pub T);
+
+impl<F, V, T> Shape<F, V> for Sdf<T>
+where
+    F: Float,
+    V: Vector<F>,
+    T: Fn(&V) -> F,
+{
+    fn contains(&self, sample: &V) -> bool {
+        (self.0)(sample) <= F::cast(0)
+    }
+}
+
+impl<F, V, T> Parametric<F, V> for Sdf<T>
+where
+    F: Float,
+    V: Vector<F>,
+    T: Fn(&V) -> F,
+{
+    fn distance(&self, position: &V) -> F {
+        max((self.0)(position), F::cast(0))
+    }
+
+    fn perimeter(&self) -> F {
+        F::cast(0)
+    }
+
+    fn outline(&self, _t: F) -> V {
+        V::zero()
+    }
+
+    fn depth(&self, position: &V) -> F {
+        max(-(self.0)(position), F::cast(0))
+    }
+
+    fn on_outline(&self, _t: F) -> bool {
+        false
+    }
+}
+
+/// Shape given by signed distances sampled on a 3D grid of voxels, such as the signed distance fields
+/// baked from level geometry, which are negative inside the shape.
+/// The value of each voxel is at its center and the distance between them is trilinearly interpolated.
+/// Outside the grid the distance grows by the distance to the grid, and the voxels are meant to
+/// cover the domain. Like with `Sdf` the outline isn't known so boundary sampling skips it.
+///
+/// ````rust
+/// # use poisson::{algorithm, shape::VoxelSdf, Builder, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// // Open space is the lower half of the domain.
+/// let side = 8;
+/// let values = (0..side * side * side)
+///     .map(|i| ((i / (side * side)) as f64 + 0.5) / side as f64 - 0.5)
+///     .collect();
+/// let space = VoxelSdf::new(values, [side; 3], 1. / side as f64);
+/// let samples = Builder::<_, na::Vector3<f64>>::with_disk_radius(0.1, Type::Normal)
+///     .with_shape(space)
+///     .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+///     .generate();
+/// assert!(samples.iter().all(|s| s.z <= 0.5));
+/// ````
+#[derive(Clone, Debug, PartialEq)]
+pub struct VoxelSdf<F> {
+    values: Vec<F>,
+    resolution: [usize; 3],
+    cell_size: F,
+}
+
+impl<F> VoxelSdf<F>
+where
+    F: Float,
+{
+    /// Creates the shape from the signed distances of the voxels with the first axis changing fastest,
+    /// the amount of voxels along each axis and the side of the voxels.
+    pub fn new(values: Vec<F>, resolution: [usize; 3], cell_size: F) -> Self {
+        assert!(resolution.iter().all(|&r| r > 0));
+        assert_eq!(values.len(), resolution.iter().product::<usize>());
+        assert!(F::cast(0) < cell_size);
+        VoxelSdf {
+            values,
+            resolution,
+            cell_size,
+        }
+    }
+
+    /// Returns the amount of voxels along each axis.
+    pub fn resolution(&self) -> [usize; 3] {
+        self.resolution
+    }
+
+    /// Returns the side of the voxels.
+    pub fn cell_size(&self) -> F {
+        self.cell_size
+    }
+
+    /// Returns the interpolated signed distance at the position.
+    pub fn signed_distance<V>(&self, position: &V) -> F
+    where
+        V: Vector<F>,
+    {
+        assert_eq!(V::dimension(), 3, "Voxels are only defined in 3D.");
+        let half = F::cast(1) / F::cast(2);
+        let mut outside = F::cast(0);
+        // Index of the lower voxel of the interpolation and the weight of the upper one along each axis.
+        let mut lower = [0; 3];
+        let mut weight = [F::cast(0); 3];
+        for n in 0..3 {
+            let last = self.resolution[n] - 1;
+            let size = F::cast(self.resolution[n]) * self.cell_size;
+            let gap = max(max(-position[n], position[n] - size), F::cast(0));
+            outside += gap * gap;
+            let u = min(max(position[n] / self.cell_size - half, F::cast(0)), F::cast(last));
+            let index = NumFloat::floor(u).to_usize().unwrap_or(0).min(last.saturating_sub(1));
+            lower[n] = index;
+            weight[n] = if last > 0 { u - F::cast(index) } else { F::cast(0) };
+        }
+        let mut value = F::cast(0);
+        for corner in 0..8 {
+            let mut index = 0;
+            let mut w = F::cast(1);
+            for n in (0..3).rev() {
+                let upper = corner >> n & 1 == 1;
+                let i = (lower[n] + upper as usize).min(self.resolution[n] - 1);
+                index = index * self.resolution[n] + i;
+                w *= if upper { weight[n] } else { F::cast(1) - weight[n] };
+            }
+            value += w * self.values[index];
+        }
+        value + NumFloat::sqrt(outside)
+    }
+}
+
+impl<F, V> Shape<F, V> for VoxelSdf<F>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self.signed_distance(sample) <= F::cast(0)
+    }
+}
+
+impl<F, V> Parametric<F, V> for VoxelSdf<F>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn distance(&self, position: &V) -> F {
+        max(self.signed_distance(position), F::cast(0))
+    }
+
+    fn perimeter(&self) -> F {
+        F::cast(0)
+    }
+
+    fn outline(&self, _t: F) -> V {
+        V::zero()
+    }
+
+    fn depth(&self, position: &V) -> F {
+        max(-self.signed_distance(position), F::cast(0))
+    }
+
+    fn on_outline(&self, _t: F) -> bool {
+        false
+    }
+}
+
+/// Positions inside either of the shapes, such as a map plus islands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Union<A, B>(pub A, pub B);
+
+/// Positions inside both of the shapes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Intersection<A, B>(pub A, pub B);
+
+/// Positions inside the first shape but not inside the second one, such as a map minus lakes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference<A, B>(pub A, pub B);
+
+impl<F, V, A, B> Shape<F, V> for Union<A, B>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Shape<F, V>,
+    B: Shape<F, V>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self.0.contains(sample) || self.1.contains(sample)
+    }
+}
+
+impl<F, V, A, B> Shape<F, V> for Intersection<A, B>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Shape<F, V>,
+    B: Shape<F, V>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self.0.contains(sample) && self.1.contains(sample)
+    }
+}
+
+impl<F, V, A, B> Shape<F, V> for Difference<A, B>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Shape<F, V>,
+    B: Shape<F, V>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self.0.contains(sample) && !self.1.contains(sample)
+    }
+}
+
+/// Splits the parameter of the outline of a composition between the outlines of its parts by their perimeters,
+/// returning which part it's on and the parameter of the outline of that part.
+fn split<F, V, A, B>(a: &A, b: &B, t: F) -> (bool, F)
+where
+    F: Float,
+    V: Vector<F>,
+    A: Parametric<F, V>,
+    B: Parametric<F, V>,
+{
+    let (first, second) = (a.perimeter(), b.perimeter());
+    let split = if first + second > F::cast(0) {
+        first / (first + second)
+    } else {
+        F::cast(1)
+    };
+    if t < split {
+        (true, t / split)
+    } else {
+        (false, (t - split) / (F::cast(1) - split))
+    }
+}
+
+impl<F, V, A, B> Parametric<F, V> for Union<A, B>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Parametric<F, V>,
+    B: Parametric<F, V>,
+{
+    fn distance(&self, position: &V) -> F {
+        min(self.0.distance(position), self.1.distance(position))
+    }
+
+    fn perimeter(&self) -> F {
+        self.0.perimeter() + self.1.perimeter()
+    }
+
+    /// The outline of the first shape is on the start of the parameter and the outline of the second one on the rest,
+    /// split by their perimeters.
+    fn outline(&self, t: F) -> V {
+        match split(&self.0, &self.1, t) {
+            (true, t) => self.0.outline(t),
+            (false, t) => self.1.outline(t),
+        }
+    }
+
+    fn depth(&self, position: &V) -> F {
+        max(self.0.depth(position), self.1.depth(position))
+    }
+
+    /// The outline of each shape is on the boundary where it isn't inside the other shape.
+    fn on_outline(&self, t: F) -> bool {
+        match split(&self.0, &self.1, t) {
+            (true, t) => self.0.on_outline(t) && !self.1.contains(&self.0.outline(t)),
+            (false, t) => self.1.on_outline(t) && !self.0.contains(&self.1.outline(t)),
+        }
+    }
+}
+
+impl<F, V, A, B> Parametric<F, V> for Intersection<A, B>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Parametric<F, V>,
+    B: Parametric<F, V>,
+{
+    fn distance(&self, position: &V) -> F {
+        max(self.0.distance(position), self.1.distance(position))
+    }
+
+    fn perimeter(&self) -> F {
+        self.0.perimeter() + self.1.perimeter()
+    }
+
+    /// The outline of the first shape is on the start of the parameter and the outline of the second one on the rest,
+    /// split by their perimeters.
+    fn outline(&self, t: F) -> V {
+        match split(&self.0, &self.1, t) {
+            (true, t) => self.0.outline(t),
+            (false, t) => self.1.outline(t),
+        }
+    }
+
+    fn depth(&self, position: &V) -> F {
+        min(self.0.depth(position), self.1.depth(position))
+    }
+
+    /// The outline of each shape is on the boundary where it's inside the other shape.
+    fn on_outline(&self, t: F) -> bool {
+        match split(&self.0, &self.1, t) {
+            (true, t) => self.0.on_outline(t) && self.1.contains(&self.0.outline(t)),
+            (false, t) => self.1.on_outline(t) && self.0.contains(&self.1.outline(t)),
+        }
+    }
+}
+
+impl<F, V, A, B> Parametric<F, V> for Difference<A, B>
+where
+    F: Float,
+    V: Vector<F>,
+    A: Parametric<F, V>,
+    B: Parametric<F, V>,
+{
+    fn distance(&self, position: &V) -> F {
+        max(self.0.distance(position), self.1.depth(position))
+    }
+
+    fn perimeter(&self) -> F {
+        self.0.perimeter() + self.1.perimeter()
+    }
+
+    /// The outline of the first shape is on the start of the parameter and the outline of the second one on the rest,
+    /// split by their perimeters.
+    fn outline(&self, t: F) -> V {
+        match split(&self.0, &self.1, t) {
+            (true, t) => self.0.outline(t),
+            (false, t) => self.1.outline(t),
+        }
+    }
+
+    fn depth(&self, position: &V) -> F {
+        min(self.0.depth(position), self.1.distance(position))
+    }
+
+    /// The outline of the first shape is on the boundary where it isn't inside the second shape,
+    /// and the outline of the second shape where it's inside the first shape.
+    fn on_outline(&self, t: F) -> bool {
+        match split(&self.0, &self.1, t) {
+            (true, t) => self.0.on_outline(t) && !self.1.contains(&self.0.outline(t)),
+            (false, t) => self.1.on_outline(t) && self.0.contains(&self.1.outline(t)),
+        }
+    }
+}
+
+/// Simple shape that generators can sample directly with `Builder::with_shape`.
+/// Knowing the distance lets the generators skip the parts of the grid outside of the shape
+/// and knowing the outline lets them sample its boundary with `Builder::with_boundary_sampling`.
+pub trait Parametric<F, V>: Shape<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Returns lower bound for the distance from the position to the shape, which is zero inside of it.
+    fn distance(&self, position: &V) -> F;
+
+    /// Returns the length of the outline.
+    fn perimeter(&self) -> F;
+
+    /// Returns the point of the outline at the parameter in [0, 1).
+    /// Outlines lie on the plane of the first two axes.
+    fn outline(&self, t: F) -> V;
+
+    /// Returns lower bound for the distance from the position to the outside of the shape, which is zero outside of it.
+    /// Compositions that subtract the shape use it to skip the parts of the grid inside of it.
+    fn depth(&self, _position: &V) -> F {
+        F::cast(0)
+    }
+
+    /// Checks if the point of the outline at the parameter is on the boundary of the shape.
+    /// Compositions walk the outlines of their parts, some of which end up inside the composition.
+    fn on_outline(&self, _t: F) -> bool {
+        true
+    }
+}
+
+/// Ring between two circles sharing a center.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annulus<F, V> {
+    /// Center of the circles.
+    pub center: V,
+    /// Radius of the inner circle.
+    pub inner: F,
+    /// Radius of the outer circle.
+    pub outer: F,
+}
+
+impl<F, V> Shape<F, V> for Annulus<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        let sqdist = (sample.clone() - self.center.clone()).norm_squared();
+        self.inner * self.inner <= sqdist && sqdist <= self.outer * self.outer
+    }
+}
+
+impl<F, V> Parametric<F, V> for Annulus<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn distance(&self, position: &V) -> F {
+        let dist = (position.clone() - self.center.clone()).norm();
+        max(max(dist - self.outer, self.inner - dist), F::cast(0))
+    }
+
+    fn perimeter(&self) -> F {
+        tau::<F>() * (self.inner + self.outer)
+    }
+
+    /// The outer circle is on [0, outer / (inner + outer)) and the inner circle on the rest.
+    fn outline(&self, t: F) -> V {
+        let split = self.outer / (self.inner + self.outer);
+        if t < split {
+            circle(&self.center, self.outer, self.outer, t / split)
+        } else {
+            circle(
+                &self.center,
+                self.inner,
+                self.inner,
+                (t - split) / (F::cast(1) - split),
+            )
+        }
+    }
+    fn depth(&self, position: &V) -> F {
+        let dist = (position.clone() - self.center.clone()).norm();
+        max(min(dist - self.inner, self.outer - dist), F::cast(0))
+    }
+}
+
+/// Axis aligned ellipse with center and semi-axes.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ellipse<V> {
+    /// Center of the ellipse.
+    pub center: V,
+    /// Lengths of the semi-axes along each axis.
+    pub radii: V,
+}
+
+impl<F, V> Shape<F, V> for Ellipse<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self.scaled(sample).norm_squared() <= F::cast(1)
+    }
+}
+
+impl<V> Ellipse<V> {
+    /// Returns the position in the space where the ellipse is the unit ball.
+    fn scaled<F>(&self, position: &V) -> V
+    where
+        F: Float,
+        V: Vector<F>,
+    {
+        let mut scaled = position.clone() - self.center.clone();
+        for n in 0..V::dimension() {
+            scaled[n] /= self.radii[n];
+        }
+        scaled
+    }
+}
+
+impl<F, V> Parametric<F, V> for Ellipse<V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Scaling the ellipse into the unit ball shrinks distances by at most the shortest semi-axis,
+    /// which bounds the distance from below.
+    fn distance(&self, position: &V) -> F {
+        let shortest = (0..V::dimension())
+            .map(|n| self.radii[n])
+            .fold(self.radii[0], min);
+        max(
+            (self.scaled(position).norm() - F::cast(1)) * shortest,
+            F::cast(0),
+        )
+    }
+
+    /// Ramanujan's approximation of the perimeter.
+    fn perimeter(&self) -> F {
+        let (a, b) = (self.radii[0], self.radii[1]);
+        let h = NumFloat::powi((a - b) / (a + b), 2);
+        let three = F::cast(3);
+        tau::<F>() / F::cast(2)
+            * (a + b)
+            * (F::cast(1) + three * h / (F::cast(10) + NumFloat::sqrt(F::cast(4) - three * h)))
+    }
+
+    fn outline(&self, t: F) -> V {
+        circle(&self.center, self.radii[0], self.radii[1], t)
+    }
+
+    /// Bounded from below the same way as the distance.
+    fn depth(&self, position: &V) -> F {
+        let shortest = (0..V::dimension())
+            .map(|n| self.radii[n])
+            .fold(self.radii[0], min);
+        max(
+            (F::cast(1) - self.scaled(position).norm()) * shortest,
+            F::cast(0),
+        )
+    }
+}
+
+/// Axis aligned box with rounded corners from minimum corner to maximum corner.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundedRectangle<F, V> {
+    /// Minimum corner of the box.
+    pub min: V,
+    /// Maximum corner of the box.
+    pub max: V,
+    /// Radius of the rounded corners.
+    pub corner: F,
+}
+
+impl<F, V> RoundedRectangle<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Returns the distance from the position to the box shrunk by the radius of the corners.
+    fn inner_distance(&self, position: &V) -> F {
+        let mut outside = V::zero();
+        for n in 0..V::dimension() {
+            let low = self.min[n] + self.corner;
+            let high = self.max[n] - self.corner;
+            outside[n] = max(max(low - position[n], position[n] - high), F::cast(0));
+        }
+        outside.norm()
+    }
+}
+
+impl<F, V> Shape<F, V> for RoundedRectangle<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn contains(&self, sample: &V) -> bool {
+        self.inner_distance(sample) <= self.corner
+    }
+}
+
+impl<F, V> Parametric<F, V> for RoundedRectangle<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn distance(&self, position: &V) -> F {
+        max(self.inner_distance(position) - self.corner, F::cast(0))
+    }
+
+    fn perimeter(&self) -> F {
+        let width = self.max[0] - self.min[0] - F::cast(2) * self.corner;
+        let height = self.max[1] - self.min[1] - F::cast(2) * self.corner;
+        F::cast(2) * (width + height) + tau::<F>() * self.corner
+    }
+
+    /// The outline goes counterclockwise from the start of the bottom edge with constant speed.
+    fn outline(&self, t: F) -> V {
+        let width = self.max[0] - self.min[0] - F::cast(2) * self.corner;
+        let height = self.max[1] - self.min[1] - F::cast(2) * self.corner;
+        let arc = tau::<F>() * self.corner / F::cast(4);
+        let mut s = t * self.perimeter();
+        let mut point = V::zero();
+        let (x0, y0) = (self.min[0] + self.corner, self.min[1] + self.corner);
+        let (x1, y1) = (x0 + width, y0 + height);
+        // Each side is followed by the quarter circle turning to the next one.
+        let sides = [
+            (x0, self.min[1], F::cast(1), F::cast(0), width),
+            (self.max[0], y0, F::cast(0), F::cast(1), height),
+            (x1, self.max[1], -F::cast(1), F::cast(0), width),
+            (self.min[0], y1, F::cast(0), -F::cast(1), height),
+        ];
+        let corners = [(x1, y0), (x1, y1), (x0, y1), (x0, y0)];
+        for (i, &(x, y, dx, dy, length)) in sides.iter().enumerate() {
+            if s < length {
+                point[0] = x + dx * s;
+                point[1] = y + dy * s;
+                return point;
+            }
+            s -= length;
+            if s < arc || i == 3 {
+                let angle = tau::<F>() / F::cast(4) * (F::cast(i) - F::cast(1)) + s / self.corner;
+                point[0] = corners[i].0 + self.corner * NumFloat::cos(angle);
+                point[1] = corners[i].1 + self.corner * NumFloat::sin(angle);
+                return point;
+            }
+            s -= arc;
+        }
+        unreachable!("The parameter should be inside of the outline.")
+    }
+
+    /// Inside the box shrunk by the radius of the corners the depth grows by the distance to its sides.
+    fn depth(&self, position: &V) -> F {
+        let inner = self.inner_distance(position);
+        if inner > F::cast(0) {
+            return max(self.corner - inner, F::cast(0));
+        }
+        let sides = (0..V::dimension())
+            .map(|n| {
+                let low = self.min[n] + self.corner;
+                let high = self.max[n] - self.corner;
+                min(position[n] - low, high - position[n])
+            })
+            .fold(NumFloat::max_value(), min);
+        self.corner + sides
+    }
+}
+
+/// Returns the point at the parameter in [0, 1) on the axis aligned ellipse on the plane of the first two axes.
+fn circle<F, V>(center: &V, a: F, b: F, t: F) -> V
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let angle = tau::<F>() * t;
+    let mut point = center.clone();
+    point[0] += a * NumFloat::cos(angle);
+    point[1] += b * NumFloat::sin(angle);
+    point
+}
+
+/// Returns the point at the parameter in [0, 1) on the closed path through the corners on the plane of
+/// the first two axes, walked with constant speed. The rest of the coordinates are taken from the base.
+fn walk<F, V>(corners: &[(F, F)], base: V, t: F) -> V
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let count = corners.len();
+    let length = |i: usize| {
+        let (a, b) = (corners[i], corners[(i + 1) % count]);
+        NumFloat::sqrt(NumFloat::powi(b.0 - a.0, 2) + NumFloat::powi(b.1 - a.1, 2))
+    };
+    let mut s = t * (0..count).map(length).fold(F::cast(0), |a, b| a + b);
+    let mut point = base;
+    for i in 0..count {
+        let (a, b) = (corners[i], corners[(i + 1) % count]);
+        let edge = length(i);
+        if s < edge || i == count - 1 {
+            let f = if edge > F::cast(0) {
+                min(s / edge, F::cast(1))
+            } else {
+                F::cast(0)
+            };
+            point[0] = a.0 + (b.0 - a.0) * f;
+            point[1] = a.1 + (b.1 - a.1) * f;
+            return point;
+        }
+        s -= edge;
+    }
+    point
+}
+
+fn tau<F>() -> F
+where
+    F: Float,
+{
+    NumCast::from(std::f64::consts::TAU).expect("Casting constant should always work.")
+}
+
+fn max<F>(a: F, b: F) -> F
+where
+    F: Float,
+{
+    if a < b {
+        b
+    } else {
+        a
+    }
+}
+
+fn min<F>(a: F, b: F) -> F
+where
+    F: Float,
+{
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+/// Shape restricting the generation, shared between clones of the builder.
+#[derive(Clone)]
+pub(crate) struct Restriction<F, V>(pub(crate) Arc<dyn Parametric<F, V> + Send + Sync>);
+
+impl<F, V> fmt::Debug for Restriction<F, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Shape")
+    }
+}
+
+impl<F, V> PartialEq for Restriction<F, V> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
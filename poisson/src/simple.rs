@@ -0,0 +1,39 @@
+//! Module that contains functions for generating distributions without choosing the types.
+
+use crate::array::{Array2, Array3};
+use crate::{algorithm, Builder, Type};
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Generates non-tiling poisson-disk distribution in [0, 1)<sup>2</sup> with given disk radius using `algorithm::Bridson`.
+/// The same seed always generates the same samples.
+///
+/// ````rust
+/// let samples = poisson::sample_2d(0.05, 1);
+/// assert!(!samples.is_empty());
+/// assert_eq!(samples, poisson::sample_2d(0.05, 1));
+/// ````
+pub fn sample_2d(radius: f64, seed: u64) -> Vec<[f64; 2]> {
+    Array2::into_vec(
+        Builder::<_, Array2<f64>>::with_disk_radius(radius, Type::Normal)
+            .build(StdRng::seed_from_u64(seed), algorithm::Bridson)
+            .generate(),
+    )
+}
+
+/// Generates non-tiling poisson-disk distribution in [0, 1)<sup>3</sup> with given disk radius using `algorithm::Bridson`.
+/// The same seed always generates the same samples.
+///
+/// ````rust
+/// let samples = poisson::sample_3d(0.1, 1);
+/// assert!(!samples.is_empty());
+/// assert_eq!(samples, poisson::sample_3d(0.1, 1));
+/// ````
+pub fn sample_3d(radius: f64, seed: u64) -> Vec<[f64; 3]> {
+    Array3::into_vec(
+        Builder::<_, Array3<f64>>::with_disk_radius(radius, Type::Normal)
+            .build(StdRng::seed_from_u64(seed), algorithm::Bridson)
+            .generate(),
+    )
+}
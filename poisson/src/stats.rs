@@ -0,0 +1,33 @@
+//! Module that contains statistics about the generation.
+
+use std::time::Duration;
+
+/// Statistics about the generation for diagnosing slow parameter sets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenerationStats {
+    /// Amount of candidate samples the algorithm tried.
+    pub candidates: usize,
+    /// Amount of candidate samples that were accepted to the distribution.
+    pub accepted: usize,
+    /// Amount of candidate samples that were rejected.
+    pub rejected: usize,
+    /// Deepest level of grid subdivision reached by the algorithm.
+    pub depth: usize,
+    /// Time spent generating samples.
+    pub elapsed: Duration,
+    /// Name of the algorithm that generated the samples, which can differ from the one asked for
+    /// when it's picked by `algorithm::Auto` or when the radius is large enough for `algorithm::Sparse`.
+    pub algorithm: Option<&'static str>,
+}
+
+impl GenerationStats {
+    pub(crate) fn accept(&mut self) {
+        self.candidates += 1;
+        self.accepted += 1;
+    }
+
+    pub(crate) fn reject(&mut self) {
+        self.candidates += 1;
+        self.rejected += 1;
+    }
+}
@@ -0,0 +1,257 @@
+//! Module that contains recordings of the generation that can be replayed without generating again.
+
+use crate::{Boundary, Domain, Float, PointSet, Vector};
+
+use num_traits::NumCast;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Event of the generation in the order it happened.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TraceEvent<V> {
+    /// Start point or boundary sample that was emitted before the generated samples.
+    Start(V),
+    /// Amount of candidates the algorithm rejected in a row.
+    /// The algorithms don't keep the positions of rejected candidates so only their amount is recorded.
+    Rejected(usize),
+    /// Candidate that was accepted to the distribution.
+    Accepted(V),
+}
+
+/// Compact log of the candidates an iterator went through, recorded when `Builder::record_trace` is set.
+///
+/// `PointSet::replay` reconstructs the exact distribution from the trace without the random number generator or
+/// the algorithm, so traces can be attached to bug reports or cached in asset pipelines.
+/// The trace is written as text with a header line of `key=value` pairs followed by an event on each line,
+/// which can be parsed back with `str::parse`. Coordinates are written so that they are parsed back to the same bits.
+/// Samples added with `PoissonIter::restrict` aren't emitted by the iterator so they aren't in the trace.
+///
+/// ````rust
+/// # use poisson::{algorithm, Builder, PointSet, Trace, Type};
+/// # use rand::{rngs::SmallRng, SeedableRng};
+/// # use nalgebra as na;
+/// type Vec2 = na::Vector2<f64>;
+/// let generator = Builder::<_, Vec2>::with_disk_radius(0.05, Type::Normal)
+///     .build(SmallRng::seed_from_u64(3), algorithm::Bridson);
+/// let (set, trace) = generator.generate_with_trace();
+/// let text = trace.to_string();
+/// let parsed: Trace<f64, Vec2> = text.parse().unwrap();
+/// assert_eq!(set.points(), PointSet::replay(&parsed).points());
+/// ````
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Radius of the distribution.
+    pub radius: F,
+    /// Domain of the distribution.
+    pub domain: Domain,
+    /// Name of the algorithm that generated the samples if it's known.
+    pub algorithm: Option<String>,
+    /// Events in the order they happened.
+    pub events: Vec<TraceEvent<V>>,
+}
+
+impl<F, V> Trace<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Returns the amount of candidates the algorithm tried, which doesn't include the start points.
+    pub fn candidates(&self) -> usize {
+        self.accepted() + self.rejected()
+    }
+
+    /// Returns the amount of candidates that were accepted.
+    pub fn accepted(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|e| matches!(e, TraceEvent::Accepted(_)))
+            .count()
+    }
+
+    /// Returns the amount of candidates that were rejected.
+    pub fn rejected(&self) -> usize {
+        self.events
+            .iter()
+            .map(|e| match *e {
+                TraceEvent::Rejected(n) => n,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+impl<F, V> PointSet<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    /// Reconstructs the distribution the trace was recorded from, with the samples in the order they were emitted.
+    pub fn replay(trace: &Trace<F, V>) -> Self {
+        let points = trace
+            .events
+            .iter()
+            .filter_map(|e| match *e {
+                TraceEvent::Start(ref p) | TraceEvent::Accepted(ref p) => Some(p.clone()),
+                TraceEvent::Rejected(_) => None,
+            })
+            .collect();
+        PointSet::new(points, trace.radius, trace.domain)
+    }
+}
+
+/// Writes the coordinates as `f64` so that parsing them gives the same bits.
+fn coordinates<F, V>(f: &mut fmt::Formatter<'_>, p: &V) -> fmt::Result
+where
+    F: Float,
+    V: Vector<F>,
+{
+    for n in 0..V::dimension() {
+        let c = p[n].to_f64().expect("Coordinate should be castable to f64.");
+        write!(f, " {:?}", c)?;
+    }
+    Ok(())
+}
+
+impl<F, V> fmt::Display for Trace<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dim = V::dimension();
+        let radius = self.radius.to_f64().expect("Radius should be castable to f64.");
+        let boundaries = (0..dim)
+            .map(|n| match self.domain.boundary(n) {
+                Boundary::Open => 'o',
+                Boundary::Perioditic => 'p',
+            })
+            .collect::<String>();
+        let extents = (0..dim)
+            .map(|n| format!("{:?}", self.domain.extent(n)))
+            .collect::<Vec<_>>()
+            .join(",");
+        write!(
+            f,
+            "radius={:?} dimension={} boundaries={} extents={}",
+            radius, dim, boundaries, extents
+        )?;
+        if let Some(ref algorithm) = self.algorithm {
+            write!(f, " algorithm={}", algorithm)?;
+        }
+        writeln!(f)?;
+        for event in &self.events {
+            match *event {
+                TraceEvent::Start(ref p) => {
+                    write!(f, "start")?;
+                    coordinates(f, p)?;
+                }
+                TraceEvent::Rejected(n) => write!(f, "reject {}", n)?,
+                TraceEvent::Accepted(ref p) => {
+                    write!(f, "accept")?;
+                    coordinates(f, p)?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<F, V> FromStr for Trace<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let dim = V::dimension();
+        let invalid = |what: &str| format!("Invalid or missing {} in trace", what);
+        let mut lines = s.lines();
+        let header = lines.next().ok_or_else(|| invalid("header"))?;
+        let mut radius = None;
+        let mut domain = Domain::open();
+        let mut algorithm = None;
+        let mut found = 0;
+        for pair in header.split_whitespace() {
+            let (key, value) = pair.split_once('=').ok_or_else(|| invalid(pair))?;
+            match key {
+                "radius" => {
+                    let r: f64 = value.parse().map_err(|_| invalid(key))?;
+                    radius = Some(NumCast::from(r).ok_or_else(|| invalid(key))?);
+                }
+                "dimension" => {
+                    if value.parse() != Ok(dim) {
+                        return Err(invalid(key));
+                    }
+                }
+                "boundaries" => {
+                    if value.len() != dim {
+                        return Err(invalid(key));
+                    }
+                    for (n, c) in value.chars().enumerate() {
+                        let boundary = match c {
+                            'o' => Boundary::Open,
+                            'p' => Boundary::Perioditic,
+                            _ => return Err(invalid(key)),
+                        };
+                        domain = domain.with_boundary(n, boundary);
+                    }
+                }
+                "extents" => {
+                    for (n, e) in value.split(',').enumerate() {
+                        let e: f64 = e.parse().map_err(|_| invalid(key))?;
+                        if e != 1. {
+                            domain = domain.with_extent(n, e);
+                        }
+                    }
+                }
+                "algorithm" => algorithm = Some(value.to_owned()),
+                _ => return Err(invalid(key)),
+            }
+            if key != "algorithm" {
+                found += 1;
+            }
+        }
+        if found != 4 {
+            return Err(invalid("field"));
+        }
+        let mut events = vec![];
+        for line in lines {
+            let mut words = line.split_whitespace();
+            let kind = match words.next() {
+                Some(kind) => kind,
+                None => continue,
+            };
+            if kind == "reject" {
+                let n = words.next().and_then(|n| n.parse().ok());
+                events.push(TraceEvent::Rejected(n.ok_or_else(|| invalid("event"))?));
+                continue;
+            }
+            let mut p = V::zero();
+            for n in 0..dim {
+                let c: f64 = words
+                    .next()
+                    .and_then(|c| c.parse().ok())
+                    .ok_or_else(|| invalid("coordinate"))?;
+                p[n] = NumCast::from(c).ok_or_else(|| invalid("coordinate"))?;
+            }
+            events.push(match kind {
+                "start" => TraceEvent::Start(p),
+                "accept" => TraceEvent::Accepted(p),
+                _ => return Err(invalid("event")),
+            });
+        }
+        Ok(Trace {
+            radius: radius.ok_or_else(|| invalid("radius"))?,
+            domain,
+            algorithm,
+            events,
+        })
+    }
+}
@@ -0,0 +1,78 @@
+//! Module that contains settings tuned for the speed of generation on a machine.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::DEFAULT_ATTEMPTS;
+
+/// Settings that generate a set of parameters fastest on a machine, as benchmarked with criterion by `poisson-tune`.
+///
+/// The tuning is written as TOML with a `key = value` pair on each line and can be parsed back with `str::parse`,
+/// so it can be stored next to the configuration of an application. `Builder::with_tuning` applies it.
+///
+/// ````rust
+/// # use poisson::Tuning;
+/// let tuning: Tuning = "algorithm = \"bridson\"\ngrid_factor = 0.7\nbridson_k = 20\n".parse().unwrap();
+/// assert_eq!(20, tuning.bridson_k);
+/// assert_eq!(tuning, tuning.to_string().parse().unwrap());
+/// ````
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tuning {
+    /// Name of the fastest algorithm in lowercase, which has to be picked by hand as it's a type parameter.
+    pub algorithm: String,
+    /// Factor the cells of the grid are scaled by relative to the cells inscribed in the disk of the radius,
+    /// as in `Builder::with_cell_scale`.
+    pub grid_factor: f64,
+    /// Amount of samples Bridson tries around an active sample, called k in the paper of Bridson,
+    /// as in `Builder::with_attempts`.
+    pub bridson_k: usize,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Tuning {
+            algorithm: "bridson".to_owned(),
+            grid_factor: 1.,
+            bridson_k: DEFAULT_ATTEMPTS,
+        }
+    }
+}
+
+impl fmt::Display for Tuning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "algorithm = {:?}", self.algorithm)?;
+        writeln!(f, "grid_factor = {:?}", self.grid_factor)?;
+        writeln!(f, "bridson_k = {}", self.bridson_k)
+    }
+}
+
+impl FromStr for Tuning {
+    type Err = String;
+
+    /// Parses the tuning leaving the settings missing from it to their defaults.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |line: &str| format!("Invalid line in tuning: {}", line);
+        let mut tuning = Tuning::default();
+        for line in s.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid(line))?;
+            let value = value.trim();
+            match key.trim() {
+                "algorithm" => {
+                    tuning.algorithm = value
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .ok_or_else(|| invalid(line))?
+                        .to_owned()
+                }
+                "grid_factor" => tuning.grid_factor = value.parse().map_err(|_| invalid(line))?,
+                "bridson_k" => tuning.bridson_k = value.parse().map_err(|_| invalid(line))?,
+                _ => return Err(invalid(line)),
+            }
+        }
+        Ok(tuning)
+    }
+}
@@ -0,0 +1,98 @@
+//! Exact comparison of distances for pairs of samples whose distance is too close to the diameter for
+//! the rounded squared distance to tell which side of it they are on.
+//!
+//! Each value is represented as an expansion, a sum of floats that don't overlap, and the sums and
+//! products of expansions are computed without rounding following Shewchuk's adaptive predicates.
+
+use crate::{Domain, Float, Vector};
+
+use num_traits::Float as NumFloat;
+
+/// Relative distance from the squared diameter within which the rounded squared distance can't be trusted.
+pub fn is_near<F, V>(sqdist: F, sqdiameter: F) -> bool
+where
+    F: Float,
+    V: Vector<F>,
+{
+    // Each axis adds the rounding of a difference, a square and a sum, and the squared diameter rounds once more.
+    let tolerance = F::cast(4 * (V::dimension() + 2)) * NumFloat::epsilon();
+    NumFloat::abs(sqdist - sqdiameter) <= tolerance * sqdiameter
+}
+
+/// Checks without rounding if the samples are farther apart than the diameter.
+pub fn is_farther<F, V>(v1: &V, v2: &V, diameter: F, domain: Domain) -> bool
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let mut sum = vec![];
+    for n in 0..V::dimension() {
+        let (hi, lo) = two_sum(v2[n], -v1[n]);
+        let mut diff = vec![lo, hi];
+        if domain.is_perioditic(n) {
+            let extent = domain.extent_of::<F>(n);
+            let half = extent / F::cast(2);
+            if hi > half {
+                diff = grow(&diff, -extent);
+            } else if hi < -half {
+                diff = grow(&diff, extent);
+            }
+        }
+        for &a in &diff {
+            for &b in &diff {
+                let (hi, lo) = two_product(a, b);
+                sum = grow(&grow(&sum, lo), hi);
+            }
+        }
+    }
+    let (hi, lo) = two_product(diameter, diameter);
+    sum = grow(&grow(&sum, -lo), -hi);
+    // The components grow in magnitude so the largest one that isn't zero has the sign of the whole sum.
+    sum.iter()
+        .rev()
+        .find(|&&c| c != F::cast(0))
+        .is_some_and(|&c| c > F::cast(0))
+}
+
+/// Sum of two floats as the rounded sum and its rounding error.
+fn two_sum<F: Float>(a: F, b: F) -> (F, F) {
+    let sum = a + b;
+    let virt = sum - a;
+    (sum, (a - (sum - virt)) + (b - virt))
+}
+
+/// Product of two floats as the rounded product and its rounding error.
+fn two_product<F: Float>(a: F, b: F) -> (F, F) {
+    let product = a * b;
+    (product, NumFloat::mul_add(a, b, -product))
+}
+
+/// Adds a float to an expansion whose components grow in magnitude.
+fn grow<F: Float>(expansion: &[F], b: F) -> Vec<F> {
+    let mut result = Vec::with_capacity(expansion.len() + 1);
+    let mut carry = b;
+    for &e in expansion {
+        let (sum, error) = two_sum(carry, e);
+        if error != F::cast(0) {
+            result.push(error);
+        }
+        carry = sum;
+    }
+    result.push(carry);
+    result
+}
+
+#[test]
+fn two_product_is_exact() {
+    let (hi, lo) = two_product(0.1f64, 0.1);
+    assert_eq!(0.1f64 * 0.1, hi);
+    assert!(lo != 0.);
+    assert_eq!(hi, hi + lo);
+}
+
+#[test]
+fn pair_exactly_at_diameter_is_not_farther() {
+    let (v1, v2) = (nalgebra::Vector2::new(0., 0.), nalgebra::Vector2::new(0.1, 0.));
+    assert!(!is_farther(&v1, &v2, 0.1, Domain::open()));
+    assert!(is_farther(&v1, &v2, f64::from_bits(0.1f64.to_bits() - 1), Domain::open()));
+}
@@ -47,6 +47,21 @@ lazy_static! {
         ];
 }
 
+/// Returns the fraction of the space the balls of the radius around the samples cover in a maximal distribution.
+/// Measured from large perioditic distributions generated with Ebeida, which is known only for 2, 3 and 4 dimensions.
+pub fn maximal_density(dim: usize) -> Option<f64> {
+    const DENSITIES: [f64; 3] = [0.502, 0.341, 0.223];
+    DENSITIES.get(dim.wrapping_sub(2)).cloned()
+}
+
+/// Returns the largest radius that is allowed, √2 / 2.
+pub fn max_radius<F>() -> F
+where
+    F: Float,
+{
+    NumCast::from(2f64.sqrt() / 2.).expect("Casting constant should always work.")
+}
+
 fn precalc(dim: usize) -> f64 {
     let index = dim - 2;
     (MAX_PACKING_DENSITIES[index] * GAMMA[index]) / HALF_TAU.powf(dim as f64 / 2.)
@@ -1,6 +1,7 @@
 //! Helper functions that poisson uses.
 
-use crate::{Builder, Float, Type, Vector};
+use crate::field::Anisotropy;
+use crate::{Builder, Buffers, Domain, Float, Precision, Rejection, Vector};
 
 use num_traits::{Float as NumFloat, NumCast};
 
@@ -10,20 +11,49 @@ use rand_distr::Distribution;
 
 use modulo::Mod;
 
+use smallvec::SmallVec;
+
 use std::marker::PhantomData;
 
+pub mod exact;
 pub mod math;
+pub mod vector;
+
+/// Marks the end of samples in a cell.
+const EMPTY: usize = usize::MAX;
+
+/// Buffer for the samples around a cell.
+/// Without regions or anisotropy a cell has at most one sample so the inline capacity fits the 25 cells around a cell
+/// in two dimensions and higher dimensions spill to the heap only when their neighbourhood is crowded.
+pub type Neighbours<V> = SmallVec<[V; 32]>;
+
+/// Buffer for the coordinates of the samples in the grid.
+#[derive(Clone)]
+enum Samples<V> {
+    Full(Vec<V>),
+    Single(Vec<f32>),
+    #[cfg(feature = "half")]
+    Half(Vec<half::f16>),
+}
 
+/// Background grid for the samples.
+/// Samples are stored in a single buffer where each sample links to the next sample of the same cell
+/// so the grid does a handful of large allocations instead of one for each occupied cell.
 #[derive(Clone)]
 pub struct Grid<F, V>
 where
     F: Float,
     V: Vector<F>,
 {
-    data: Vec<Vec<V>>,
+    heads: Vec<usize>,
+    links: Vec<usize>,
+    samples: Samples<V>,
     side: usize,
     cell: F,
-    poisson_type: Type,
+    reach: Vec<i64>,
+    reached: F,
+    domain: Domain,
+    buffers: Option<Buffers<V>>,
     _marker: PhantomData<F>,
 }
 
@@ -32,37 +62,139 @@ where
     F: Float,
     V: Vector<F>,
 {
-    pub fn new(radius: F, poisson_type: Type) -> Grid<F, V> {
+    pub fn new(radius: F, domain: Domain, precision: Precision) -> Grid<F, V> {
+        Grid::scaled(radius, F::cast(1), domain, precision)
+    }
+
+    /// Grid with cells `scale` times the size of the cells inscribed in the disk of the radius.
+    /// The neighbourhood grows or shrinks with the cells so that it covers the same distance.
+    pub fn scaled(radius: F, scale: F, domain: Domain, precision: Precision) -> Grid<F, V> {
         let dim = F::cast(V::dimension());
-        let cell = (F::cast(2) * radius) / NumFloat::sqrt(dim);
+        let cell = scale * (F::cast(2) * radius) / NumFloat::sqrt(dim);
+        // The diameter is √d / scale cells wide, which is more than two cells from five dimensions on.
+        let reach = NumFloat::ceil(NumFloat::sqrt(dim) / scale)
+            .to_i64()
+            .expect("Expected that the cell scale would be positive.");
+        // Radii too large for a whole cell to fit still get a single cell covering the whole domain.
         let side = (F::cast(1) / cell)
             .to_usize()
-            .expect("Expected that dividing 1 by cell width would be legal.");
+            .expect("Expected that dividing 1 by cell width would be legal.")
+            .max(1);
+        let reached = F::cast(reach as usize) / F::cast(side);
+        let reach = reach + domain.has_short_seam(V::dimension(), side) as i64;
         Grid {
             cell,
             side,
-            data: vec![
-                vec![];
-                side.pow(
-                    dim.to_u32()
-                        .expect("Dimension should be always be castable to u32.")
-                )
-            ],
-            poisson_type,
+            reach: (-reach..=reach).collect(),
+            reached,
+            // Axes shorter than the unit have fewer cells so long thin domains don't waste cells.
+            heads: vec![EMPTY; (0..V::dimension()).map(|n| domain.cells(n, side)).product()],
+            links: vec![],
+            samples: match precision {
+                Precision::Full => Samples::Full(vec![]),
+                Precision::Single => Samples::Single(vec![]),
+                #[cfg(feature = "half")]
+                Precision::Half => Samples::Half(vec![]),
+            },
+            domain,
+            buffers: None,
             _marker: PhantomData,
         }
     }
 
-    pub fn get(&self, index: V) -> Option<&Vec<V>> {
-        encode(&index, self.side, self.poisson_type).map(|t| &self.data[t])
+    /// Moves the samples into buffers taken from the pool, which get them back when the grid is dropped.
+    pub fn reusing(mut self, buffers: Option<&Buffers<V>>) -> Self {
+        if let Some(buffers) = buffers {
+            let mut heads = buffers.take_indices();
+            heads.resize(self.heads.len(), EMPTY);
+            self.heads = heads;
+            self.links = buffers.take_indices();
+            if let Samples::Full(ref mut samples) = self.samples {
+                *samples = buffers.take_samples();
+            }
+            self.buffers = Some(buffers.clone());
+        }
+        self
+    }
+
+    #[inline]
+    pub fn get(&self, index: V) -> Option<Cell<'_, F, V>> {
+        encode(&index, self.side, self.domain).map(|t| Cell {
+            grid: self,
+            cur: self.heads[t],
+        })
     }
 
-    pub fn get_mut(&mut self, index: V) -> Option<&mut Vec<V>> {
-        encode(&index, self.side, self.poisson_type).map(move |t| &mut self.data[t])
+    /// Inserts sample to the cell or gives it back if the cell is outside of the grid.
+    pub fn insert(&mut self, index: V, sample: V) -> Result<(), V> {
+        match encode(&index, self.side, self.domain) {
+            Some(t) => {
+                match self.samples {
+                    Samples::Full(ref mut samples) => samples.push(sample),
+                    Samples::Single(ref mut samples) => {
+                        samples.extend((0..V::dimension()).map(|n| {
+                            sample[n]
+                                .to_f32()
+                                .expect("Coordinate should be castable to f32.")
+                        }))
+                    }
+                    #[cfg(feature = "half")]
+                    Samples::Half(ref mut samples) => {
+                        samples.extend((0..V::dimension()).map(|n| {
+                            half::f16::from_f64(
+                                sample[n]
+                                    .to_f64()
+                                    .expect("Coordinate should be castable to f64."),
+                            )
+                        }))
+                    }
+                }
+                self.links.push(self.heads[t]);
+                self.heads[t] = self.links.len() - 1;
+                Ok(())
+            }
+            None => Err(sample),
+        }
+    }
+
+    /// Returns the sample from the buffer widened back to the full precision.
+    #[inline]
+    fn sample(&self, i: usize) -> V {
+        let dim = V::dimension();
+        match self.samples {
+            Samples::Full(ref samples) => samples[i].clone(),
+            Samples::Single(ref samples) => {
+                let mut v = V::zero();
+                for n in 0..dim {
+                    v[n] = NumCast::from(samples[i * dim + n])
+                        .expect("f32 should be castable to float.");
+                }
+                v
+            }
+            #[cfg(feature = "half")]
+            Samples::Half(ref samples) => {
+                let mut v = V::zero();
+                for n in 0..dim {
+                    v[n] = NumCast::from(samples[i * dim + n].to_f32())
+                        .expect("f32 should be castable to float.");
+                }
+                v
+            }
+        }
+    }
+
+    /// Collects the samples from the cells close enough to the cell to conflict with it, which are the cells
+    /// at most ⌈√d⌉ cells away along each axis unless the cells are scaled.
+    #[inline]
+    pub fn neighbours(&self, index: V) -> Neighbours<V> {
+        each_combination(&self.reach)
+            .filter_map(|t| self.get(index.clone() + t))
+            .flatten()
+            .collect()
     }
 
     pub fn cells(&self) -> usize {
-        self.data.len()
+        self.heads.len()
     }
 
     pub fn side(&self) -> usize {
@@ -72,54 +204,116 @@ where
     pub fn cell(&self) -> F {
         self.cell
     }
+
+    /// Returns the distance from a position within which every sample is among the neighbours of its cell.
+    pub fn reached(&self) -> F {
+        self.reached
+    }
+}
+
+impl<F, V> Drop for Grid<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    fn drop(&mut self) {
+        if let Some(buffers) = self.buffers.take() {
+            buffers.give_indices(std::mem::take(&mut self.heads));
+            buffers.give_indices(std::mem::take(&mut self.links));
+            if let Samples::Full(ref mut samples) = self.samples {
+                buffers.give_samples(std::mem::take(samples));
+            }
+        }
+    }
+}
+
+/// Iterator over the samples of a cell in the grid.
+pub struct Cell<'a, F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    grid: &'a Grid<F, V>,
+    cur: usize,
+}
+
+impl<F, V> Cell<'_, F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    pub fn is_empty(&self) -> bool {
+        self.cur == EMPTY
+    }
 }
 
-pub fn encode<F, V>(v: &V, side: usize, poisson_type: Type) -> Option<usize>
+impl<F, V> Iterator for Cell<'_, F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    type Item = V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cur == EMPTY {
+            return None;
+        }
+        let sample = self.grid.sample(self.cur);
+        self.cur = self.grid.links[self.cur];
+        Some(sample)
+    }
+}
+
+/// Encodes the index vector of a cell to its position in the grid with `side` cells along the unit.
+/// Axes shorter than the unit have fewer cells along them.
+#[inline]
+pub fn encode<F, V>(v: &V, side: usize, domain: Domain) -> Option<usize>
 where
     F: Float,
     V: Vector<F>,
 {
-    use crate::Type::*;
     let mut index = 0;
-    for n in 0..V::dimension() {
-        let n = v[n];
-        let cur = match poisson_type {
-            Perioditic => n
-                .to_isize()
+    for axis in 0..V::dimension() {
+        let n = v[axis];
+        let cells = domain.cells(axis, side);
+        let cur = if domain.is_perioditic(axis) {
+            n.to_isize()
                 .expect(
                     "Expected that all scalars of the index vector should be castable to \
                      isize.",
                 )
-                .modulo(side as isize) as usize,
-            Normal => {
-                if n < F::cast(0) || n >= F::cast(side) {
-                    return None;
-                }
-                n.to_usize().expect(
-                    "Expected that all scalars of the index vector should be castable to \
-                     usize.",
-                )
+                .modulo(cells as isize) as usize
+        } else {
+            if n < F::cast(0) || n >= F::cast(cells) {
+                return None;
             }
+            n.to_usize().expect(
+                "Expected that all scalars of the index vector should be castable to \
+                 usize.",
+            )
         };
-        index = (index + cur) * side;
+        index = index * cells + cur;
     }
-    Some(index / side)
+    Some(index)
 }
 
-pub fn decode<F, V>(index: usize, side: usize) -> Option<V>
+/// Decodes the position of a cell in the grid with `side` cells along the unit to its index vector.
+pub fn decode<F, V>(index: usize, side: usize, domain: Domain) -> Option<V>
 where
     F: Float,
     V: Vector<F>,
 {
     let dim = V::dimension();
-    if index >= side.pow(dim as u32) {
+    if index >= (0..dim).map(|n| domain.cells(n, side)).product() {
         return None;
     }
     let mut result = V::zero();
     let mut last = index;
     for n in (0..V::dimension()).rev() {
-        let cur = last / side;
-        result[n] = F::cast(last - cur * side);
+        let cells = domain.cells(n, side);
+        let cur = last / cells;
+        result[n] = F::cast(last - cur * cells);
         last = cur;
     }
     Some(result)
@@ -130,7 +324,7 @@ fn encoding_decoding_works() {
     let n = nalgebra::Vector2::new(10., 7.);
     assert_eq!(
         n,
-        decode::<_, nalgebra::Vector2<_>>(encode(&n, 15, Type::Normal).expect("Test vector should encode properly"), 15).expect("Encoded value should decode properly")
+        decode::<_, nalgebra::Vector2<_>>(encode(&n, 15, Domain::open()).expect("Test vector should encode properly"), 15, Domain::open()).expect("Encoded value should decode properly")
     );
 }
 
@@ -139,21 +333,21 @@ fn encoding_decoding_at_edge_works() {
     let n = nalgebra::Vector2::new(14., 14.);
     assert_eq!(
         n,
-        decode::<_, nalgebra::Vector2<_>>(encode(&n, 15, Type::Normal).expect("Test vector should encode properly"), 15).expect("Encoded value should decode properly")
+        decode::<_, nalgebra::Vector2<_>>(encode(&n, 15, Domain::open()).expect("Test vector should encode properly"), 15, Domain::open()).expect("Encoded value should decode properly")
     );
 }
 
 #[test]
 fn encoding_outside_of_area_fails() {
     let n = nalgebra::Vector2::new(9., 7.);
-    assert_eq!(None, encode(&n, 9, Type::Normal));
+    assert_eq!(None, encode(&n, 9, Domain::open()));
     let n = nalgebra::Vector2::new(7., 9.);
-    assert_eq!(None, encode(&n, 9, Type::Normal));
+    assert_eq!(None, encode(&n, 9, Domain::open()));
 }
 
 #[test]
 fn decoding_outside_of_area_fails() {
-    assert_eq!(None, decode::<f64, nalgebra::Vector2<_>>(100, 10));
+    assert_eq!(None, decode::<f64, nalgebra::Vector2<_>>(100, 10, Domain::open()));
 }
 
 pub fn choose_random_sample<F, V, R>(rng: &mut R, grid: &Grid<F, V>, index: V, level: usize) -> V
@@ -174,7 +368,7 @@ fn random_point_is_between_right_values_top_lvl() {
     use rand::{rngs::SmallRng, SeedableRng};
     let mut rand = SmallRng::from_seed([1; 32]); // range from 1 to 32
     let radius = 0.2;
-    let grid = Grid::<f64, nalgebra::Vector2<_>>::new(radius, Type::Normal);
+    let grid = Grid::<f64, nalgebra::Vector2<_>>::new(radius, Domain::open(), Precision::Full);
     for _ in 0..1000 {
         let result = choose_random_sample(&mut rand, &grid, nalgebra::Vector2::<f64>::zero(), 0);
         assert!(result.x >= 0.);
@@ -184,6 +378,7 @@ fn random_point_is_between_right_values_top_lvl() {
     }
 }
 
+#[inline]
 pub fn sample_to_index<F, V>(value: &V, side: usize) -> V
 where
     F: Float,
@@ -208,6 +403,7 @@ where
     cur
 }
 
+#[inline]
 pub fn is_disk_free<F, V>(
     grid: &Grid<F, V>,
     poisson: &Builder<F, V>,
@@ -220,42 +416,246 @@ where
     F: Float,
     V: Vector<F>,
 {
+    if !poisson.inside_shape(&sample) || poisson.blocking(&sample).is_some() {
+        return false;
+    }
     let parent = get_parent(index, level);
-    let sqradius = NumFloat::powi(F::cast(2) * poisson.radius, 2);
     // NOTE: This does unnessary checks for corners, but it doesn't affect much in higher dimensions: 5^d vs 5^d - 2d
-    each_combination(&[-2, -1, 0, 1, 2])
+    let free = each_combination(&grid.reach)
         .filter_map(|t| grid.get(parent.clone() + t))
         .flatten()
-        .all(|v| sqdist(v.clone(), sample.clone(), poisson.poisson_type) >= sqradius)
-        && is_valid(poisson, outside, sample)
+        .all(|v| !conflicts(poisson, &v, &sample))
+        && is_valid(poisson, outside, sample.clone());
+    if !free || poisson.checks.is_empty() {
+        return free;
+    }
+    let mut near = grid.neighbours(parent);
+    near.extend(outside.iter().cloned());
+    poisson.failed_check(&sample, &near).is_none()
 }
 
-pub fn is_valid<F, V>(poisson: &Builder<F, V>, samples: &[V], sample: V) -> bool
+/// Explains why `is_disk_free` rejects the sample, reporting the closest conflicting sample.
+pub fn explain_disk<F, V>(
+    grid: &Grid<F, V>,
+    poisson: &Builder<F, V>,
+    index: V,
+    sample: V,
+    outside: &[V],
+) -> Option<Rejection<F, V>>
 where
     F: Float,
     V: Vector<F>,
 {
-    let sqradius = NumFloat::powi(F::cast(2) * poisson.radius, 2);
+    if !poisson.inside_shape(&sample) {
+        return Some(Rejection::OutsideShape);
+    }
+    if let Some(rejection) = explain_blocking(poisson, &sample) {
+        return Some(rejection);
+    }
+    let mut near = grid.neighbours(index);
+    near.extend(outside.iter().cloned());
+    explain_valid(poisson, &near, sample.clone()).or_else(|| explain_check(poisson, &near, &sample))
+}
+
+/// Explains which custom legality check rejects the sample if any.
+pub fn explain_check<F, V>(poisson: &Builder<F, V>, samples: &[V], sample: &V) -> Option<Rejection<F, V>>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    poisson
+        .failed_check(sample, samples)
+        .map(|check| Rejection::Illegal { check })
+}
+
+/// Explains which dynamic blocker or segment obstacle the sample overlaps if any.
+pub fn explain_blocking<F, V>(poisson: &Builder<F, V>, sample: &V) -> Option<Rejection<F, V>>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    poisson
+        .blocking(sample)
+        .map(|(position, distance)| Rejection::Blocked { position, distance })
+}
+
+/// Explains why `is_valid` rejects the sample, reporting the closest conflicting sample.
+pub fn explain_valid<F, V>(
+    poisson: &Builder<F, V>,
+    samples: &[V],
+    sample: V,
+) -> Option<Rejection<F, V>>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    // Samples restricted with clearance conflict farther than the other samples.
+    let cleared = poisson
+        .clearing(&sample)
+        .map(|(t, d)| (NumFloat::powi(d, 2), t));
     samples
         .iter()
-        .all(|t| sqdist(t.clone(), sample.clone(), poisson.poisson_type) >= sqradius)
+        .filter(|t| conflicts(poisson, t, &sample))
+        .map(|t| (sqdist(t.clone(), sample.clone(), poisson.domain), t))
+        .chain(cleared)
+        .fold(None, |closest: Option<(F, &V)>, (d, t)| match closest {
+            Some((c, _)) if c <= d => closest,
+            _ => Some((d, t)),
+        })
+        .map(|(d, t)| Rejection::Conflict {
+            sample: t.clone(),
+            distance: NumFloat::sqrt(d),
+        })
 }
 
-pub fn sqdist<F, V>(v1: V, v2: V, poisson_type: Type) -> F
+#[inline]
+pub fn is_valid<F, V>(poisson: &Builder<F, V>, samples: &[V], sample: V) -> bool
 where
     F: Float,
     V: Vector<F>,
 {
-    use crate::Type::*;
-    let diff = v2 - v1;
-    match poisson_type {
-        Perioditic => each_combination(&[-1, 0, 1])
-            .map(|v| (diff.clone() + v).norm_squared())
-            .fold(NumFloat::max_value(), |a, b| NumFloat::min(a, b)),
-        Normal => diff.norm_squared(),
+    poisson.clearing(&sample).is_none() && samples.iter().all(|t| !conflicts(poisson, t, &sample))
+}
+
+/// Checks if the samples are too close to each other for the distribution.
+/// With regions the distance depends on the radii of the regions the samples are in.
+/// With anisotropy the samples conflict if either of them is inside the exclusion zone of the other.
+/// With radii for each axis the difference is scaled along each axis before comparing it.
+#[inline]
+pub fn conflicts<F, V>(poisson: &Builder<F, V>, v1: &V, v2: &V) -> bool
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let diameter = match poisson.regions {
+        None => F::cast(2) * poisson.radius,
+        Some(ref regions) => regions.distance(v1, v2),
+    };
+    let sqradius = NumFloat::powi(diameter, 2);
+    match poisson.anisotropy {
+        None if poisson.radii.is_some() => {
+            let radii = poisson.radii.as_ref().expect("Radii were checked to be set.");
+            let mut diff = closest_image(v1.clone(), v2.clone(), poisson.domain);
+            // Each axis is stretched so that the ellipsoid of the radii becomes the disk of the radius.
+            for n in 0..V::dimension() {
+                diff[n] *= poisson.radius / radii[n];
+            }
+            diff.norm_squared() < sqradius
+        }
+        None => {
+            let sqdist = sqdist(v1.clone(), v2.clone(), poisson.domain);
+            if poisson.exact && exact::is_near::<F, V>(sqdist, sqradius) {
+                !exact::is_farther(v1, v2, diameter, poisson.domain)
+            } else {
+                sqdist < sqradius
+            }
+        }
+        Some(ref a) => {
+            sqdist_from(a, v1, v2, poisson.domain) < sqradius
+                || sqdist_from(a, v2, v1, poisson.domain) < sqradius
+        }
     }
 }
 
+/// Squared distance between samples scaled by the exclusion zone of the first one.
+pub fn sqdist_from<F, V>(anisotropy: &Anisotropy<F, V>, from: &V, to: &V, domain: Domain) -> F
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let diff = to.clone() - from.clone();
+    if (0..V::dimension()).all(|n| !domain.is_perioditic(n)) {
+        return anisotropy.sqdist(from, diff);
+    }
+    // Scaling doesn't preserve the closest image along each axis so every image across perioditic axes is tried.
+    each_combination(&[-1, 0, 1])
+        .filter(|v: &V| (0..V::dimension()).all(|n| domain.is_perioditic(n) || v[n] == F::cast(0)))
+        .map(|mut v: V| {
+            for n in 0..V::dimension() {
+                v[n] *= domain.extent_of::<F>(n);
+            }
+            anisotropy.sqdist(from, diff.clone() + v)
+        })
+        .fold(NumFloat::max_value(), |a, b| NumFloat::min(a, b))
+}
+
+#[inline]
+pub fn sqdist<F, V>(v1: V, v2: V, domain: Domain) -> F
+where
+    F: Float,
+    V: Vector<F>,
+{
+    closest_image(v1, v2, domain).norm_squared()
+}
+
+/// Difference from the first sample to the closest image of the second one across perioditic axes.
+#[inline]
+pub fn closest_image<F, V>(v1: V, v2: V, domain: Domain) -> V
+where
+    F: Float,
+    V: Vector<F>,
+{
+    let mut diff = v2 - v1;
+    // Squared distance is sum over the axes so the closest image can be chosen for each axis separately
+    // instead of going through all 3^d images.
+    for n in 0..V::dimension() {
+        if !domain.is_perioditic(n) {
+            continue;
+        }
+        let extent = domain.extent_of::<F>(n);
+        let half = extent / F::cast(2);
+        if diff[n] > half {
+            diff[n] -= extent;
+        } else if diff[n] < -half {
+            diff[n] += extent;
+        }
+    }
+    diff
+}
+
+#[test]
+fn perioditic_sqdist_uses_closest_image() {
+    use rand::{rngs::SmallRng, SeedableRng};
+    let mut rng = SmallRng::seed_from_u64(3);
+    for _ in 0..1000 {
+        let v1: nalgebra::Vector3<f64> = rng.sample(StandardUniform);
+        let v2: nalgebra::Vector3<f64> = rng.sample(StandardUniform);
+        let expected = each_combination(&[-1, 0, 1])
+            .map(|t: nalgebra::Vector3<f64>| (v2 - v1 + t).norm_squared())
+            .fold(f64::MAX, f64::min);
+        assert_eq!(expected, sqdist(v1, v2, Domain::perioditic()));
+    }
+}
+
+/// Returns the amount of bits in the mantissa of the float.
+pub fn mantissa_digits<F>() -> usize
+where
+    F: Float,
+{
+    let (mantissa, _, _) = <F as NumFloat>::max_value().integer_decode();
+    mantissa.count_ones() as usize
+}
+
+/// Turns random bits into a float evenly distributed in [0, 1).
+/// Only as many bits are used as the float represents exactly so that the result never rounds up to 1.
+pub fn unit_from_bits<F>(bits: u64) -> F
+where
+    F: Float,
+{
+    let digits = mantissa_digits::<F>().min(63);
+    let scaled: F = NumCast::from(bits >> (64 - digits)).expect("u64 should be castable to float.");
+    scaled / NumFloat::powi(F::cast(2), digits as i32)
+}
+
+#[test]
+fn unit_from_bits_stays_below_one() {
+    assert_eq!(0., unit_from_bits::<f32>(0));
+    assert!(unit_from_bits::<f32>(u64::MAX) < 1.);
+    assert!(unit_from_bits::<f64>(u64::MAX) < 1.);
+    assert_eq!(0.5, unit_from_bits::<f64>(1 << 63));
+}
+
+#[inline]
 pub fn get_parent<F, V>(mut index: V, level: usize) -> V
 where
     F: Float,
@@ -300,6 +700,7 @@ where
     V: Vector<F>,
 {
     type Item = V;
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
         let dim = V::dimension();
         let len = self.choices.len();
@@ -374,5 +775,7 @@ fn mapping_inplace_works() {
     };
     result.flat_map_inplace(&func);
     let mut expected = vec.into_iter().flat_map(func).collect::<Vec<_>>();
-    assert_eq!(expected.sort(), result.sort());
+    expected.sort();
+    result.sort();
+    assert_eq!(expected, result);
 }
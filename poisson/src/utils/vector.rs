@@ -0,0 +1,296 @@
+//! Macro for implementing `Vector` for wrappers of arrays and of vector types of other crates.
+
+/// Implements `Vector` for a transparent wrapper of an array or a vector type with named coordinates and a `new` constructor.
+macro_rules! mirror {
+    ($(#[$attr:meta])* $name:ident([F; $n:literal]) { $($i:literal),+ }) => {
+        $crate::utils::vector::mirror! {
+            @impl [$(#[$attr])*] $name([F; $n])
+            zero: [F::zero(); $n];
+            { $($i => ([$i])),+ }
+        }
+    };
+    ($(#[$attr:meta])* $name:ident($inner:ty) { $($i:literal => $c:ident),+ }) => {
+        $crate::utils::vector::mirror! {
+            @impl [$(#[$attr])*] $name($inner)
+            zero: {
+                $(let $c = F::zero();)+
+                <$inner>::new($($c),+)
+            };
+            { $($i => (.$c)),+ }
+        }
+    };
+    (@impl [$(#[$attr:meta])*] $name:ident($inner:ty) zero: $zero:expr; { $($i:literal => ($($c:tt)+)),+ }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        #[repr(transparent)]
+        pub struct $name<F>(pub $inner);
+
+        const _: () = {
+            use $crate::Float;
+
+            use alga::general::{
+                AbstractGroup, AbstractGroupAbelian, AbstractLoop, AbstractMagma, AbstractModule, AbstractMonoid,
+                AbstractQuasigroup, AbstractSemigroup, Additive, Identity, Module, TwoSidedInverse,
+            };
+            use alga::linear::{FiniteDimVectorSpace, NormedSpace, VectorSpace};
+
+            use num_traits::Zero;
+
+            use rand::distr::StandardUniform;
+            use rand::Rng;
+            use rand_distr::Distribution;
+
+            use std::ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+
+            impl<F> $name<F> {
+                /// Reinterprets the vectors as mirrors without copying them.
+                pub fn from_slice(vectors: &[$inner]) -> &[Self] {
+                    // Because the mirror is transparent over the vector they have the same layout.
+                    unsafe { std::slice::from_raw_parts(vectors.as_ptr() as *const Self, vectors.len()) }
+                }
+
+                /// Reinterprets the mirrors as the vectors they wrap without copying them.
+                pub fn as_slice(mirrors: &[Self]) -> &[$inner] {
+                    // Because the mirror is transparent over the vector they have the same layout.
+                    unsafe { std::slice::from_raw_parts(mirrors.as_ptr() as *const $inner, mirrors.len()) }
+                }
+
+                /// Turns the mirrors into the vectors they wrap without copying them.
+                pub fn into_vec(mirrors: Vec<Self>) -> Vec<$inner> {
+                    let mut mirrors = std::mem::ManuallyDrop::new(mirrors);
+                    // Because the mirror is transparent over the vector they have the same layout and alignment.
+                    unsafe {
+                        Vec::from_raw_parts(mirrors.as_mut_ptr() as *mut $inner, mirrors.len(), mirrors.capacity())
+                    }
+                }
+            }
+
+            impl<F> From<$inner> for $name<F> {
+                fn from(vector: $inner) -> Self {
+                    $name(vector)
+                }
+            }
+
+            impl<F> From<$name<F>> for $inner {
+                fn from(mirror: $name<F>) -> Self {
+                    mirror.0
+                }
+            }
+
+            impl<F: Float> Index<usize> for $name<F> {
+                type Output = F;
+
+                fn index(&self, i: usize) -> &F {
+                    match i {
+                        $($i => &self.0 $($c)+,)+
+                        _ => panic!("Index {} is out of bounds of {} dimensional vector.", i, Self::dimension()),
+                    }
+                }
+            }
+
+            impl<F: Float> IndexMut<usize> for $name<F> {
+                fn index_mut(&mut self, i: usize) -> &mut F {
+                    match i {
+                        $($i => &mut self.0 $($c)+,)+
+                        _ => panic!("Index {} is out of bounds of {} dimensional vector.", i, Self::dimension()),
+                    }
+                }
+            }
+
+            impl<F: Float> Add for $name<F> {
+                type Output = Self;
+
+                fn add(mut self, other: Self) -> Self {
+                    self += other;
+                    self
+                }
+            }
+
+            impl<F: Float> AddAssign for $name<F> {
+                fn add_assign(&mut self, other: Self) {
+                    $(self.0 $($c)+ += other.0 $($c)+;)+
+                }
+            }
+
+            impl<F: Float> Sub for $name<F> {
+                type Output = Self;
+
+                fn sub(mut self, other: Self) -> Self {
+                    self -= other;
+                    self
+                }
+            }
+
+            impl<F: Float> SubAssign for $name<F> {
+                fn sub_assign(&mut self, other: Self) {
+                    $(self.0 $($c)+ -= other.0 $($c)+;)+
+                }
+            }
+
+            impl<F: Float> Neg for $name<F> {
+                type Output = Self;
+
+                fn neg(mut self) -> Self {
+                    $(self.0 $($c)+ = -self.0 $($c)+;)+
+                    self
+                }
+            }
+
+            impl<F: Float> Mul<F> for $name<F> {
+                type Output = Self;
+
+                fn mul(mut self, s: F) -> Self {
+                    self *= s;
+                    self
+                }
+            }
+
+            impl<F: Float> MulAssign<F> for $name<F> {
+                fn mul_assign(&mut self, s: F) {
+                    $(self.0 $($c)+ *= s;)+
+                }
+            }
+
+            impl<F: Float> Div<F> for $name<F> {
+                type Output = Self;
+
+                fn div(mut self, s: F) -> Self {
+                    self /= s;
+                    self
+                }
+            }
+
+            impl<F: Float> DivAssign<F> for $name<F> {
+                fn div_assign(&mut self, s: F) {
+                    $(self.0 $($c)+ /= s;)+
+                }
+            }
+
+            impl<F: Float> Zero for $name<F> {
+                fn zero() -> Self {
+                    $name($zero)
+                }
+
+                fn is_zero(&self) -> bool {
+                    true $(&& self.0 $($c)+.is_zero())+
+                }
+            }
+
+            impl<F> Distribution<$name<F>> for StandardUniform
+            where
+                F: Float,
+                StandardUniform: Distribution<F>,
+            {
+                fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $name<F> {
+                    let mut sample = $name::zero();
+                    $(sample.0 $($c)+ = rng.random();)+
+                    sample
+                }
+            }
+
+            impl<F: Float> AbstractMagma<Additive> for $name<F> {
+                fn operate(&self, right: &Self) -> Self {
+                    *self + *right
+                }
+            }
+
+            impl<F: Float> TwoSidedInverse<Additive> for $name<F> {
+                fn two_sided_inverse(&self) -> Self {
+                    -*self
+                }
+            }
+
+            impl<F: Float> Identity<Additive> for $name<F> {
+                fn identity() -> Self {
+                    Self::zero()
+                }
+            }
+
+            impl<F: Float> AbstractQuasigroup<Additive> for $name<F> {}
+            impl<F: Float> AbstractSemigroup<Additive> for $name<F> {}
+            impl<F: Float> AbstractLoop<Additive> for $name<F> {}
+            impl<F: Float> AbstractMonoid<Additive> for $name<F> {}
+            impl<F: Float> AbstractGroup<Additive> for $name<F> {}
+            impl<F: Float> AbstractGroupAbelian<Additive> for $name<F> {}
+
+            impl<F: Float> AbstractModule for $name<F> {
+                type AbstractRing = F;
+
+                fn multiply_by(&self, r: F) -> Self {
+                    *self * r
+                }
+            }
+
+            impl<F: Float> Module for $name<F> {
+                type Ring = F;
+            }
+
+            impl<F: Float> VectorSpace for $name<F> {
+                type Field = F;
+            }
+
+            impl<F: Float> NormedSpace for $name<F> {
+                type RealField = F;
+                type ComplexField = F;
+
+                fn norm_squared(&self) -> F {
+                    self.dot(self)
+                }
+
+                fn norm(&self) -> F {
+                    num_traits::Float::sqrt(self.norm_squared())
+                }
+
+                fn normalize(&self) -> Self {
+                    *self / self.norm()
+                }
+
+                fn normalize_mut(&mut self) -> F {
+                    let norm = self.norm();
+                    *self /= norm;
+                    norm
+                }
+
+                fn try_normalize(&self, eps: F) -> Option<Self> {
+                    let norm = self.norm();
+                    if norm <= eps { None } else { Some(*self / norm) }
+                }
+
+                fn try_normalize_mut(&mut self, eps: F) -> Option<F> {
+                    let norm = self.norm();
+                    if norm <= eps {
+                        None
+                    } else {
+                        *self /= norm;
+                        Some(norm)
+                    }
+                }
+            }
+
+            impl<F: Float> FiniteDimVectorSpace for $name<F> {
+                fn dimension() -> usize {
+                    [$($i),+].len()
+                }
+
+                fn canonical_basis_element(i: usize) -> Self {
+                    let mut basis = Self::zero();
+                    basis[i] = F::one();
+                    basis
+                }
+
+                fn dot(&self, other: &Self) -> F {
+                    F::zero() $(+ self.0 $($c)+ * other.0 $($c)+)+
+                }
+
+                unsafe fn component_unchecked(&self, i: usize) -> &F {
+                    &self[i]
+                }
+
+                unsafe fn component_unchecked_mut(&mut self, i: usize) -> &mut F {
+                    &mut self[i]
+                }
+            }
+        };
+    };
+}
+pub(crate) use mirror;
@@ -0,0 +1,102 @@
+//! Module that contains checks for the correctness of poisson-disk distributions.
+//!
+//! They are meant for the test suites of crates that post-process the distributions, to assert that the processing
+//! keeps them legal.
+
+use crate::utils::{each_combination, encode, sample_to_index, sqdist};
+use crate::{Domain, Float, Vector};
+
+use num_traits::{Float as NumFloat, NumCast};
+
+use std::collections::HashMap;
+
+/// Pair of samples closer to each other than the diameter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Violation<F> {
+    /// Index of the first sample of the pair.
+    pub first: usize,
+    /// Index of the second sample of the pair, which is larger than the first one.
+    pub second: usize,
+    /// Distance between the samples, wrapping around the perioditic axes of the domain.
+    pub distance: F,
+}
+
+/// Checks that no two samples of the set are closer to each other than twice the radius in the domain,
+/// returning the violation with the smallest indices if there is one.
+pub fn verify_separation<F, V, D>(set: &[V], radius: F, domain: D) -> Result<(), Violation<F>>
+where
+    F: Float,
+    V: Vector<F>,
+    D: Into<Domain>,
+{
+    match violations(set, radius, domain).into_iter().next() {
+        Some(violation) => Err(violation),
+        None => Ok(()),
+    }
+}
+
+/// Returns every pair of samples of the set closer to each other than twice the radius in the domain,
+/// ordered by their indices.
+pub fn violations<F, V, D>(set: &[V], radius: F, domain: D) -> Vec<Violation<F>>
+where
+    F: Float,
+    V: Vector<F>,
+    D: Into<Domain>,
+{
+    assert!(F::cast(0) < radius);
+    let domain = domain.into();
+    let diameter = F::cast(2) * radius;
+    // Cells are as wide as the diameter so conflicting samples are in the same or adjacent cells in any dimension.
+    let side = NumCast::from(NumFloat::floor(F::cast(1) / diameter))
+        .unwrap_or(usize::MAX)
+        .max(1);
+    let mut cells = HashMap::<usize, Vec<usize>>::new();
+    let mut outside = vec![];
+    for (i, v) in set.iter().enumerate() {
+        match encode(&sample_to_index(v, side), side, domain) {
+            Some(cell) => cells.entry(cell).or_default().push(i),
+            None => outside.push(i),
+        }
+    }
+    let reach = 1 + domain.has_short_seam(V::dimension(), side) as i64;
+    let offsets = (-reach..=reach).collect::<Vec<_>>();
+    let mut found = vec![];
+    let mut check = |a: usize, b: usize| {
+        let (first, second) = (a.min(b), a.max(b));
+        let distance = NumFloat::sqrt(sqdist(set[first].clone(), set[second].clone(), domain));
+        if distance < diameter {
+            found.push(Violation {
+                first,
+                second,
+                distance,
+            });
+        }
+    };
+    for (i, v) in set.iter().enumerate() {
+        if outside.contains(&i) {
+            continue;
+        }
+        let index = sample_to_index(v, side);
+        // Few cells wrapping around a perioditic axis can be adjacent from both sides.
+        let mut around = each_combination(&offsets)
+            .filter_map(|t: V| encode(&(index.clone() + t), side, domain))
+            .collect::<Vec<_>>();
+        around.sort_unstable();
+        around.dedup();
+        for j in around.iter().filter_map(|c| cells.get(c)).flatten() {
+            if i < *j {
+                check(i, *j);
+            }
+        }
+    }
+    // Samples outside of the domain along open axes aren't in any cell so they are checked against every sample.
+    for &i in &outside {
+        for j in 0..set.len() {
+            if i != j && (j > i || !outside.contains(&j)) {
+                check(i, j);
+            }
+        }
+    }
+    found.sort_by_key(|v| (v.first, v.second));
+    found
+}
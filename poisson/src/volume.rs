@@ -0,0 +1,221 @@
+//! Module that contains lazy sampling of unbounded space in chunks.
+
+use crate::{algorithm, Builder, Domain, Float, SeedTree, Vector};
+
+use num_traits::{Float as NumFloat, NumCast};
+
+use rand::distr::StandardUniform;
+use rand::rngs::StdRng;
+use rand_distr::Distribution;
+
+use std::collections::HashMap;
+
+/// Poisson-disk distribution over unbounded space that is generated only in the chunks that are queried,
+/// such as placing ores and caves in the voxels of a world as they are loaded.
+///
+/// Space is split into cubic chunks and the chunks into elements: the halos, which are bands one radius wide
+/// on each side of the faces between chunks, their edges and corners where the bands cross, and the interiors of
+/// the chunks. Each element is generated once with `algorithm::Bridson` and a seed derived from its coordinates,
+/// and the elements are generated in a fixed order from the corners to the interiors with the samples of
+/// the elements before them restricting them. The samples near the faces of chunks are therefore generated once and
+/// shared by both of the chunks, so the samples are legal and as dense across the faces as inside the chunks,
+/// and they don't depend on the order or the regions they are queried in.
+/// Generated elements are kept until `clear` is called.
+///
+/// ````rust
+/// # use poisson::VolumeSampler;
+/// # use nalgebra as na;
+/// type Vec3 = na::Vector3<f64>;
+/// let mut sampler = VolumeSampler::<f64, Vec3>::new(2., 16., 7);
+/// let ores = sampler.points_in_aabb(Vec3::new(-8., -8., -8.), Vec3::new(8., 8., 8.));
+/// assert!(!ores.is_empty());
+/// assert!(ores.iter().all(|p| p.iter().all(|&c| -8. <= c && c < 8.)));
+/// ````
+#[derive(Clone, Debug)]
+pub struct VolumeSampler<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+{
+    radius: F,
+    chunk: F,
+    tree: SeedTree,
+    elements: HashMap<(Vec<i64>, usize), Vec<V>>,
+}
+
+impl<F, V> VolumeSampler<F, V>
+where
+    F: Float,
+    V: Vector<F>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    /// Creates sampler with the radius of the disks and the length of the edges of the chunks in world units.
+    /// The chunks have to be at least four times as long as the radius so that the halos fit into them.
+    pub fn new(radius: F, chunk: F, seed: u64) -> Self {
+        assert!(F::cast(0) < radius);
+        assert!(F::cast(4) * radius <= chunk);
+        VolumeSampler {
+            radius,
+            chunk,
+            tree: SeedTree::new(seed),
+            elements: HashMap::new(),
+        }
+    }
+
+    /// Returns the radius of the disks in world units.
+    pub fn radius(&self) -> F {
+        self.radius
+    }
+
+    /// Returns the length of the edges of the chunks in world units.
+    pub fn chunk(&self) -> F {
+        self.chunk
+    }
+
+    /// Returns the samples in the box from `min` inclusive to `max` exclusive, generating the elements it overlaps.
+    /// The samples are ordered by element so the same box always gives the same samples in the same order.
+    pub fn points_in_aabb(&mut self, min: V, max: V) -> Vec<V> {
+        let dim = V::dimension();
+        let mut result = vec![];
+        if (0..dim).any(|n| max[n] <= min[n]) {
+            return result;
+        }
+        // Halos around the face at the far side of the last chunk can reach back into the box.
+        let low = (0..dim).map(|n| self.chunk_of(min[n])).collect::<Vec<_>>();
+        let high = (0..dim).map(|n| self.chunk_of(max[n]) + 1).collect::<Vec<_>>();
+        let inside = |p: &V| (0..dim).all(|n| min[n] <= p[n] && p[n] < max[n]);
+        let mut coords = low.clone();
+        loop {
+            for mask in order(dim) {
+                let (lo, hi) = self.bounds(&coords, mask);
+                if (0..dim).all(|n| lo[n] < max[n] && min[n] < hi[n]) {
+                    let samples = self.generate(&coords, mask);
+                    result.extend(samples.iter().filter(|p| inside(p)).cloned());
+                }
+            }
+            // Advances to the next chunk like an odometer.
+            let mut n = 0;
+            while n < dim && coords[n] == high[n] {
+                coords[n] = low[n];
+                n += 1;
+            }
+            if n == dim {
+                return result;
+            }
+            coords[n] += 1;
+        }
+    }
+
+    /// Forgets the generated elements to free their memory. They are generated again the same way when queried.
+    pub fn clear(&mut self) {
+        self.elements.clear();
+    }
+
+    fn chunk_of(&self, coordinate: F) -> i64 {
+        NumFloat::floor(coordinate / self.chunk)
+            .to_i64()
+            .expect("Expected that the coordinate would be in range of chunks.")
+    }
+
+    /// Returns the box of the element as its lower corner inclusive and upper corner exclusive.
+    /// Along the axes in the mask the element is the halo around the face at the start of the chunk,
+    /// and along the other axes it's the part of the chunk between the halos.
+    fn bounds(&self, coords: &[i64], mask: usize) -> (V, V) {
+        let (mut lo, mut hi) = (V::zero(), V::zero());
+        for (n, &c) in coords.iter().enumerate() {
+            let c: F = NumCast::from(c).expect("Chunk coordinates should be castable to float.");
+            let face = c * self.chunk;
+            if mask & (1 << n) != 0 {
+                lo[n] = face - self.radius;
+                hi[n] = face + self.radius;
+            } else {
+                lo[n] = face + self.radius;
+                hi[n] = face + self.chunk - self.radius;
+            }
+        }
+        (lo, hi)
+    }
+
+    /// Generates the samples of the element if they haven't been generated yet,
+    /// first generating the elements before it in the order that are close enough to restrict it.
+    fn generate(&mut self, coords: &[i64], mask: usize) -> &[V] {
+        let key = (coords.to_vec(), mask);
+        if !self.elements.contains_key(&key) {
+            let dim = V::dimension();
+            let diameter = F::cast(2) * self.radius;
+            let (lo, hi) = self.bounds(coords, mask);
+            let gap = |p: &V| {
+                let sqdist = (0..dim)
+                    .map(|n| {
+                        let d = NumFloat::max(lo[n] - p[n], p[n] - hi[n]);
+                        NumFloat::powi(NumFloat::max(d, F::cast(0)), 2)
+                    })
+                    .fold(F::cast(0), |a, b| a + b);
+                NumFloat::sqrt(sqdist)
+            };
+            let mut restrictions = vec![];
+            for earlier in order(dim).take_while(|&m| m != mask) {
+                for i in 0..3usize.pow(dim as u32) {
+                    let neighbour = (0..dim)
+                        .map(|n| coords[n] + (i / 3usize.pow(n as u32) % 3) as i64 - 1)
+                        .collect::<Vec<_>>();
+                    let (nlo, nhi) = self.bounds(&neighbour, earlier);
+                    // Boxes that are a diameter apart can't have samples that conflict.
+                    let apart = (0..dim)
+                        .any(|n| nlo[n] - hi[n] >= diameter || lo[n] - nhi[n] >= diameter);
+                    if !apart {
+                        let samples = self.generate(&neighbour, earlier);
+                        restrictions.extend(samples.iter().filter(|p| gap(p) < diameter).cloned());
+                    }
+                }
+            }
+            let tree = coords
+                .iter()
+                .fold(self.tree, |tree, &c| tree.child(c as u64))
+                .child(mask as u64);
+            // The element is generated in the unit domain scaled down by its longest side.
+            let longest = (0..dim)
+                .map(|n| hi[n] - lo[n])
+                .fold(F::cast(0), NumFloat::max);
+            let mut domain = Domain::open();
+            for n in 0..dim {
+                let extent = ((hi[n] - lo[n]) / longest)
+                    .to_f64()
+                    .expect("Extent should be castable to f64.");
+                if extent < 1. {
+                    domain = domain.with_extent(n, extent);
+                }
+            }
+            // Bridson grows the element from the restricting samples, which reach into it from every side.
+            let scale = F::cast(1) / longest;
+            let start = restrictions
+                .iter()
+                .map(|p| (p.clone() - lo.clone()) * scale)
+                .collect::<Vec<_>>();
+            let points = Builder::<F, V>::with_disk_radius(self.radius * scale, domain)
+                .with_start_points(&start, false)
+                .build(tree.rng::<StdRng>(), algorithm::Bridson)
+                .into_iter()
+                .map(|p| lo.clone() + p * longest)
+                // Scaling can round samples a hair closer to the restricting samples than the diameter.
+                .filter(|p| {
+                    let sqdiameter = diameter * diameter;
+                    restrictions
+                        .iter()
+                        .all(|q| (q.clone() - p.clone()).norm_squared() >= sqdiameter)
+                })
+                .collect();
+            self.elements.insert(key.clone(), points);
+        }
+        &self.elements[&key]
+    }
+}
+
+/// Masks of the elements in the order they are generated, from the corners where the halos of every axis cross
+/// to the interiors of the chunks. Elements with the same mask are at least a diameter apart from each other.
+fn order(dim: usize) -> impl Iterator<Item = usize> {
+    let mut masks = (0..1usize << dim).collect::<Vec<_>>();
+    masks.sort_by_key(|&m| (std::cmp::Reverse(m.count_ones()), m));
+    masks.into_iter()
+}
@@ -1,3 +1,4 @@
+#![cfg(feature = "full")]
 use poisson::{algorithm, Builder, Type};
 
 use rand::{rngs::SmallRng, SeedableRng};
@@ -37,6 +38,7 @@ fn adding_valid_start_works() {
 }
 
 #[test]
+#[allow(clippy::manual_repeat_n)]
 fn adding_valid_middle_works() {
     let samples = 100;
     let relative_radius = 0.8;
@@ -0,0 +1,87 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+const ALONG: f64 = 0.5;
+const ACROSS: f64 = 1.;
+
+fn flow(_: &Vect) -> Vect {
+    Vect::new(1., 0.)
+}
+
+/// Squared distance scaled by the exclusion zone of a horizontal flow.
+fn scaled(a: &Vect, b: &Vect, ptype: Type) -> f64 {
+    let offsets: &[f64] = match ptype {
+        Type::Normal => &[0.],
+        Type::Perioditic => &[-1., 0., 1.],
+    };
+    let d = b - a;
+    let mut min = f64::MAX;
+    for &x in offsets {
+        for &y in offsets {
+            let dx = (d.x + x) / ALONG;
+            let dy = (d.y + y) / ACROSS;
+            min = min.min(dx * dx + dy * dy);
+        }
+    }
+    min
+}
+
+fn check<A>(ptype: Type, algo: A)
+where
+    A: algorithm::Creator<f64, Vect> + Copy,
+{
+    for seed in 0..20 {
+        let builder =
+            Builder::<_, Vect>::with_disk_radius(0.05, ptype).with_flow_field(flow, ALONG, ACROSS);
+        let radius = builder.radius();
+        let samples = builder
+            .build(SmallRng::seed_from_u64(seed), algo)
+            .generate();
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[i + 1..] {
+                assert!(scaled(a, b, ptype) >= (2. * radius) * (2. * radius));
+            }
+        }
+        // Samples should make use of the smaller exclusion zone along the flow.
+        let closest = samples
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| samples[i + 1..].iter().map(move |b| (a - b).norm()))
+            .fold(f64::MAX, f64::min);
+        assert!(closest < 2. * radius);
+    }
+}
+
+#[test]
+fn anisotropic_ebeida_is_valid() {
+    check(Type::Normal, algorithm::Ebeida);
+    check(Type::Perioditic, algorithm::Ebeida);
+}
+
+#[test]
+fn anisotropic_bridson_is_valid() {
+    check(Type::Normal, algorithm::Bridson);
+    check(Type::Perioditic, algorithm::Bridson);
+}
+
+#[test]
+fn anisotropic_dart_throwing_is_valid() {
+    check(Type::Normal, algorithm::DartThrowing);
+    check(Type::Perioditic, algorithm::DartThrowing);
+}
+
+#[test]
+fn anisotropy_is_kept_by_builder() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal);
+    assert!(builder.anisotropy().is_none());
+    let builder = builder.with_flow_field(flow, ALONG, ACROSS);
+    let anisotropy = builder.anisotropy().unwrap();
+    assert_eq!(ALONG, anisotropy.along);
+    assert_eq!(ACROSS, anisotropy.across);
+    assert_eq!(builder.clone(), builder);
+}
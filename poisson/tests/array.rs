@@ -0,0 +1,25 @@
+#![cfg(feature = "full")]
+use poisson::array::{Array2, Array3, Array4};
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+#[test]
+fn array_vectors_generate_valid_distributions() {
+    let samples = Builder::<_, Array2<f32>>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert!(poisson::verify::verify_separation(&samples, 0.05, Type::Normal).is_ok());
+    let arrays: Vec<[f32; 2]> = Array2::into_vec(samples.clone());
+    assert_eq!(samples.as_slice(), Array2::from_slice(&arrays));
+    assert!(arrays.iter().flatten().all(|c| (0. ..1.).contains(c)));
+    let samples = Builder::<_, Array3<f64>>::with_disk_radius(0.1, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    assert!(poisson::verify::verify_separation(&samples, 0.1, Type::Perioditic).is_ok());
+    let samples = Builder::<_, Array4<f64>>::with_samples(100, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(3), algorithm::DartThrowing)
+        .generate_set();
+    let radius = samples.radius();
+    assert!(poisson::verify::verify_separation(samples.points(), radius, Type::Perioditic).is_ok());
+}
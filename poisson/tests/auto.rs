@@ -0,0 +1,63 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect2 = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+mod helper;
+
+#[test]
+fn auto_picks_bridson_by_default() {
+    let (set, stats) = Builder::<_, Vect2>::with_disk_radius(0.03, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Auto)
+        .generate_with_stats();
+    assert_eq!(Some("Bridson"), stats.algorithm);
+    assert_eq!(Some("Bridson"), set.algorithm());
+    helper::assert_legal_poisson(&set.into_points(), 0.03, algorithm::Auto);
+}
+
+#[test]
+fn auto_picks_ebeida_for_maximal() {
+    let builder = Builder::<_, Vect3>::with_disk_radius(0.1, Type::Perioditic).with_maximal(true);
+    assert!(builder.maximal());
+    let (set, stats) = builder
+        .build(SmallRng::seed_from_u64(2), algorithm::Auto)
+        .generate_with_stats();
+    assert_eq!(Some("Ebeida"), stats.algorithm);
+    assert_eq!(Some("Ebeida"), set.algorithm());
+    helper::assert_legal_poisson(&set.into_points(), 0.1, algorithm::Auto);
+}
+
+#[test]
+fn auto_generates_same_samples_as_picked_algorithm() {
+    let builder = Builder::<_, Vect2>::with_disk_radius(0.05, Type::Normal).with_maximal(true);
+    assert_eq!(
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+            .generate(),
+        builder
+            .build(SmallRng::seed_from_u64(3), algorithm::Auto)
+            .generate()
+    );
+}
+
+#[test]
+fn large_radius_is_reported_as_sparse() {
+    let (set, stats) = Builder::<_, Vect2>::with_disk_radius(0.4, Type::Normal)
+        .build(SmallRng::seed_from_u64(4), algorithm::Auto)
+        .generate_with_stats();
+    assert_eq!(Some("Sparse"), stats.algorithm);
+    assert_eq!(Some("Sparse"), set.algorithm());
+}
+
+#[test]
+fn explicit_algorithm_is_reported() {
+    let (_, stats) = Builder::<_, Vect2>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(5), algorithm::Hashed)
+        .generate_with_stats();
+    assert_eq!(Some("Hashed"), stats.algorithm);
+}
@@ -0,0 +1,188 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Budget, Builder, Rejection, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn assert_clear_of(points: &[Vect], blocker: Vect, reach: f64) {
+    for p in points {
+        assert!(
+            (p - blocker).norm() >= reach,
+            "{:?} overlaps blocker at {:?}",
+            p,
+            blocker
+        );
+    }
+}
+
+#[test]
+fn samples_keep_away_from_blockers() {
+    let radius = 0.02;
+    let blocker = Vect::new(0.5, 0.5);
+    let builder = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal);
+    let mut iters = (
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+            .into_iter(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+            .into_iter(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(1), algorithm::DartThrowing)
+            .into_iter(),
+        builder
+            .build(SmallRng::seed_from_u64(1), algorithm::Hashed)
+            .into_iter(),
+    );
+    iters.0.add_blocker(blocker, 0.1);
+    iters.1.add_blocker(blocker, 0.1);
+    iters.2.add_blocker(blocker, 0.1);
+    iters.3.add_blocker(blocker, 0.1);
+    let samples = [
+        iters.0.collect::<Vec<_>>(),
+        iters.1.collect::<Vec<_>>(),
+        iters.2.collect::<Vec<_>>(),
+        iters.3.collect::<Vec<_>>(),
+    ];
+    for points in &samples {
+        assert!(!points.is_empty());
+        assert_clear_of(points, blocker, 0.1 + radius);
+    }
+    // Radius this large is generated with the sparse algorithm.
+    let mut sparse = Builder::<_, Vect>::with_disk_radius(0.4, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .into_iter();
+    sparse.add_blocker(blocker, 0.05);
+    assert_clear_of(&sparse.collect::<Vec<_>>(), blocker, 0.45);
+}
+
+#[test]
+fn blocker_covering_the_domain_stops_generation() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal);
+    let mut iters = (
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(6), algorithm::Bridson)
+            .into_iter(),
+        builder
+            .build(SmallRng::seed_from_u64(6), algorithm::Auto)
+            .into_iter(),
+    );
+    iters.0.add_blocker(Vect::new(0.5, 0.5), 2.0);
+    iters.1.add_blocker(Vect::new(0.5, 0.5), 2.0);
+    assert_eq!(None, iters.0.next());
+    assert_eq!(None, iters.1.next());
+}
+
+#[test]
+fn blockers_move_between_steps() {
+    let radius = 0.02;
+    let mut iter = Builder::<_, Vect>::with_disk_radius(radius, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing)
+        .into_iter();
+    let player = iter.add_blocker(Vect::new(0.2, 0.2), 0.05);
+    let first = iter.step(Budget::Points(100));
+    assert_clear_of(&first, Vect::new(0.2, 0.2), 0.07);
+    assert!(iter.move_blocker(player, Vect::new(0.7, 0.7)));
+    let second = iter.step(Budget::Points(100));
+    assert_clear_of(&second, Vect::new(0.7, 0.7), 0.07);
+    assert_eq!(Some(Vect::new(0.7, 0.7)), iter.remove_blocker(player));
+    assert_eq!(None, iter.remove_blocker(player));
+    assert!(!iter.move_blocker(player, Vect::new(0.1, 0.1)));
+    assert!(iter.stays_legal(Vect::new(0.7, 0.7)) || !second.is_empty());
+}
+
+#[test]
+fn explain_reports_closest_blocker() {
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .into_iter();
+    iter.add_blocker(Vect::new(0.5, 0.5), 0.1);
+    let near = iter.add_blocker(Vect::new(0.6, 0.5), 0.1);
+    let candidate = Vect::new(0.57, 0.5);
+    match iter.explain(candidate) {
+        Some(Rejection::Blocked { position, distance }) => {
+            assert_eq!(Vect::new(0.6, 0.5), position);
+            assert!((distance - 0.03).abs() < 1e-9);
+        }
+        r => panic!("Expected blocker, got {:?}", r),
+    }
+    assert!(!iter.stays_legal(candidate));
+    iter.clear_blockers();
+    assert!(iter.stays_legal(candidate));
+    // Handles of removed blockers are reused.
+    assert_eq!(near, {
+        iter.add_blocker(Vect::new(0.1, 0.1), 0.1);
+        iter.add_blocker(Vect::new(0.9, 0.9), 0.1)
+    });
+}
+
+fn sqdist_to_segment(p: Vect, a: Vect, b: Vect) -> f64 {
+    let t = ((p - a).dot(&(b - a)) / (b - a).norm_squared()).clamp(0., 1.);
+    (p - (a + (b - a) * t)).norm_squared()
+}
+
+#[test]
+fn samples_keep_away_from_segment_obstacles() {
+    let radius = 0.02;
+    let road = (Vect::new(0.1, 0.2), Vect::new(0.8, 0.6));
+    let builder = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .with_segment_obstacle(road.0, road.1, 0.05);
+    let samples = [
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+            .generate(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::Bridson)
+            .generate(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::DartThrowing)
+            .generate(),
+        builder
+            .build(SmallRng::seed_from_u64(4), algorithm::Hashed)
+            .generate(),
+    ];
+    for points in &samples {
+        assert!(!points.is_empty());
+        for p in points {
+            assert!(sqdist_to_segment(*p, road.0, road.1) >= 0.07f64.powi(2));
+        }
+    }
+    let iter = Builder::<_, Vect>::with_disk_radius(radius, Type::Perioditic)
+        .with_segment_obstacle(Vect::new(0.9, 0.5), Vect::new(1.1, 0.5), 0.03)
+        .build(SmallRng::seed_from_u64(5), algorithm::Bridson);
+    match iter.explain(Vect::new(0.04, 0.52)) {
+        Some(Rejection::Blocked { position, distance }) => {
+            assert!((position - Vect::new(1.04, 0.5)).norm() < 1e-9);
+            assert!((distance - 0.02).abs() < 1e-9);
+        }
+        r => panic!("Expected segment obstacle, got {:?}", r),
+    }
+    assert_eq!(None, iter.explain(Vect::new(0.5, 0.5)));
+}
+
+#[test]
+fn segment_obstacle_covering_the_domain_stops_generation() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal).with_segment_obstacle(
+        Vect::new(0., 0.5),
+        Vect::new(1., 0.5),
+        1.0,
+    );
+    assert!(builder
+        .clone()
+        .build(SmallRng::seed_from_u64(7), algorithm::Bridson)
+        .generate()
+        .is_empty());
+    assert!(builder
+        .build(SmallRng::seed_from_u64(7), algorithm::Auto)
+        .generate()
+        .is_empty());
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect2 = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+mod helper;
+
+/// Checks that every point on the edges of the unit square is within the diameter of some sample.
+fn assert_edges_covered(samples: &[Vect2], radius: f64) {
+    let steps = 1000;
+    for i in 0..=steps {
+        let t = i as f64 / steps as f64;
+        for p in &[
+            Vect2::new(t, 0.),
+            Vect2::new(t, 1.),
+            Vect2::new(0., t),
+            Vect2::new(1., t),
+        ] {
+            assert!(
+                samples.iter().any(|s| (s - p).norm() < 2. * radius + 1e-9),
+                "Edge point {:?} wasn't covered.",
+                p
+            );
+        }
+    }
+}
+
+#[test]
+fn boundary_is_sampled_first_2d() {
+    let builder =
+        Builder::<_, Vect2>::with_samples(300, 0.8, Type::Normal).with_boundary_sampling(true);
+    let radius = builder.radius();
+    for seed in 0..5 {
+        let samples = builder
+            .clone()
+            .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+            .generate();
+        assert_eq!(Vect2::new(0., 0.), samples[0]);
+        let on_edge = |v: &Vect2| v.iter().any(|&x| x == 0. || x > 1. - 1e-9);
+        let boundary = samples
+            .iter()
+            .take_while(|v| on_edge(v))
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_edges_covered(&boundary, radius);
+        helper::test_poisson(
+            samples.into_iter(),
+            radius,
+            Type::Normal,
+            algorithm::Ebeida,
+            false,
+        );
+    }
+}
+
+#[test]
+fn boundary_sampling_works_with_every_algorithm_3d() {
+    let builder =
+        Builder::<_, Vect3>::with_samples(300, 0.8, Type::Normal).with_boundary_sampling(true);
+    let radius = builder.radius();
+    let corners = |samples: &[Vect3]| {
+        samples
+            .iter()
+            .filter(|v| v.iter().all(|&x| x == 0. || x > 1. - 1e-9))
+            .count()
+    };
+    let samples = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_eq!(8, corners(&samples));
+    helper::test_poisson(
+        samples.into_iter(),
+        radius,
+        Type::Normal,
+        algorithm::Ebeida,
+        false,
+    );
+    let samples = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate();
+    assert_eq!(8, corners(&samples));
+    helper::test_poisson(
+        samples.into_iter(),
+        radius,
+        Type::Normal,
+        algorithm::Bridson,
+        false,
+    );
+    let samples = builder
+        .build(SmallRng::seed_from_u64(1), algorithm::DartThrowing)
+        .generate();
+    assert_eq!(8, corners(&samples));
+    helper::test_poisson(
+        samples.into_iter(),
+        radius,
+        Type::Normal,
+        algorithm::DartThrowing,
+        false,
+    );
+}
+
+#[test]
+#[should_panic]
+fn boundary_sampling_is_not_supported_for_perioditic() {
+    let _ = Builder::<_, Vect2>::with_disk_radius(0.1, Type::Perioditic).with_boundary_sampling(true);
+}
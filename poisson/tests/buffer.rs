@@ -0,0 +1,79 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Buffers, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn buffer_matches_generate() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida);
+    let mut buffer = vec![Vect::new(2., 2.); 3];
+    generator.generate_into_buffer(&mut buffer);
+    assert_eq!(generator.generate(), buffer);
+}
+
+#[test]
+fn buffer_capacity_is_reused() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.05, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson);
+    let mut buffer = Vec::with_capacity(4096);
+    let address = buffer.as_ptr();
+    for _ in 0..3 {
+        generator.generate_into_buffer(&mut buffer);
+        assert!(!buffer.is_empty());
+        assert_eq!(address, buffer.as_ptr());
+    }
+}
+
+#[test]
+fn iterating_reference_matches_generate() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson);
+    let mut samples = vec![];
+    for sample in &generator {
+        samples.push(sample);
+    }
+    assert_eq!(generator.generate(), samples);
+}
+
+#[test]
+fn pooled_buffers_are_reused_between_generations() {
+    let buffers = Buffers::new();
+    for seed in 0..2 {
+        let builder = Builder::<_, Vect>::with_disk_radius(0.02, Type::Perioditic);
+        let pooled = builder.clone().with_buffers(buffers.clone());
+        assert_eq!(
+            builder
+                .clone()
+                .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+                .generate(),
+            pooled
+                .clone()
+                .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+                .generate()
+        );
+        let capacity = buffers.capacity();
+        assert!(capacity > 0);
+        assert_eq!(
+            builder
+                .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+                .generate(),
+            pooled
+                .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+                .generate()
+        );
+        assert!(buffers.capacity() >= capacity);
+    }
+    let capacity = buffers.capacity();
+    // Generating again takes the buffers from the pool instead of allocating more.
+    Builder::<_, Vect>::with_disk_radius(0.02, Type::Perioditic)
+        .with_buffers(buffers.clone())
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    assert_eq!(capacity, buffers.capacity());
+    buffers.clear();
+    assert_eq!(0, buffers.capacity());
+}
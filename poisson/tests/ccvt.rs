@@ -0,0 +1,70 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Ccvt, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+/// Returns the coefficient of variation of the areas of the perioditic Voronoi cells estimated on a regular grid.
+fn cell_area_variation(points: &[Vect]) -> f64 {
+    let steps = 200;
+    let mut areas = vec![0usize; points.len()];
+    for x in 0..steps {
+        for y in 0..steps {
+            let p = Vect::new(x as f64, y as f64) / steps as f64;
+            let nearest = (0..points.len())
+                .min_by(|&a, &b| {
+                    let da = (points[a] - p).map(|x| x - x.round()).norm();
+                    let db = (points[b] - p).map(|x| x - x.round()).norm();
+                    da.partial_cmp(&db).unwrap()
+                })
+                .unwrap();
+            areas[nearest] += 1;
+        }
+    }
+    let mean = areas.iter().sum::<usize>() as f64 / areas.len() as f64;
+    let variance = areas
+        .iter()
+        .map(|&a| (a as f64 - mean).powi(2))
+        .sum::<f64>()
+        / areas.len() as f64;
+    variance.sqrt() / mean
+}
+
+#[test]
+fn refinement_evens_out_cell_areas() {
+    let set = Builder::<_, Vect>::with_samples(150, 0.6, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing)
+        .generate_set();
+    let refined = set.clone().refine_ccvt(
+        &Ccvt::new().with_iterations(30).with_sites(64),
+        &mut SmallRng::seed_from_u64(3),
+    );
+    assert_eq!(set.len(), refined.len());
+    assert_eq!(set.algorithm(), refined.algorithm());
+    for p in &refined {
+        assert!(p.iter().all(|&x| (0. ..1.).contains(&x)));
+    }
+    assert!(cell_area_variation(refined.points()) < cell_area_variation(set.points()));
+}
+
+#[test]
+fn refinement_stops_at_threshold() {
+    let set = Builder::<_, Vect>::with_samples(50, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+        .generate_set();
+    let ccvt = Ccvt::new().with_iterations(0);
+    assert_eq!(
+        set.points(),
+        set.clone()
+            .refine_ccvt(&ccvt, &mut SmallRng::seed_from_u64(3))
+            .points()
+    );
+    let ccvt = Ccvt::new().with_iterations(1000).with_threshold(1e-3);
+    assert_eq!(1e-3, ccvt.threshold());
+    let refined = set
+        .clone()
+        .refine_ccvt(&ccvt, &mut SmallRng::seed_from_u64(3));
+    assert_eq!(set.len(), refined.len());
+}
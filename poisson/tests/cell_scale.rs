@@ -0,0 +1,51 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector3<f64>;
+
+fn check_separation<A: algorithm::Creator<f64, Vect> + Copy>(algo: A, scale: f64) {
+    for &ptype in &[Type::Normal, Type::Perioditic] {
+        for seed in 0..3 {
+            let builder = Builder::<_, Vect>::with_samples(300, 0.8, ptype).with_cell_scale(scale);
+            let radius = builder.radius();
+            let samples = builder
+                .build(SmallRng::seed_from_u64(seed), algo)
+                .generate();
+            assert!(!samples.is_empty());
+            for (i, a) in samples.iter().enumerate() {
+                for b in &samples[i + 1..] {
+                    let mut d = b - a;
+                    if ptype == Type::Perioditic {
+                        d = d.map(|c| c - c.round());
+                    }
+                    assert!(d.norm() >= 2. * radius);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn scaled_cells_keep_separation() {
+    for &scale in &[0.4, 0.7, 1.5] {
+        check_separation(algorithm::Bridson, scale);
+        check_separation(algorithm::DartThrowing, scale);
+    }
+}
+
+#[test]
+fn cell_scale_does_not_change_other_algorithms() {
+    let builder = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal);
+    let plain = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    let scaled = builder
+        .with_cell_scale(0.5)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_eq!(plain, scaled);
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Rejection, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn generate_around<A>(algo: A, building: Vect, clearance: f64, radius: f64) -> Vec<Vect>
+where
+    A: algorithm::Creator<f64, Vect>,
+{
+    let mut iter = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algo)
+        .into_iter();
+    iter.restrict_with_radius(building, clearance);
+    iter.collect()
+}
+
+#[test]
+fn samples_keep_clearance_of_restricted_samples() {
+    let radius = 0.02;
+    let building = Vect::new(0.5, 0.5);
+    let samples = [
+        generate_around(algorithm::Ebeida, building, 0.15, radius),
+        generate_around(algorithm::DartThrowing, building, 0.15, radius),
+        generate_around(algorithm::Hashed, building, 0.15, radius),
+        // Radius this large is generated with the sparse algorithm.
+        generate_around(algorithm::Ebeida, Vect::new(0.2, 0.2), 0.2, 0.3),
+    ];
+    for (i, points) in samples.iter().enumerate() {
+        let (building, clearance, radius) = if i == 3 {
+            (Vect::new(0.2, 0.2), 0.2, 0.3)
+        } else {
+            (building, 0.15, radius)
+        };
+        assert!(!points.is_empty());
+        for (j, p) in points.iter().enumerate() {
+            assert!((p - building).norm() >= clearance + radius);
+            for q in &points[j + 1..] {
+                assert!((p - q).norm() >= 2. * radius);
+            }
+        }
+    }
+    // Without the clearance samples are generated right next to the building.
+    let near = generate_around(algorithm::Ebeida, building, 0., radius);
+    assert!(near.iter().any(|p| (p - building).norm() < 0.15));
+}
+
+#[test]
+fn clearance_below_radius_is_the_radius() {
+    let radius = 0.05;
+    let building = Vect::new(0.5, 0.5);
+    let points = generate_around(algorithm::DartThrowing, building, 0.01, radius);
+    assert!(!points.is_empty());
+    assert!(points.iter().all(|p| (p - building).norm() >= 2. * radius));
+}
+
+#[test]
+fn explains_clearance_as_conflict() {
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .into_iter();
+    let building = Vect::new(0.5, 0.5);
+    iter.restrict_with_radius(building, 0.2);
+    let candidate = Vect::new(0.7, 0.5);
+    assert!(!iter.stays_legal(candidate));
+    match iter.explain(candidate) {
+        Some(Rejection::Conflict { sample, distance }) => {
+            assert_eq!(building, sample);
+            assert!((distance - 0.2).abs() < 1e-9);
+        }
+        other => panic!("Expected conflict with the building, got {:?}", other),
+    }
+    assert!(iter.stays_legal(Vect::new(0.75, 0.5)));
+}
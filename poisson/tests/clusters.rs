@@ -0,0 +1,89 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Clusters, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+const SPREAD: f64 = 0.1;
+const CHILD: f64 = 0.01;
+
+fn clusters(ptype: Type) -> Clusters<f64, Vect> {
+    Clusters::new(
+        Builder::with_disk_radius(0.08, ptype),
+        Builder::with_disk_radius(CHILD, ptype),
+        SPREAD,
+    )
+}
+
+fn wrapped(a: &Vect, b: &Vect, ptype: Type) -> f64 {
+    let mut d = a - b;
+    if ptype == Type::Perioditic {
+        d = d.map(|c| c - c.round());
+    }
+    d.norm()
+}
+
+fn check<A>(ptype: Type, algo: A)
+where
+    A: algorithm::Creator<f64, Vect>,
+{
+    let (parents, clumps) = clusters(ptype).generate(SmallRng::seed_from_u64(3), algo);
+    assert_eq!(parents.len(), clumps.len());
+    let mut children = vec![];
+    for (parent, clump) in parents.iter().zip(&clumps) {
+        for child in clump {
+            let d = wrapped(child, parent, ptype);
+            assert!(d <= SPREAD);
+            // Each child is in the clump of the closest parent.
+            assert!(parents.iter().all(|p| d <= wrapped(child, p, ptype)));
+        }
+        children.extend(clump.iter().cloned());
+    }
+    // Children of neighbouring clumps keep their spacing too.
+    for (i, a) in children.iter().enumerate() {
+        for b in &children[i + 1..] {
+            assert!(wrapped(a, b, ptype) >= 2. * CHILD);
+        }
+    }
+}
+
+#[test]
+fn children_are_spaced_inside_disks() {
+    check(Type::Normal, algorithm::Ebeida);
+    check(Type::Normal, algorithm::Bridson);
+    check(Type::Perioditic, algorithm::Ebeida);
+    check(Type::Perioditic, algorithm::Bridson);
+}
+
+#[test]
+fn clumps_are_filled() {
+    let (parents, clumps) =
+        clusters(Type::Normal).generate(SmallRng::seed_from_u64(4), algorithm::Ebeida);
+    assert!(clumps.iter().all(|clump| !clump.is_empty()));
+    let mut rng = SmallRng::seed_from_u64(5);
+    let children = clumps.iter().flat_map(|c| c.iter()).collect::<Vec<_>>();
+    let (mut inside, mut uncovered) = (0, 0);
+    for _ in 0..4000 {
+        let p = Vect::new(rng.random(), rng.random());
+        if parents.iter().any(|q| (q - p).norm() <= SPREAD) {
+            inside += 1;
+            if children.iter().all(|c| (*c - p).norm() >= 2. * CHILD) {
+                uncovered += 1;
+            }
+        }
+    }
+    assert!(uncovered * 100 < inside, "{} of {} uncovered", uncovered, inside);
+}
+
+#[test]
+fn clusters_are_reproducible() {
+    let clusters = clusters(Type::Normal);
+    assert_eq!(
+        clusters.generate(SmallRng::seed_from_u64(6), algorithm::Bridson),
+        clusters.generate(SmallRng::seed_from_u64(6), algorithm::Bridson)
+    );
+    assert_eq!(SPREAD, clusters.spread());
+    assert_eq!(CHILD, clusters.children().radius());
+}
@@ -1,4 +1,7 @@
-use poisson::{Builder, Type};
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
 
 extern crate nalgebra as na;
 use crate::na::Vector2 as naVec2;
@@ -7,13 +10,24 @@ pub type Vec2 = naVec2<f64>;
 #[test]
 #[should_panic]
 fn test_normal_too_small_radius() {
-    let _ = Builder::<_, Vec2>::with_radius(0.0, Type::Normal);
+    let _ = Builder::<_, Vec2>::with_disk_radius(0.0, Type::Normal);
 }
 
 #[test]
-#[should_panic]
-fn test_normal_too_large_radius() {
-    let _ = Builder::<_, Vec2>::with_radius(2f64.sqrt() / 2.0 + 0.0001, Type::Normal);
+fn test_normal_too_large_radius_fits_one_sample() {
+    let points = Builder::<_, Vec2>::with_disk_radius(2f64.sqrt() / 2.0 + 0.0001, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_eq!(1, points.len());
+}
+
+#[test]
+fn test_radius_accessors() {
+    let builder = Builder::<_, Vec2>::with_relative_radius(0.5, Type::Normal);
+    assert!((builder.relative_radius() - 0.5).abs() < 1e-12);
+    assert!((builder.radius() - 2f64.sqrt() / 4.).abs() < 1e-12);
+    assert!((builder.diameter() - 2. * builder.radius()).abs() < 1e-12);
+    assert!((builder.absolute_radius(100.) - 100. * builder.radius()).abs() < 1e-9);
 }
 
 // #[test]
@@ -1,3 +1,4 @@
+#![cfg(feature = "full")]
 use poisson::Type::*;
 
 extern crate nalgebra as na;
@@ -0,0 +1,49 @@
+#![cfg(feature = "full")]
+use poisson::verify::verify_separation;
+use poisson::{algorithm, Builder, Type::*};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector5<f64>;
+
+mod helper;
+use crate::helper::test_with_samples;
+
+#[test]
+fn test_5d_1_80_perioditic() {
+    test_with_samples::<Vect>(1, 0.8, 10, Perioditic);
+}
+
+#[test]
+fn test_5d_10_80_perioditic() {
+    test_with_samples::<Vect>(10, 0.8, 2, Perioditic);
+}
+
+#[test]
+fn test_5d_radius_15_normal_is_separated() {
+    let samples = Builder::<_, Vect>::with_disk_radius(0.15, Normal)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    assert!(samples.len() >= 200);
+    assert!(verify_separation(&samples, 0.15, Normal).is_ok());
+    let samples = Builder::<_, Vect>::with_disk_radius(0.15, Normal)
+        .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing)
+        .generate();
+    assert!(samples.len() >= 200);
+    assert!(verify_separation(&samples, 0.15, Normal).is_ok());
+}
+
+#[test]
+fn test_5d_radius_15_perioditic_is_separated() {
+    let samples = Builder::<_, Vect>::with_disk_radius(0.15, Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    assert!(samples.len() >= 200);
+    assert!(verify_separation(&samples, 0.15, Perioditic).is_ok());
+    let samples = Builder::<_, Vect>::with_disk_radius(0.15, Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing)
+        .generate();
+    assert!(samples.len() >= 200);
+    assert!(verify_separation(&samples, 0.15, Perioditic).is_ok());
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "full")]
+use poisson::verify::verify_separation;
+use poisson::{algorithm, Builder, Type::*};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector6<f64>;
+
+mod helper;
+use crate::helper::test_with_samples;
+
+#[test]
+fn test_6d_10_80_perioditic() {
+    test_with_samples::<Vect>(10, 0.8, 1, Perioditic);
+}
+
+#[test]
+fn test_6d_radius_17_normal_is_separated() {
+    let samples = Builder::<_, Vect>::with_disk_radius(0.17, Normal)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    assert!(samples.len() >= 200);
+    assert!(verify_separation(&samples, 0.17, Normal).is_ok());
+}
+
+#[test]
+fn test_6d_radius_17_perioditic_is_separated() {
+    let samples = Builder::<_, Vect>::with_disk_radius(0.17, Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    assert!(samples.len() >= 200);
+    assert!(verify_separation(&samples, 0.17, Perioditic).is_ok());
+}
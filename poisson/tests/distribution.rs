@@ -0,0 +1,32 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, PointSet, PoissonDisk, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn sampling_matches_generator() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal);
+    let disk = PoissonDisk::new(builder.clone(), algorithm::Ebeida);
+    let points: Vec<Vect> = SmallRng::seed_from_u64(3).sample(&disk);
+    let expected = builder
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate();
+    assert_eq!(expected, points);
+}
+
+#[test]
+fn sampled_sets_are_independent() {
+    let disk = PoissonDisk::new(
+        Builder::<_, Vect>::with_disk_radius(0.05, Type::Perioditic),
+        algorithm::Bridson,
+    );
+    let sets: Vec<PointSet<_, _>> = SmallRng::seed_from_u64(5)
+        .sample_iter(&disk)
+        .take(2)
+        .collect();
+    assert_eq!(Some("Bridson"), sets[0].algorithm());
+    assert_ne!(sets[0].points(), sets[1].points());
+}
@@ -0,0 +1,197 @@
+#![cfg(feature = "full")]
+use poisson::verify::verify_separation;
+use poisson::{algorithm, Boundary, Builder, Domain, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+/// Domain of the surface of a cylinder which wraps around the x axis.
+fn cylinder() -> Domain {
+    Domain::open().with_boundary(0, Boundary::Perioditic)
+}
+
+fn assert_legal_on_cylinder(samples: &[Vect], radius: f64) {
+    for (i, a) in samples.iter().enumerate() {
+        for b in &samples[i + 1..] {
+            let mut diff = b - a;
+            diff.x -= diff.x.round();
+            assert!(
+                diff.norm() >= 2. * radius,
+                "Samples {:?} and {:?} are too close on the cylinder.",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn mixed_boundaries_are_respected() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.03, cylinder());
+    assert_eq!(Type::Normal, builder.poisson_type());
+    assert_eq!(Boundary::Perioditic, builder.domain().boundary(0));
+    assert_eq!(Boundary::Open, builder.domain().boundary(1));
+    let radius = builder.radius();
+    let ebeida = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_legal_on_cylinder(&ebeida, radius);
+    let bridson = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate();
+    assert_legal_on_cylinder(&bridson, radius);
+    let dart = builder
+        .build(SmallRng::seed_from_u64(1), algorithm::DartThrowing)
+        .generate();
+    assert_legal_on_cylinder(&dart, radius);
+}
+
+#[test]
+fn type_is_shorthand_for_uniform_domain() {
+    for &(poisson_type, domain) in &[
+        (Type::Normal, Domain::open()),
+        (Type::Perioditic, Domain::perioditic()),
+    ] {
+        let from_type = Builder::<_, Vect>::with_disk_radius(0.05, poisson_type)
+            .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+            .generate_set();
+        let from_domain = Builder::<_, Vect>::with_disk_radius(0.05, domain)
+            .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+            .generate_set();
+        assert_eq!(from_type, from_domain);
+        assert_eq!(poisson_type, from_domain.poisson_type());
+    }
+}
+
+#[test]
+fn boundary_sampling_skips_perioditic_axes() {
+    let samples = Builder::<_, Vect>::with_disk_radius(0.03, cylinder())
+        .with_boundary_sampling(true)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    let on_side = |v: &Vect| v.y == 0. || v.y > 1. - 1e-9;
+    let boundary = samples.iter().take_while(|v| on_side(v)).count();
+    assert!(boundary > 0);
+    assert!(samples[..boundary].iter().any(|v| v.y == 0.));
+    assert!(samples[..boundary].iter().any(|v| v.y > 1. - 1e-9));
+    assert_legal_on_cylinder(&samples, 0.03);
+}
+
+#[test]
+fn extents_shorten_axes() {
+    let domain = Domain::open().with_extent(1, 1. / 32.);
+    assert_eq!(1., domain.extent(0));
+    assert_eq!(1. / 32., domain.extent(1));
+    assert_eq!(1., domain.extent(20));
+    assert!(!domain.is_unit());
+    assert!(Domain::perioditic().is_unit());
+    assert_eq!(1. / 32., domain.volume(2));
+    assert_ne!(Domain::open(), domain);
+}
+
+#[test]
+#[should_panic]
+fn extent_larger_than_unit_panics() {
+    let _ = Domain::open().with_extent(0, 1.5);
+}
+
+#[test]
+fn long_strip_is_sampled_within_extents() {
+    // Strip of 4096 × 128 with the longer side normalized to the unit.
+    let radius = 0.004;
+    let strip = Domain::open().with_extent(1, 128. / 4096.);
+    let builder = Builder::<_, Vect>::with_disk_radius(radius, strip);
+    let samples = [
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+            .generate(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::Bridson)
+            .generate(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::DartThrowing)
+            .generate(),
+        builder
+            .build(SmallRng::seed_from_u64(4), algorithm::Hashed)
+            .generate(),
+    ];
+    for points in &samples {
+        assert!(points
+            .iter()
+            .all(|p| (0. ..1.).contains(&p.x) && (0. ..1. / 32.).contains(&p.y)));
+        assert_eq!(Ok(()), verify_separation(points, radius, strip));
+    }
+    // The strip is covered up to its far end.
+    let ebeida = &samples[0];
+    let mut rng = SmallRng::seed_from_u64(5);
+    let uncovered = (0..2000)
+        .map(|_| Vect::new(rng.random(), rng.random::<f64>() / 32.))
+        .filter(|p| ebeida.iter().all(|q| (q - p).norm() >= 2. * radius))
+        .count();
+    assert!(uncovered < 20, "{} uncovered", uncovered);
+}
+
+#[test]
+fn perioditic_strip_wraps_around_extents() {
+    let radius = 0.02;
+    let strip = Domain::perioditic().with_extent(1, 0.25);
+    for points in [
+        Builder::<_, Vect>::with_disk_radius(radius, strip)
+            .build(SmallRng::seed_from_u64(6), algorithm::Ebeida)
+            .generate_set(),
+        Builder::<_, Vect>::with_disk_radius(radius, strip)
+            .build(SmallRng::seed_from_u64(6), algorithm::Bridson)
+            .generate_set(),
+    ] {
+        assert!(points.iter().all(|p| p.y < 0.25));
+        assert_eq!(Ok(()), verify_separation(points.points(), radius, strip));
+        // Tiles are offset by the extents so they stay legal across their seams.
+        let (tiled, extent) = points.tile(&[2, 3]);
+        assert_eq!(Vect::new(2., 0.75), extent);
+        assert!(tiled.iter().any(|p| p.y > 0.5));
+        assert!(tiled.iter().all(|p| p.x < extent.x && p.y < extent.y));
+        assert_eq!(Ok(()), verify_separation(&tiled, radius, Type::Normal));
+    }
+}
+
+#[test]
+fn samples_fill_the_volume_of_the_domain() {
+    let strip = Domain::perioditic().with_extent(1, 0.25);
+    let unit = Builder::<f64, Vect>::with_samples(400, 0.8, Type::Perioditic).radius();
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, strip);
+    assert!((builder.radius() - unit).abs() < 1e-9);
+    let count = builder
+        .build(SmallRng::seed_from_u64(7), algorithm::Ebeida)
+        .generate()
+        .len();
+    assert!((70..130).contains(&count), "{}", count);
+}
+
+#[test]
+fn perioditic_strip_with_a_short_cell_stays_legal_across_its_seam() {
+    let radius = 0.02;
+    // The extent isn't a whole amount of cells so the last cell along y is cut short where it wraps.
+    let strip = Domain::perioditic().with_extent(1, 8.05 / 35.);
+    for seed in 0..20 {
+        for points in [
+            Builder::<_, Vect>::with_disk_radius(radius, strip)
+                .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+                .generate_set(),
+            Builder::<_, Vect>::with_disk_radius(radius, strip)
+                .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+                .generate_set(),
+        ] {
+            assert_eq!(Ok(()), verify_separation(points.points(), radius, strip));
+            // Tiling puts the seams side by side so the open domain check doesn't depend on wrapping.
+            let (tiled, _) = points.tile(&[2, 2]);
+            assert_eq!(Ok(()), verify_separation(&tiled, radius, Type::Normal));
+        }
+    }
+}
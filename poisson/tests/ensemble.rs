@@ -0,0 +1,51 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+#[test]
+fn ensemble_generates_independent_valid_sets() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic);
+    let sets = builder
+        .generate_ensemble(SmallRng::seed_from_u64(9), algorithm::Bridson, 10)
+        .collect::<Vec<_>>();
+    assert_eq!(10, sets.len());
+    for (i, set) in sets.iter().enumerate() {
+        assert!(sets[i + 1..].iter().all(|other| other != set));
+        helper::test_poisson(
+            set.iter().cloned(),
+            set.radius(),
+            set.poisson_type(),
+            algorithm::Bridson,
+            false,
+        );
+    }
+}
+
+#[test]
+fn ensemble_is_reproducible() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal);
+    let first = builder
+        .generate_ensemble(SmallRng::seed_from_u64(9), algorithm::Ebeida, 5)
+        .collect::<Vec<_>>();
+    let second = builder
+        .generate_ensemble(SmallRng::seed_from_u64(9), algorithm::Ebeida, 5)
+        .collect::<Vec<_>>();
+    assert_eq!(first, second);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_ensemble_matches_sequential() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal);
+    let sequential = builder
+        .generate_ensemble(SmallRng::seed_from_u64(9), algorithm::Ebeida, 8)
+        .collect::<Vec<_>>();
+    let parallel = builder.generate_ensemble_par(SmallRng::seed_from_u64(9), algorithm::Ebeida, 8);
+    assert_eq!(sequential, parallel);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+const SCALE: f64 = (1u64 << 60) as f64;
+
+fn isqrt(n: i128) -> i128 {
+    let mut x = (n as f64).sqrt() as i128;
+    while x * x > n {
+        x -= 1;
+    }
+    while (x + 1) * (x + 1) <= n {
+        x += 1;
+    }
+    x
+}
+
+/// Checks pairs of samples close to the diameter apart. Every float in [0, 1) closer to zero than 0.125 is
+/// a multiple of 2^-60 so the squared distance can be computed exactly with integers.
+fn count_misjudged(exact: bool) -> usize {
+    let radius = 0.05;
+    let diameter = (2. * radius * SCALE) as i128;
+    let start = Vect::new(0., 0.);
+    let generator = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .with_start_points(&[start], false)
+        .with_exact_checks(exact)
+        .build(SmallRng::seed_from_u64(0), algorithm::DartThrowing);
+    let mut rng = SmallRng::seed_from_u64(1);
+    let mut misjudged = 0;
+    for _ in 0..20000 {
+        let dx = rng.random_range(0..diameter);
+        let dy = isqrt(diameter * diameter - dx * dx) + rng.random_range(-64..=64);
+        let sample = Vect::new(dx as f64, dy as f64) / SCALE;
+        let (dx, dy) = ((sample.x * SCALE) as i128, (sample.y * SCALE) as i128);
+        let conflicting = dx * dx + dy * dy <= diameter * diameter;
+        if generator.explain(sample).is_some() != conflicting {
+            misjudged += 1;
+        }
+    }
+    misjudged
+}
+
+#[test]
+fn exact_checks_judge_every_near_pair_correctly() {
+    assert_eq!(0, count_misjudged(true));
+}
+
+#[test]
+fn rounded_checks_misjudge_some_near_pairs() {
+    assert!(count_misjudged(false) > 0);
+}
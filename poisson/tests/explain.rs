@@ -0,0 +1,109 @@
+#![cfg(feature = "full")]
+use poisson::shape::Annulus;
+use poisson::{algorithm, Builder, Rejection, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn explain_reports_closest_conflicting_sample() {
+    let radius = 0.05;
+    let start = [
+        Vect::new(0.3, 0.5),
+        Vect::new(0.42, 0.5),
+        Vect::new(0.8, 0.8),
+    ];
+    let generator = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .with_start_points(&start, false)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida);
+    assert_eq!(
+        Some(Rejection::Conflict {
+            sample: start[1],
+            distance: 0.04,
+        }),
+        generator.explain(Vect::new(0.38, 0.5)).map(|r| match r {
+            Rejection::Conflict { sample, distance } => Rejection::Conflict {
+                sample,
+                distance: (distance * 1e9).round() / 1e9,
+            },
+            r => r,
+        })
+    );
+    assert_eq!(None, generator.explain(Vect::new(0.6, 0.2)));
+}
+
+#[test]
+fn explain_agrees_with_stays_legal() {
+    let radius = 0.03;
+    let sample = Vect::new(0.99, 0.5);
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let builder = Builder::<_, Vect>::with_disk_radius(radius, ptype);
+        let mut iters = (
+            builder
+                .clone()
+                .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+                .into_iter(),
+            builder
+                .clone()
+                .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+                .into_iter(),
+            builder
+                .clone()
+                .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing)
+                .into_iter(),
+            builder
+                .build(SmallRng::seed_from_u64(2), algorithm::Hashed)
+                .into_iter(),
+        );
+        iters.0.restrict(Vect::new(0.01, 0.5));
+        iters.1.restrict(Vect::new(0.01, 0.5));
+        iters.2.restrict(Vect::new(0.01, 0.5));
+        iters.3.restrict(Vect::new(0.01, 0.5));
+        let explained = [
+            (iters.0.explain(sample), iters.0.stays_legal(sample)),
+            (iters.1.explain(sample), iters.1.stays_legal(sample)),
+            (iters.2.explain(sample), iters.2.stays_legal(sample)),
+            (iters.3.explain(sample), iters.3.stays_legal(sample)),
+        ];
+        for (explanation, legal) in explained {
+            assert_eq!(legal, explanation.is_none());
+            // Only the perioditic domain wraps the samples next to each other.
+            assert_eq!(ptype == Type::Perioditic, explanation.is_some());
+        }
+        while let Some(p) = iters.0.next() {
+            let probe = p + Vect::new(radius, 0.);
+            assert_eq!(iters.0.stays_legal(probe), iters.0.explain(probe).is_none());
+        }
+    }
+}
+
+#[test]
+fn explain_reports_shape_and_sparse_conflicts() {
+    let annulus = Annulus {
+        center: Vect::new(0.5, 0.5),
+        inner: 0.2,
+        outer: 0.4,
+    };
+    let generator = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal)
+        .with_shape(annulus)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson);
+    assert_eq!(
+        Some(Rejection::OutsideShape),
+        generator.explain(Vect::new(0.5, 0.5))
+    );
+    assert_eq!(None, generator.explain(Vect::new(0.8, 0.5)));
+    // Radius this large is generated with the sparse algorithm.
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.4, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .into_iter();
+    let first = iter.next().unwrap();
+    match iter.explain(first) {
+        Some(Rejection::Conflict { sample, distance }) => {
+            assert_eq!(first, sample);
+            assert_eq!(0., distance);
+        }
+        r => panic!("Expected conflict, got {:?}", r),
+    }
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, PointSet, Type, BUFFER_MAGIC};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn shader_arrays_list_every_sample() {
+    let set = PointSet::new(
+        vec![Vect::new(0.25, 0.5), Vect::new(0.75, 0.125)],
+        0.1,
+        Type::Perioditic,
+    );
+    assert_eq!(
+        "const KERNEL: array<vec2<f32>, 2> = array<vec2<f32>, 2>(\n    vec2<f32>(0.25, 0.5),\n    vec2<f32>(0.75, 0.125),\n);\n",
+        set.to_wgsl("KERNEL")
+    );
+    assert_eq!(
+        "const vec2 kernel[2] = vec2[2](\n    vec2(0.25, 0.5),\n    vec2(0.75, 0.125)\n);\n",
+        set.to_glsl("kernel")
+    );
+}
+
+#[test]
+fn buffer_has_header_and_packed_coordinates() {
+    let set = Builder::<_, Vect>::with_samples(64, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate_set();
+    let buffer = set.to_buffer();
+    assert_eq!(16 + 8 * set.len(), buffer.len());
+    assert_eq!(BUFFER_MAGIC, buffer[0..4]);
+    let word = |i: usize| <[u8; 4]>::try_from(&buffer[4 * i..4 * i + 4]).unwrap();
+    assert_eq!(2, u32::from_le_bytes(word(1)));
+    assert_eq!(set.len() as u32, u32::from_le_bytes(word(2)));
+    assert_eq!(set.radius() as f32, f32::from_le_bytes(word(3)));
+    for (i, p) in set.iter().enumerate() {
+        assert_eq!(p.x as f32, f32::from_le_bytes(word(4 + 2 * i)));
+        assert_eq!(p.y as f32, f32::from_le_bytes(word(5 + 2 * i)));
+    }
+}
+
+#[test]
+fn writer_gets_same_buffer_as_point_set() {
+    // Enough samples to fill more than one chunk of the writer.
+    let generator = Builder::<_, Vect>::with_samples(100_000, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson);
+    let expected = generator.generate_set().to_buffer();
+    let mut cursor = std::io::Cursor::new(b"head".to_vec());
+    cursor.set_position(4);
+    let count = generator.generate_to_writer(&mut cursor).unwrap();
+    assert!(count > 1 << 16);
+    assert_eq!(4 + expected.len() as u64, cursor.position());
+    let written = cursor.into_inner();
+    assert_eq!(b"head", &written[..4]);
+    assert_eq!(expected, written[4..]);
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "full")]
+use poisson::algorithm::{self, Creator};
+use poisson::shape::Annulus;
+use poisson::verify::verify_separation;
+use poisson::{Builder, Ccvt, Float, MergePolicy, PointSet, Type, Vector};
+
+use rand::distr::StandardUniform;
+use rand::{rngs::SmallRng, SeedableRng};
+use rand_distr::Distribution;
+
+use std::fmt::Debug;
+
+extern crate nalgebra as na;
+
+mod helper;
+
+fn cast<F: Float>(value: f64) -> F {
+    num_traits::NumCast::from(value).expect("Test constant should be castable to float.")
+}
+
+fn generate<F, V, A>(algo: A)
+where
+    F: Float,
+    V: Debug + Vector<F> + Copy,
+    A: Creator<F, V>,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let radius = cast::<F>(0.08);
+        let points = Builder::<F, V>::with_disk_radius(radius, ptype)
+            .build(SmallRng::seed_from_u64(1), algo)
+            .generate();
+        helper::test_poisson(points.into_iter(), radius, ptype, algo, false);
+        let builder = Builder::<F, V>::with_samples(50, cast(0.8), ptype);
+        let radius = builder.radius();
+        let points = builder.build(SmallRng::seed_from_u64(2), algo).generate();
+        helper::test_poisson(points.into_iter(), radius, ptype, algo, false);
+    }
+}
+
+fn generate_all<F, V>()
+where
+    F: Float,
+    V: Debug + Vector<F> + Copy,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    generate::<F, V, _>(algorithm::Ebeida);
+    generate::<F, V, _>(algorithm::Bridson);
+    generate::<F, V, _>(algorithm::DartThrowing);
+    generate::<F, V, _>(algorithm::Hashed);
+    generate::<F, V, _>(algorithm::Sparse);
+    generate::<F, V, _>(algorithm::Auto);
+    generate::<F, V, _>(algorithm::Triangular);
+    generate::<F, V, _>(algorithm::Hexagonal);
+}
+
+#[test]
+fn algorithms_work_with_f32_and_f64() {
+    generate_all::<f32, na::Vector2<f32>>();
+    generate_all::<f64, na::Vector2<f64>>();
+    generate_all::<f32, na::Vector3<f32>>();
+    generate_all::<f64, na::Vector3<f64>>();
+}
+
+fn features<F, V>()
+where
+    F: Float,
+    V: Debug + Vector<F> + Copy + PartialEq + Send + Sync + 'static,
+    StandardUniform: Distribution<F>,
+    StandardUniform: Distribution<V>,
+{
+    let radius = cast::<F>(0.02);
+    let mut center = V::zero();
+    for n in 0..V::dimension() {
+        center[n] = cast(0.5);
+    }
+    let annulus = Annulus {
+        center,
+        inner: cast(0.2),
+        outer: cast(0.45),
+    };
+    let points = Builder::<F, V>::with_disk_radius(radius, Type::Normal)
+        .with_shape(annulus)
+        .with_boundary_sampling(true)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    helper::assert_legal_poisson(&points, radius, algorithm::Bridson);
+    let points = Builder::<F, V>::with_disk_radius(radius, Type::Normal)
+        .with_boundary_sampling(true)
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate();
+    helper::assert_legal_poisson(&points, radius, algorithm::Ebeida);
+
+    let generator = Builder::<F, V>::with_disk_radius(radius, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(4), algorithm::Bridson);
+    let set = generator.generate_set();
+    assert!(verify_separation(set.points(), radius, Type::Perioditic).is_ok());
+    let quantized = set.clone().quantize(cast(0.01));
+    assert!(verify_separation(quantized.points(), radius, Type::Perioditic).is_ok());
+    assert!(set.packing_fraction() > cast(0.4));
+    assert_eq!(set.len(), set.iter_morton().count());
+    assert_eq!(4 * set.len(), set.tile(&[2, 2]).0.len());
+    let merged = set.clone().merge(
+        generator.generate_set().retain_far_from_boundary(cast(0.1)),
+        MergePolicy::KeepFirst,
+    );
+    assert!(verify_separation(merged.points(), radius, Type::Perioditic).is_ok());
+    let refined = set.refine_ccvt(
+        &Ccvt::new().with_iterations(2),
+        &mut SmallRng::seed_from_u64(5),
+    );
+    assert!(!refined.is_empty());
+    let prior = PointSet::new(vec![center], radius, Type::Perioditic);
+    assert!(generator.regenerate_from(&prior).points().contains(&center));
+}
+
+#[test]
+fn features_work_with_f32_and_f64() {
+    features::<f32, na::Vector2<f32>>();
+    features::<f64, na::Vector2<f64>>();
+}
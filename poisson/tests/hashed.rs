@@ -0,0 +1,45 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn far_from(points: &[Vect], corner: Vect) -> Vec<Vect> {
+    points
+        .iter()
+        .cloned()
+        .filter(|p| (p - corner).norm() > 0.5)
+        .collect()
+}
+
+#[test]
+fn restriction_keeps_distant_samples() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal);
+    let points = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(4), algorithm::Hashed)
+        .generate();
+    let corner = Vect::new(0.01, 0.01);
+    let restricted = builder
+        .with_start_points(&[corner], false)
+        .build(SmallRng::seed_from_u64(4), algorithm::Hashed)
+        .generate();
+    let (far, restricted_far) = (far_from(&points, corner), far_from(&restricted, corner));
+    assert!(!far.is_empty());
+    assert_eq!(far, restricted_far);
+}
+
+#[test]
+fn seed_changes_samples() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.05, Type::Perioditic);
+    let first = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Hashed)
+        .generate();
+    let second = builder
+        .build(SmallRng::seed_from_u64(2), algorithm::Hashed)
+        .generate();
+    assert_ne!(first, second);
+}
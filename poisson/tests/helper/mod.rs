@@ -1,5 +1,5 @@
 #![allow(unused)]
-use poisson::{algorithm, Builder, Float, Type, Vector};
+use poisson::{algorithm, Builder, Float, PointSet, Type, Vector};
 
 use rand::distr::StandardUniform;
 use rand::{rngs::SmallRng, SeedableRng};
@@ -78,8 +78,27 @@ pub fn test_with_samples_prefilled<'r, T, F, I>(
         valid,
         algorithm::Bridson,
     );
+    test_algo(
+        samples,
+        relative_radius,
+        seeds,
+        ptype,
+        &mut prefiller,
+        valid,
+        algorithm::DartThrowing,
+    );
+    test_algo(
+        samples,
+        relative_radius,
+        seeds,
+        ptype,
+        &mut prefiller,
+        valid,
+        algorithm::Hashed,
+    );
 }
 
+#[allow(clippy::useless_conversion)]
 fn test_algo<'r, T, F, I, A>(
     samples: usize,
     relative_radius: f64,
@@ -211,23 +230,15 @@ pub fn test_poisson<F, I, T, A>(
         }
     }
 
+    if let Err(v) = poisson::verify::verify_separation(&vecs, radius, poisson_type) {
+        panic!(
+            "Poisson-disk distribution requirement not met while generating using the '{:?}' algorithm: {:?}",
+            algo, v
+        );
+    }
+
     let vecs = match poisson_type {
-        Perioditic => {
-            let mut vecs2 = vec![];
-            for n in 0..3i64.pow(dim as u32) {
-                let mut t = T::zero();
-                let mut div = n;
-                for i in 0..T::dimension() {
-                    let rem = div % 3;
-                    div /= 3;
-                    t[i] = NumCast::from(rem - 1).expect("Test offset value should convert to vector element type");
-                }
-                for v in &vecs {
-                    vecs2.push(*v + t);
-                }
-            }
-            vecs2
-        }
+        Perioditic => PointSet::new(vecs, radius, poisson_type).tile(&vec![3; dim]).0,
         Normal => vecs,
     };
 
@@ -0,0 +1,88 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect2 = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+mod helper;
+
+#[test]
+fn lattices_generate_legal_distributions() {
+    for ptype in [Type::Normal, Type::Perioditic] {
+        for jitter in [0.1, 0.5, 1.] {
+            let builder = Builder::<_, Vect2>::with_disk_radius(0.03, ptype).with_jitter(jitter);
+            let points = builder
+                .clone()
+                .build(SmallRng::seed_from_u64(1), algorithm::Triangular)
+                .generate();
+            helper::test_poisson(
+                points.into_iter(),
+                0.03,
+                ptype,
+                algorithm::Triangular,
+                false,
+            );
+            let points = builder
+                .build(SmallRng::seed_from_u64(1), algorithm::Hexagonal)
+                .generate();
+            helper::test_poisson(points.into_iter(), 0.03, ptype, algorithm::Hexagonal, false);
+        }
+        let points = Builder::<_, Vect3>::with_disk_radius(0.08, ptype)
+            .build(SmallRng::seed_from_u64(2), algorithm::Triangular)
+            .generate();
+        helper::test_poisson(
+            points.into_iter(),
+            0.08,
+            ptype,
+            algorithm::Triangular,
+            false,
+        );
+    }
+}
+
+#[test]
+fn triangular_lattice_packs_denser_than_poisson_disk() {
+    let builder = Builder::<_, Vect2>::with_disk_radius(0.02, Type::Perioditic).with_jitter(0.1);
+    let triangular = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(3), algorithm::Triangular)
+        .generate();
+    let hexagonal = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(3), algorithm::Hexagonal)
+        .generate();
+    let bridson = builder
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    assert!(triangular.len() > bridson.len());
+    let ratio = hexagonal.len() as f64 / triangular.len() as f64;
+    assert!((ratio - 2. / 3.).abs() < 0.02, "{}", ratio);
+}
+
+#[test]
+fn samples_stay_within_jitter_of_lattice() {
+    let points = Builder::<_, Vect2>::with_disk_radius(0.05, Type::Normal)
+        .with_jitter(0.)
+        .build(SmallRng::seed_from_u64(4), algorithm::Triangular)
+        .generate();
+    assert_eq!(Vect2::new(0., 0.), points[0]);
+    let row = 0.1 * 3f64.sqrt() / 2.;
+    for p in &points {
+        let rows = p.y / row;
+        assert!((rows - rows.round()).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn lattice_honours_start_points() {
+    let start = Vect2::new(0.33, 0.41);
+    let points = Builder::<_, Vect2>::with_disk_radius(0.03, Type::Normal)
+        .with_start_points(&[start], true)
+        .build(SmallRng::seed_from_u64(5), algorithm::Triangular)
+        .generate();
+    assert_eq!(start, points[0]);
+    helper::assert_legal_poisson(&points, 0.03, algorithm::Triangular);
+}
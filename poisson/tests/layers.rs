@@ -0,0 +1,54 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Layers, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn layers() -> Layers<f64, Vect> {
+    Layers::new()
+        .with_layer(Builder::with_disk_radius(0.15, Type::Perioditic))
+        .with_layer(Builder::with_disk_radius(0.05, Type::Perioditic))
+}
+
+#[test]
+fn disks_of_different_layers_do_not_overlap() {
+    let layers = layers()
+        .with_layer(Builder::with_disk_radius(0.01, Type::Perioditic))
+        .generate(SmallRng::seed_from_u64(2), algorithm::Ebeida);
+    assert_eq!(3, layers.len());
+    for (i, high) in layers.iter().enumerate() {
+        assert!(!high.is_empty());
+        for low in &layers[i + 1..] {
+            for p in high {
+                for q in low {
+                    let d = (p - q).map(|c| c - c.round()).norm();
+                    assert!(d >= high.radius() + low.radius());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn adding_layer_keeps_earlier_layers() {
+    let two = layers().generate(SmallRng::seed_from_u64(4), algorithm::Bridson);
+    let three = layers()
+        .with_layer(Builder::with_disk_radius(0.02, Type::Perioditic))
+        .generate(SmallRng::seed_from_u64(4), algorithm::Bridson);
+    assert_eq!(two[..], three[..2]);
+}
+
+#[test]
+#[should_panic]
+fn layers_need_the_same_domain() {
+    layers().with_layer(Builder::with_disk_radius(0.02, Type::Normal));
+}
+
+#[test]
+fn later_layers_grow_around_earlier_ones_with_bridson() {
+    let layers = layers().generate(SmallRng::seed_from_u64(6), algorithm::Bridson);
+    // The lower layer fills the space left by the higher one instead of stopping at its samples.
+    assert!(layers[1].len() > 3 * layers[0].len());
+}
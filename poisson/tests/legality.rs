@@ -0,0 +1,160 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, LegalityCheck, Rejection, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+const RADIUS: f64 = 0.03;
+
+/// Samples on different sides of the wall at x = 0.5 can't see each other so they have to be farther apart.
+/// The grid cells around a candidate reach 2√2 radii away in two dimensions.
+fn hidden(candidate: &Vect, neighbours: &[Vect]) -> bool {
+    neighbours
+        .iter()
+        .all(|p| (p.x < 0.5) == (candidate.x < 0.5) || (p - candidate).norm() >= 2.5 * RADIUS)
+}
+
+fn left(candidate: &Vect, _: &[Vect]) -> bool {
+    candidate.x < 0.75
+}
+
+fn check<A>(algo: A)
+where
+    A: algorithm::Creator<f64, Vect> + Copy,
+{
+    for seed in 0..10 {
+        let samples = Builder::<_, Vect>::with_disk_radius(RADIUS, Type::Normal)
+            .with_legality_check(hidden)
+            .with_legality_check(left)
+            .build(SmallRng::seed_from_u64(seed), algo)
+            .generate();
+        assert!(!samples.is_empty());
+        for (i, a) in samples.iter().enumerate() {
+            assert!(a.x < 0.75);
+            for b in &samples[i + 1..] {
+                let d = (a - b).norm();
+                assert!(d >= 2. * RADIUS);
+                if (a.x < 0.5) != (b.x < 0.5) {
+                    assert!(d >= 2.5 * RADIUS);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn ebeida_passes_legality_checks() {
+    check(algorithm::Ebeida);
+}
+
+#[test]
+fn bridson_passes_legality_checks() {
+    check(algorithm::Bridson);
+}
+
+#[test]
+fn dart_throwing_passes_legality_checks() {
+    check(algorithm::DartThrowing);
+}
+
+#[test]
+fn sparse_passes_legality_checks() {
+    check(algorithm::Sparse);
+}
+
+#[test]
+fn explain_reports_failed_check() {
+    let start = [Vect::new(0.45, 0.5)];
+    let generator = Builder::<_, Vect>::with_disk_radius(RADIUS, Type::Normal)
+        .with_start_points(&start, false)
+        .with_legality_check(hidden)
+        .with_legality_check(left)
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson);
+    assert_eq!(
+        Some(Rejection::Illegal { check: 0 }),
+        generator.explain(Vect::new(0.52, 0.5))
+    );
+    assert_eq!(
+        Some(Rejection::Illegal { check: 1 }),
+        generator.explain(Vect::new(0.8, 0.5))
+    );
+    assert!(matches!(
+        generator.explain(Vect::new(0.46, 0.5)),
+        Some(Rejection::Conflict { .. })
+    ));
+    assert_eq!(None, generator.explain(Vect::new(0.3, 0.5)));
+    let iter = generator.into_iter();
+    assert!(!iter.stays_legal(Vect::new(0.52, 0.5)));
+    assert!(iter.stays_legal(Vect::new(0.3, 0.5)));
+}
+
+#[test]
+fn legality_checks_are_kept_by_builder() {
+    let builder = Builder::<_, Vect>::with_disk_radius(RADIUS, Type::Normal);
+    assert_eq!(0, builder.legality_checks().count());
+    let builder = builder.with_legality_check(left).with_legality_check(hidden);
+    assert_eq!(2, builder.legality_checks().count());
+    assert!(!builder
+        .legality_checks()
+        .next()
+        .unwrap()
+        .is_legal(&Vect::new(0.8, 0.5), &[]));
+    assert_eq!(builder, builder.clone());
+}
+
+/// Same as `left`, but tells that the candidates it rejects form a convex region.
+struct Left;
+
+impl LegalityCheck<f64, Vect> for Left {
+    fn is_legal(&self, candidate: &Vect, _: &[Vect]) -> bool {
+        candidate.x < 0.75
+    }
+
+    fn rejects_boxes(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn ebeida_drops_cells_box_rejecting_checks_reject() {
+    let rejected = |builder: Builder<f64, Vect>| {
+        let mut iter = builder
+            .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+            .into_iter();
+        let samples = iter.by_ref().collect::<Vec<_>>();
+        assert!(!samples.is_empty());
+        assert!(samples.iter().all(|s| s.x < 0.75));
+        iter.stats().rejected
+    };
+    let builder = Builder::<_, Vect>::with_disk_radius(RADIUS, Type::Normal);
+    let boxed = rejected(builder.clone().with_legality_check(Left));
+    // Darts are thrown into the cells past the edge unless the check tells they can't have legal candidates.
+    let unknown = rejected(builder.with_legality_check(left));
+    assert!(boxed * 10 < unknown);
+}
+
+/// Candidates are legal only on small spots around the points of a lattice, so the rejected candidates don't form a
+/// convex region and cells whose corners are all rejected can still have a spot inside them.
+fn spots(candidate: &Vect, _: &[Vect]) -> bool {
+    candidate.map(|x| x - (x * 10.).round() / 10.).norm() < 0.01
+}
+
+#[test]
+fn ebeida_fills_every_legal_gap_of_non_convex_checks() {
+    for seed in 0..5 {
+        let samples = Builder::<_, Vect>::with_disk_radius(RADIUS, Type::Normal)
+            .with_legality_check(spots)
+            .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+            .generate();
+        assert!(samples.iter().all(|s| spots(s, &[])));
+        // Spots are farther apart than the disks, so each of them inside the domain has room for a sample.
+        for x in 1..10 {
+            for y in 1..10 {
+                let spot = Vect::new(x as f64 / 10., y as f64 / 10.);
+                assert!(samples.iter().any(|s| (s - spot).norm() < 0.01));
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Manifest, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn manifest_round_trips_and_verifies() {
+    let generator = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(9), algorithm::Ebeida);
+    let set = generator.generate_set();
+    let manifest = set.manifest().with_seed(9);
+    assert_eq!(Some("Ebeida"), manifest.algorithm.as_deref());
+    assert_eq!(set.len(), manifest.count);
+    assert_eq!(2, manifest.dimension);
+
+    let parsed: Manifest = manifest.to_string().parse().unwrap();
+    assert_eq!(manifest, parsed);
+    assert!(generator.generate_set().verify_manifest(&parsed));
+}
+
+#[test]
+fn manifest_detects_different_distribution() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal);
+    let set = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate_set();
+    let other = builder
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate_set();
+    assert!(!other.verify_manifest(&set.manifest()));
+    let moved = set.clone().retain_far_from_boundary(0.1);
+    assert!(!moved.verify_manifest(&set.manifest()));
+}
+
+#[test]
+fn invalid_manifest_fails_to_parse() {
+    assert!("poisson=0.11.0 radius=0.1".parse::<Manifest>().is_err());
+    assert!(
+        "radius=0.1 type=normal dimension=2 count=1 hash=zz poisson=1"
+            .parse::<Manifest>()
+            .is_err()
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+#[test]
+fn generation_stops_at_max_points() {
+    let builder = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal).with_max_points(50);
+    let radius = builder.radius();
+    let samples = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_eq!(50, samples.len());
+    helper::test_poisson(
+        samples.into_iter(),
+        radius,
+        Type::Normal,
+        algorithm::Ebeida,
+        false,
+    );
+    let samples = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate();
+    assert_eq!(50, samples.len());
+    let iter = builder
+        .build(SmallRng::seed_from_u64(1), algorithm::DartThrowing)
+        .into_iter();
+    assert!(iter.size_hint().1.unwrap() <= 50);
+    assert_eq!(50, iter.count());
+}
+
+#[test]
+fn max_points_counts_start_points() {
+    let start = [Vect::new(0.5, 0.5), Vect::new(0.1, 0.1)];
+    let samples = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal)
+        .with_start_points(&start, true)
+        .with_max_points(1)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_eq!(&start[..1], &samples[..]);
+}
+
+#[test]
+fn max_points_larger_than_maximal_changes_nothing() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic);
+    let full = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate();
+    let limited = builder
+        .with_max_points(10_000)
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate();
+    assert_eq!(full, limited);
+}
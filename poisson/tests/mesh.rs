@@ -0,0 +1,45 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn boundary_samples_form_closed_loop() {
+    let set = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal)
+        .with_boundary_sampling(true)
+        .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+        .generate_set();
+    let seeds = set.mesh_seeds();
+    // Boundary sampling already places samples in the corners.
+    assert_eq!(set.points(), &seeds.vertices[..]);
+    let on_edge = |v: &Vect| v.iter().any(|&x| x == 0. || x > 1. - 1e-9);
+    let boundary = seeds.vertices.iter().filter(|v| on_edge(v)).count();
+    assert_eq!(boundary, seeds.segments.len());
+    for (n, s) in seeds.segments.iter().enumerate() {
+        let next = seeds.segments[(n + 1) % seeds.segments.len()];
+        assert_eq!(s[1], next[0]);
+        assert!(on_edge(&seeds.vertices[s[0]]));
+        // Consecutive boundary samples are at most four radii apart as the boundary is maximal.
+        assert!((seeds.vertices[s[0]] - seeds.vertices[s[1]]).norm() <= 4. * set.radius());
+    }
+}
+
+#[test]
+fn missing_corners_are_added() {
+    let set = Builder::<_, Vect>::with_samples(50, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(4), algorithm::Bridson)
+        .generate_set();
+    let seeds = set.mesh_seeds();
+    assert!(seeds.vertices.len() >= set.len());
+    assert!(seeds.segments.len() >= 4);
+    let poly = seeds.to_poly();
+    let mut lines = poly.lines();
+    assert_eq!(
+        Some(format!("{} 2 0 1", seeds.vertices.len()).as_str()),
+        lines.next()
+    );
+    assert_eq!(Some("0"), poly.lines().last());
+}
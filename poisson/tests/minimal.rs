@@ -0,0 +1,60 @@
+#[cfg(feature = "full")]
+use poisson::array::Array2;
+#[cfg(feature = "full")]
+use poisson::{algorithm, Builder};
+use poisson::{minimal, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+fn check<const N: usize>(radius: f64, ptype: Type) -> usize {
+    let samples: Vec<[f64; N]> =
+        minimal::generate(radius, ptype, &mut SmallRng::seed_from_u64(1));
+    assert!(!samples.is_empty());
+    for (i, a) in samples.iter().enumerate() {
+        assert!(a.iter().all(|&c| (0. ..1.).contains(&c)));
+        for b in &samples[i + 1..] {
+            let sqdist = a
+                .iter()
+                .zip(b)
+                .map(|(a, b)| {
+                    let mut d = a - b;
+                    if ptype == Type::Perioditic {
+                        d -= d.round();
+                    }
+                    d * d
+                })
+                .sum::<f64>();
+            assert!(sqdist >= 4. * radius * radius, "{:?} and {:?} are too close", a, b);
+        }
+    }
+    samples.len()
+}
+
+#[test]
+fn minimal_samples_are_spaced() {
+    check::<1>(0.01, Type::Normal);
+    check::<2>(0.02, Type::Normal);
+    check::<2>(0.02, Type::Perioditic);
+    check::<3>(0.08, Type::Normal);
+    check::<3>(0.08, Type::Perioditic);
+    check::<2>(0.8, Type::Perioditic);
+}
+
+#[test]
+#[cfg(feature = "full")]
+fn minimal_is_as_dense_as_bridson() {
+    let full = Builder::<_, Array2<f64>>::with_disk_radius(0.02, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate()
+        .len();
+    let minimal = check::<2>(0.02, Type::Normal);
+    assert!(minimal.abs_diff(full) * 20 < full, "{} vs {}", minimal, full);
+}
+
+#[test]
+fn minimal_is_reproducible() {
+    let generate = || {
+        minimal::generate::<f32, _, 2>(0.03, Type::Perioditic, &mut SmallRng::seed_from_u64(2))
+    };
+    assert_eq!(generate(), generate());
+}
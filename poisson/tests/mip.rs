@@ -0,0 +1,62 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, MipChain, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn chain(seed: u64) -> MipChain<f64, Vect> {
+    let builder = Builder::<_, Vect>::with_samples(800, 0.8, Type::Perioditic);
+    MipChain::generate(&builder, 5, SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+}
+
+#[test]
+fn levels_are_nested_prefixes_with_half_the_samples() {
+    for seed in 0..3 {
+        let chain = chain(seed);
+        assert_eq!(5, chain.len());
+        for pair in chain.levels().windows(2) {
+            let (finer, coarser) = (&pair[0], &pair[1]);
+            assert_eq!(&finer.points()[..coarser.len()], coarser.points());
+            let ratio = coarser.len() as f64 / finer.len() as f64;
+            assert!((0.4..=0.6).contains(&ratio), "{} of {}", coarser.len(), finer.len());
+            assert!(coarser.radius() > finer.radius());
+        }
+    }
+}
+
+#[test]
+fn levels_keep_their_separation_across_the_period() {
+    for level in chain(7).levels() {
+        for (i, a) in level.iter().enumerate() {
+            for b in &level.points()[i + 1..] {
+                assert!(level.distance(a, b) >= level.diameter());
+            }
+        }
+    }
+}
+
+#[test]
+fn texture_array_pads_layers_with_nan() {
+    let chain = chain(2);
+    let texture = chain.to_texture_array();
+    let width = chain.levels()[0].len();
+    assert_eq!(4 * 2 * width * chain.len(), texture.len());
+    let texel = |layer: usize, i: usize, n: usize| {
+        let at = 4 * (2 * (layer * width + i) + n);
+        f32::from_le_bytes([texture[at], texture[at + 1], texture[at + 2], texture[at + 3]])
+    };
+    let last = chain.len() - 1;
+    let coarsest = &chain.levels()[last];
+    assert_eq!(coarsest.points()[0].y as f32, texel(last, 0, 1));
+    assert!(texel(last, coarsest.len(), 0).is_nan());
+    assert!(!texel(0, width - 1, 1).is_nan());
+}
+
+#[test]
+#[should_panic(expected = "Only perioditic distributions")]
+fn normal_distributions_are_rejected() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal);
+    MipChain::generate(&builder, 2, SmallRng::seed_from_u64(0), algorithm::Bridson);
+}
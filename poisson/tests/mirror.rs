@@ -0,0 +1,38 @@
+#![cfg(any(feature = "cgmath", feature = "euclid"))]
+
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+#[cfg(feature = "cgmath")]
+#[test]
+fn cgmath_mirrors_generate_valid_distributions() {
+    use poisson::mirror::{CgVector2, CgVector3};
+    let samples = Builder::<_, CgVector2<f64>>::with_disk_radius(0.1, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate();
+    let vectors = CgVector2::into_vec(samples.clone());
+    assert_eq!(samples.as_slice(), CgVector2::from_slice(&vectors));
+    assert!(poisson::verify::verify_separation(&samples, 0.1, Type::Normal).is_ok());
+    let samples = Builder::<_, CgVector3<f32>>::with_disk_radius(0.2, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+        .generate();
+    assert!(poisson::verify::verify_separation(&samples, 0.2, Type::Perioditic).is_ok());
+}
+
+#[cfg(feature = "euclid")]
+#[test]
+fn euclid_mirrors_generate_valid_distributions() {
+    use poisson::mirror::{EuVector2, EuVector3};
+    let samples = Builder::<_, EuVector2<f64>>::with_disk_radius(0.1, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    let vectors = EuVector2::as_slice(&samples);
+    assert_eq!(samples.len(), vectors.len());
+    assert!(vectors.iter().zip(&samples).all(|(v, s)| *v == s.0));
+    assert!(poisson::verify::verify_separation(&samples, 0.1, Type::Perioditic).is_ok());
+    let samples = Builder::<_, EuVector3<f32>>::with_disk_radius(0.2, Type::Normal)
+        .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+        .generate();
+    assert!(poisson::verify::verify_separation(&samples, 0.2, Type::Normal).is_ok());
+}
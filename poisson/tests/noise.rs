@@ -0,0 +1,58 @@
+#![cfg(feature = "noise")]
+
+use poisson::noise::Fbm;
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn noise_is_deterministic_and_bounded() {
+    let fbm = Fbm::new(3).with_octaves(5);
+    let values = (0..1000)
+        .map(|i| {
+            let v = Vect::new((i % 37) as f64 / 37., (i / 37) as f64 / 28.);
+            let value: f64 = fbm.get(&v);
+            assert_eq!(value, Fbm::new(3).with_octaves(5).get(&v));
+            value
+        })
+        .collect::<Vec<_>>();
+    assert!(values.iter().all(|&v| (0. ..=1.).contains(&v)));
+    let min = values.iter().cloned().fold(1., f64::min);
+    let max = values.iter().cloned().fold(0., f64::max);
+    assert!(max - min > 0.3, "Noise should vary: {}..{}", min, max);
+    let other: f64 = Fbm::new(4).with_octaves(5).get(&Vect::new(0.3, 0.6));
+    assert_ne!(other, fbm.get(&Vect::new(0.3, 0.6)));
+}
+
+#[test]
+fn noise_density_is_denser_where_noise_is_low() {
+    let fbm = Fbm::new(7).with_frequency(2.);
+    let (r_min, r_max) = (0.005, 0.02);
+    let points = Builder::<_, Vect>::with_disk_radius(r_max, Type::Normal)
+        .with_noise_density(fbm, r_min, r_max)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    let nearest = |p: &Vect| {
+        points
+            .iter()
+            .filter(|q| *q != p)
+            .map(|q| (q - p).norm())
+            .fold(f64::INFINITY, f64::min)
+    };
+    let (mut low, mut high) = (vec![], vec![]);
+    for p in &points {
+        assert!(nearest(p) >= 2. * r_min);
+        let value: f64 = fbm.get(p);
+        if value < 0.4 {
+            low.push(nearest(p));
+        } else if value > 0.6 {
+            high.push(nearest(p));
+        }
+    }
+    let mean = |d: &[f64]| d.iter().sum::<f64>() / d.len() as f64;
+    assert!(!low.is_empty() && !high.is_empty());
+    assert!(mean(&low) < mean(&high));
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Domain, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect2 = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+#[test]
+fn occupancy_counts_every_sample_once() {
+    let generator = Builder::<_, Vect3>::with_disk_radius(0.1, Type::Normal)
+        .with_cell_scale(2.)
+        .build(SmallRng::seed_from_u64(5), algorithm::Bridson);
+    let samples = generator.generate();
+    let image = generator.grid_occupancy_image();
+    let side = (1. / image.cell()) as usize;
+    assert_eq!(&[side, side, side], image.cells());
+    assert_eq!(image.cells()[2], image.slices());
+    let mut total = 0;
+    for slice in 0..image.slices() {
+        total += image.slice(slice).iter().sum::<u32>();
+    }
+    assert_eq!(samples.len(), total as usize);
+    for p in &samples {
+        let cell = p.map(|c| (c * side as f64) as usize);
+        assert!(image.get(cell.x, cell.y, cell.z) > 0);
+    }
+    assert!(image.max() > 1);
+}
+
+#[test]
+fn inscribed_cells_hold_at_most_one_sample() {
+    let image = Builder::<_, Vect2>::with_samples(300, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .grid_occupancy_image();
+    assert_eq!(1, image.slices());
+    assert_eq!(1, image.max());
+}
+
+#[test]
+fn occupancy_follows_extent_of_domain() {
+    let image = Builder::<_, Vect2>::with_disk_radius(0.05, Domain::open().with_extent(1, 0.5))
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .grid_occupancy_image();
+    assert_eq!(image.width(), 2 * image.height());
+    assert!(image.slice(0).iter().any(|&c| c > 0));
+}
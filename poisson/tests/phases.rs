@@ -0,0 +1,82 @@
+#![cfg(feature = "full")]
+use poisson::algorithm::{self, Algorithm, EbeidaAlgo};
+use poisson::{Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+#[test]
+fn phases_generate_same_samples_as_iterator() {
+    for &ptype in &[Type::Normal, Type::Perioditic] {
+        let mut poisson = Builder::<_, Vect>::with_disk_radius(0.02, ptype);
+        let expected = poisson
+            .clone()
+            .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+            .generate();
+        let mut rng = SmallRng::seed_from_u64(4);
+        let mut algo = EbeidaAlgo::new(&poisson);
+        let mut samples = vec![];
+        while algo.level() < algo.max_level() && algo.active_cells() > 0 {
+            samples.extend(std::iter::from_fn(|| algo.throw(&poisson, &mut rng)));
+            assert_eq!(0, algo.throws_left() * algo.active_cells());
+            if algo.active_cells() > 0 {
+                algo.subdivide(&poisson);
+            }
+        }
+        samples.extend(std::iter::from_fn(|| algo.next(&mut poisson, &mut rng)));
+        assert_eq!(expected, samples);
+        assert_eq!(algo.level(), algo.stats().depth);
+    }
+}
+
+#[test]
+fn stopping_after_levels_is_legal() {
+    let poisson = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal);
+    let mut counts = vec![];
+    for levels in 0..4 {
+        let mut rng = SmallRng::seed_from_u64(5);
+        let mut algo = EbeidaAlgo::new(&poisson);
+        let mut samples = vec![];
+        loop {
+            samples.extend(std::iter::from_fn(|| algo.throw(&poisson, &mut rng)));
+            if algo.level() == levels {
+                break;
+            }
+            algo.subdivide(&poisson);
+        }
+        counts.push(samples.len());
+        helper::test_poisson(samples.into_iter(), 0.02, Type::Normal, algorithm::Ebeida, false);
+    }
+    assert!(counts.windows(2).all(|w| w[0] <= w[1]), "{:?}", counts);
+    assert!(counts[0] < counts[3], "{:?}", counts);
+}
+
+#[test]
+fn max_depth_resolves_rest_with_dart_throwing() {
+    // Samples fill in around a lattice of start points.
+    let start = (0..20)
+        .flat_map(|i| (0..20).map(move |j| Vect::new(i as f64 * 0.05 + 0.01, j as f64 * 0.05)))
+        .collect::<Vec<_>>();
+    let full = Builder::<_, Vect>::with_disk_radius(0.01, Type::Perioditic)
+        .with_start_points(&start, true);
+    let count = full
+        .clone()
+        .build(SmallRng::seed_from_u64(6), algorithm::Ebeida)
+        .generate()
+        .len();
+    for depth in 0..3 {
+        let capped = full.clone().with_max_depth(depth);
+        assert_eq!(Some(depth), capped.max_depth());
+        let mut iter = capped
+            .build(SmallRng::seed_from_u64(6), algorithm::Ebeida)
+            .into_iter();
+        let samples = iter.by_ref().collect::<Vec<_>>();
+        assert_eq!(depth, iter.stats().depth);
+        assert!(count * 9 / 10 <= samples.len() && samples.len() <= count);
+        helper::test_poisson(samples.into_iter(), 0.01, Type::Perioditic, algorithm::Ebeida, false);
+    }
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, PrefillPolicy, Precision, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn check<A>(builder: Builder<f64, Vect>, algo: A)
+where
+    A: algorithm::Creator<f64, Vect>,
+    A::Algo: Clone,
+{
+    let plan = builder.clone().plan(algo);
+    for seed in 0..5 {
+        let expected = builder
+            .clone()
+            .build(SmallRng::seed_from_u64(seed), algo)
+            .generate();
+        let samples = plan.execute(SmallRng::seed_from_u64(seed)).collect::<Vec<_>>();
+        assert_eq!(expected, samples);
+    }
+}
+
+#[test]
+fn execute_generates_same_samples_as_build() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.04, Type::Normal);
+    check(builder.clone(), algorithm::Ebeida);
+    check(builder.clone(), algorithm::Bridson);
+    check(builder.clone(), algorithm::DartThrowing);
+    let start = [Vect::new(0.5, 0.5), Vect::new(0.52, 0.5)];
+    check(
+        builder
+            .clone()
+            .with_start_points(&start, true)
+            .with_prefill_policy(PrefillPolicy::RejectConflicting),
+        algorithm::Bridson,
+    );
+    check(builder.with_boundary_sampling(true), algorithm::Ebeida);
+}
+
+#[test]
+fn plan_tells_grid_and_bounds() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.04, Type::Perioditic);
+    let plan = builder.clone().plan(algorithm::Ebeida);
+    let generator = builder.clone().build(SmallRng::seed_from_u64(1), algorithm::Ebeida);
+    let image = generator.grid_occupancy_image();
+    assert_eq!(image.width(), plan.grid_side());
+    assert_eq!(image.width() * image.height(), plan.grid_cells());
+    assert_eq!(generator.into_iter().size_hint(), plan.size_hint());
+    let samples = plan.execute(SmallRng::seed_from_u64(1)).count();
+    assert!(plan.size_hint().1.is_some_and(|upper| samples <= upper));
+    let single = builder.with_precision(Precision::Single).plan(algorithm::Ebeida);
+    assert!(single.memory() < plan.memory());
+    assert!(plan.grid_cells() * 8 < plan.memory());
+}
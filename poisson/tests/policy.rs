@@ -0,0 +1,52 @@
+#![cfg(feature = "full")]
+use poisson::algorithm::{self, ActivePolicy};
+use poisson::{Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+fn test_policy(policy: ActivePolicy<Vect>, poisson_type: Type) {
+    for seed in 0..50 {
+        let poisson = Builder::<_, Vect>::with_samples(100, 0.8, poisson_type)
+            .with_active_policy(policy.clone())
+            .build(SmallRng::seed_from_u64(seed), algorithm::Bridson);
+        let radius = poisson.radius();
+        let samples = poisson.generate().into_iter();
+        helper::test_poisson(samples, radius, poisson_type, algorithm::Bridson, false);
+    }
+}
+
+#[test]
+fn fifo_policy_is_valid() {
+    test_policy(ActivePolicy::Fifo, Type::Normal);
+    test_policy(ActivePolicy::Fifo, Type::Perioditic);
+}
+
+#[test]
+fn lifo_policy_is_valid() {
+    test_policy(ActivePolicy::Lifo, Type::Normal);
+    test_policy(ActivePolicy::Lifo, Type::Perioditic);
+}
+
+#[test]
+fn nearest_policy_is_valid() {
+    test_policy(ActivePolicy::Nearest(Vect::new(0.5, 0.5)), Type::Normal);
+    test_policy(ActivePolicy::Nearest(Vect::new(0.5, 0.5)), Type::Perioditic);
+}
+
+#[test]
+fn nearest_policy_grows_towards_target() {
+    let target = Vect::new(0.5, 0.5);
+    let samples = Builder::<_, Vect>::with_samples(400, 0.8, Type::Normal)
+        .with_active_policy(ActivePolicy::Nearest(target))
+        .build(SmallRng::seed_from_u64(42), algorithm::Bridson)
+        .generate();
+    let quarter = samples.len() / 4;
+    let mean = |s: &[Vect]| s.iter().map(|v| (v - target).norm()).sum::<f64>() / s.len() as f64;
+    // After the initial approach the samples closest to the target get generated before the rest.
+    assert!(mean(&samples[quarter..2 * quarter]) < mean(&samples[3 * quarter..]));
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Precision, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector3<f64>;
+
+fn check_separation(precision: Precision, epsilon: f64) {
+    let slack = 3f64.sqrt() * epsilon;
+    for &ptype in &[Type::Normal, Type::Perioditic] {
+        for seed in 0..5 {
+            let builder =
+                Builder::<_, Vect>::with_samples(300, 0.8, ptype).with_precision(precision);
+            let radius = builder.radius();
+            let samples = builder
+                .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+                .generate();
+            for (i, a) in samples.iter().enumerate() {
+                for b in &samples[i + 1..] {
+                    let mut d = b - a;
+                    if ptype == Type::Perioditic {
+                        d = d.map(|c| c - c.round());
+                    }
+                    assert!(d.norm() >= 2. * radius - slack);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn single_precision_storage_keeps_separation() {
+    check_separation(Precision::Single, 2f64.powi(-25));
+}
+
+#[cfg(feature = "half")]
+#[test]
+fn half_precision_storage_keeps_separation() {
+    check_separation(Precision::Half, 2f64.powi(-12));
+}
+
+#[test]
+fn single_precision_storage_is_lossless_for_f32() {
+    let builder = Builder::<_, na::Vector2<f32>>::with_samples(100, 0.8, Type::Normal);
+    let full = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    let single = builder
+        .with_precision(Precision::Single)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate();
+    assert_eq!(full, single);
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Budget, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn preview_matches_generated_samples() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson);
+    let preview = generator.preview_next(20);
+    assert_eq!(20, preview.len());
+    assert_eq!(generator.generate()[..20], preview[..]);
+}
+
+#[test]
+fn preview_does_not_advance_generation() {
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.05, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+        .into_iter();
+    iter.step(Budget::Points(10));
+    let preview = iter.preview_next(5);
+    assert_eq!(preview, iter.preview_next(5));
+    assert_eq!(preview, iter.step(Budget::Points(5)));
+}
+
+#[test]
+fn preview_is_cut_short_by_end_of_generation() {
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.1, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .into_iter();
+    let rest = iter.clone().collect::<Vec<_>>();
+    assert_eq!(rest, iter.preview_next(rest.len() + 10));
+    iter.by_ref().for_each(drop);
+    assert!(iter.preview_next(10).is_empty());
+}
+
+#[test]
+fn preview_follows_restricted_samples() {
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(4), algorithm::DartThrowing)
+        .into_iter();
+    iter.step(Budget::Points(5));
+    let preview = iter.preview_next(3);
+    iter.restrict(preview[0]);
+    let after = iter.preview_next(3);
+    assert!(!after.contains(&preview[0]));
+    assert_eq!(after, iter.step(Budget::Points(3)));
+}
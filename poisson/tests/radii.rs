@@ -0,0 +1,112 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, RadiusError, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+const RADII: [f64; 2] = [0.06, 0.03];
+
+/// Squared distance scaled by the diameters along each axis, which is less than 1 for conflicting samples.
+fn scaled(a: &Vect, b: &Vect, ptype: Type) -> f64 {
+    let offsets: &[f64] = match ptype {
+        Type::Normal => &[0.],
+        Type::Perioditic => &[-1., 0., 1.],
+    };
+    let d = b - a;
+    let mut min = f64::MAX;
+    for &x in offsets {
+        for &y in offsets {
+            let dx = (d.x + x) / (2. * RADII[0]);
+            let dy = (d.y + y) / (2. * RADII[1]);
+            min = min.min(dx * dx + dy * dy);
+        }
+    }
+    min
+}
+
+fn check<A>(ptype: Type, algo: A)
+where
+    A: algorithm::Creator<f64, Vect> + Copy,
+{
+    for seed in 0..20 {
+        let samples = Builder::<_, Vect>::with_radii(Vect::from(RADII), ptype)
+            .build(SmallRng::seed_from_u64(seed), algo)
+            .generate();
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[i + 1..] {
+                assert!(scaled(a, b, ptype) >= 1.);
+            }
+        }
+        // Samples should make use of the shorter radius along the second axis.
+        let isotropic = Builder::<_, Vect>::with_disk_radius(RADII[0], ptype)
+            .build(SmallRng::seed_from_u64(seed), algo)
+            .generate();
+        assert!(isotropic.len() < samples.len());
+    }
+}
+
+#[test]
+fn ebeida_with_radii_is_valid() {
+    check(Type::Normal, algorithm::Ebeida);
+    check(Type::Perioditic, algorithm::Ebeida);
+}
+
+#[test]
+fn bridson_with_radii_is_valid() {
+    check(Type::Normal, algorithm::Bridson);
+    check(Type::Perioditic, algorithm::Bridson);
+}
+
+#[test]
+fn dart_throwing_with_radii_is_valid() {
+    check(Type::Normal, algorithm::DartThrowing);
+    check(Type::Perioditic, algorithm::DartThrowing);
+}
+
+#[test]
+fn ebeida_with_radii_leaves_little_room() {
+    let mut rng = SmallRng::seed_from_u64(5);
+    for seed in 0..5 {
+        let samples = Builder::<_, Vect>::with_radii(Vect::from(RADII), Type::Perioditic)
+            .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+            .generate();
+        let free = (0..10000)
+            .map(|_| Vect::new(rng.random(), rng.random()))
+            .filter(|p| samples.iter().all(|s| scaled(s, p, Type::Perioditic) >= 1.))
+            .count();
+        assert!(free < 500);
+    }
+}
+
+#[test]
+fn radii_are_kept_by_builder() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal);
+    assert!(builder.radii().is_none());
+    let builder = Builder::<_, Vect>::with_radii(Vect::from(RADII), Type::Normal);
+    assert_eq!(Some(&Vect::from(RADII)), builder.radii());
+    assert_eq!(RADII[0], builder.radius());
+}
+
+#[test]
+#[should_panic]
+fn radii_should_be_positive() {
+    let _ = Builder::<_, Vect>::with_radii(Vect::new(0.05, 0.), Type::Normal);
+}
+
+#[test]
+fn invalid_radii_are_errors() {
+    let zero = Builder::<_, Vect>::try_with_radii(Vect::new(0.05, 0.), Type::Normal);
+    assert_eq!(Some(RadiusError::NonPositiveRadius(0.)), zero.err());
+    let negative = Builder::<_, Vect>::try_with_radii(Vect::new(-0.05, 0.03), Type::Normal);
+    assert_eq!(Some(RadiusError::NonPositiveRadius(-0.05)), negative.err());
+    // Largest radius would skip NaN, so every radius is checked before it is picked.
+    let nan = Builder::<_, Vect>::try_with_radii(Vect::new(0.05, f64::NAN), Type::Normal);
+    assert!(matches!(nan.err(), Some(RadiusError::NotFinite(_))));
+    let infinite =
+        Builder::<_, Vect>::try_with_radii(Vect::new(f64::INFINITY, 0.03), Type::Normal);
+    assert_eq!(Some(RadiusError::NotFinite(f64::INFINITY)), infinite.err());
+    let radii = Builder::<_, Vect>::try_with_radii(Vect::from(RADII), Type::Normal);
+    assert_eq!(Some(&Vect::from(RADII)), radii.unwrap().radii());
+}
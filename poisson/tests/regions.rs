@@ -0,0 +1,113 @@
+#![cfg(feature = "full")]
+use poisson::field::{raster_labels, tone_map, Spacing, ToneMapping};
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+const RADII: [f64; 2] = [0.01, 0.03];
+
+fn label(v: &Vect) -> usize {
+    if v.x < 0.5 {
+        0
+    } else {
+        1
+    }
+}
+
+fn check<A>(spacing: Spacing, algo: A)
+where
+    A: algorithm::Creator<f64, Vect> + Copy,
+{
+    for &ptype in &[Type::Normal, Type::Perioditic] {
+        let samples = Builder::<_, Vect>::with_disk_radius(0.03, ptype)
+            .with_regions(label, &RADII, spacing)
+            .build(SmallRng::seed_from_u64(2), algo)
+            .generate();
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[i + 1..] {
+                let mut d = b - a;
+                if ptype == Type::Perioditic {
+                    d = d.map(|c| c - c.round());
+                }
+                let min = spacing.distance(RADII[label(a)], RADII[label(b)]);
+                assert!(d.norm() >= min, "{:?} and {:?} are too close", a, b);
+            }
+        }
+        let dense = samples.iter().filter(|v| label(v) == 0).count();
+        let sparse = samples.len() - dense;
+        assert!(dense > 4 * sparse, "{} vs {}", dense, sparse);
+    }
+}
+
+#[test]
+fn regions_with_ebeida_are_valid() {
+    check(Spacing::Sum, algorithm::Ebeida);
+    check(Spacing::Larger, algorithm::Ebeida);
+}
+
+#[test]
+fn regions_with_bridson_are_valid() {
+    check(Spacing::Sum, algorithm::Bridson);
+    check(Spacing::Smaller, algorithm::Bridson);
+}
+
+#[test]
+fn regions_with_dart_throwing_are_valid() {
+    check(Spacing::Sum, algorithm::DartThrowing);
+}
+
+#[test]
+fn raster_labels_index_first_axis_fastest() {
+    let labels = raster_labels::<f64, Vect>(vec![0, 1, 2, 3, 4, 5], &[3, 2]);
+    assert_eq!(0, labels(&Vect::new(0.1, 0.1)));
+    assert_eq!(2, labels(&Vect::new(0.9, 0.1)));
+    assert_eq!(4, labels(&Vect::new(0.5, 0.9)));
+}
+
+#[test]
+#[should_panic]
+fn region_radius_larger_than_builder_radius_fails() {
+    let _ = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal).with_regions(
+        label,
+        &RADII,
+        Spacing::Sum,
+    );
+}
+
+#[test]
+fn tone_mappings_map_values() {
+    let values = [0., 0.25, 0.25, 0.5, 0.75, 1.];
+    assert_eq!(values.to_vec(), tone_map(&values, ToneMapping::Linear));
+    assert_eq!(vec![0., 0.0625, 0.0625, 0.25, 0.5625, 1.], tone_map(&values, ToneMapping::Gamma(2.)));
+    assert_eq!(vec![0., 0.4, 0.4, 0.6, 0.8, 1.], tone_map(&values, ToneMapping::Equalized));
+    assert_eq!(vec![0.3; 4], tone_map(&[0.3; 4], ToneMapping::Equalized));
+}
+
+#[test]
+fn equalized_density_raster_spreads_radii() {
+    // Pale image where every pixel is in the upper fifth of the tones.
+    let values = (0..64).map(|i| 0.8 + 0.2 * (i % 8) as f64 / 7.).collect::<Vec<_>>();
+    let (r_min, r_max) = (0.005, 0.02);
+    let count = |mapping| {
+        let builder = Builder::<_, Vect>::with_disk_radius(r_max, Type::Normal)
+            .with_density_raster(&values, &[8, 8], r_min, r_max, mapping);
+        let regions = builder.regions().unwrap();
+        let radius = regions.radius_at(&Vect::new(0.01, 0.5));
+        assert!(regions.radius_at(&Vect::new(0.99, 0.5)) == r_max);
+        let samples = builder.build(SmallRng::seed_from_u64(1), algorithm::Ebeida).generate();
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[i + 1..] {
+                assert!((a - b).norm() >= 2. * r_min);
+            }
+        }
+        (radius, samples.len())
+    };
+    let (linear_radius, linear) = count(ToneMapping::Linear);
+    let (equalized_radius, equalized) = count(ToneMapping::Equalized);
+    assert!(linear_radius > 0.8 * r_max);
+    assert_eq!(r_min, equalized_radius);
+    assert!(linear < equalized, "{} vs {}", linear, equalized);
+}
@@ -1,3 +1,4 @@
+#![cfg(feature = "full")]
 extern crate nalgebra as na;
 
 use rand::{rngs::SmallRng, SeedableRng};
@@ -11,7 +12,7 @@ fn reproduce_issue_29() {
         0, 0,
     ];
     let rng = SmallRng::from_seed(seed);
-    Builder::<_, na::Vector2<f32>>::with_radius(0.004, Type::Normal)
+    Builder::<_, na::Vector2<f32>>::with_disk_radius(0.004, Type::Normal)
         .build(rng, algorithm::Bridson)
         .generate();
 }
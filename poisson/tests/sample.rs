@@ -0,0 +1,46 @@
+#![cfg(feature = "full")]
+use poisson::field::Spacing;
+use poisson::{algorithm, Builder, Sample, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn label(v: &Vect) -> usize {
+    if v.x < 0.5 {
+        0
+    } else {
+        1
+    }
+}
+
+#[test]
+fn samples_carry_radius_of_their_region() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+        .with_regions(label, &[0.01, 0.03], Spacing::Larger)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida);
+    let samples = generator.generate_samples();
+    assert!(!samples.is_empty());
+    for s in &samples {
+        let expected = if s.point().x < 0.5 { 0.01 } else { 0.03 };
+        assert_eq!(expected, s.radius());
+    }
+    assert_eq!(generator.generate(), Sample::into_points(samples));
+}
+
+#[test]
+fn point_set_samples_have_uniform_radius() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.05, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+        .generate_set();
+    let samples = set.samples();
+    assert!(samples.iter().all(|s| s.radius() == 0.05));
+    assert_eq!(set.points(), &Sample::into_points(samples)[..]);
+}
+
+#[test]
+#[should_panic]
+fn zero_radius_fails() {
+    let _ = Sample::new(Vect::new(0.5, 0.5), 0.);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Seed, SeedTree, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn children_are_stable_and_distinct() {
+    let tree = SeedTree::new(42);
+    assert_eq!(tree.child(3), SeedTree::new(42).child(3));
+    let seeds = (0..1000).map(|i| tree.child(i).seed()).collect::<Vec<_>>();
+    for (i, seed) in seeds.iter().enumerate() {
+        assert!(!seeds[i + 1..].contains(seed));
+    }
+    assert_ne!(tree.child(0), SeedTree::new(43).child(0));
+    assert_ne!(tree.child(0).child(1), tree.child(1).child(0));
+}
+
+#[test]
+fn children_seed_random_number_generators() {
+    let tree = SeedTree::new(7);
+    let mut first = tree.child(1).rng::<SmallRng>();
+    let mut second = tree.child(1).rng::<SmallRng>();
+    assert_eq!(first.random::<u64>(), second.random::<u64>());
+    let mut other = tree.child(2).rng::<SmallRng>();
+    assert_ne!(first.random::<u64>(), other.random::<u64>());
+}
+
+#[test]
+fn ensemble_uses_children() {
+    let builder = Builder::<_, Vect>::with_samples(50, 0.8, Type::Normal);
+    let sets = builder
+        .generate_ensemble(SmallRng::seed_from_u64(9), algorithm::Ebeida, 3)
+        .collect::<Vec<_>>();
+    let tree = SeedTree::from_rng(&mut SmallRng::seed_from_u64(9));
+    for (i, set) in sets.iter().enumerate() {
+        let expected = builder
+            .clone()
+            .build(tree.child(i as u64).rng::<SmallRng>(), algorithm::Ebeida)
+            .generate();
+        assert_eq!(expected, set.points());
+    }
+}
+
+#[test]
+fn labels_derive_stable_and_distinct_seeds() {
+    let world = Seed::new(42);
+    assert_eq!(world.derive("trees"), Seed::from(42).derive("trees"));
+    let labels = ["trees", "rocks", "towns", "tree", "treess", ""];
+    let seeds = labels.iter().map(|l| world.derive(l)).collect::<Vec<_>>();
+    for (i, seed) in seeds.iter().enumerate() {
+        assert!(!seeds[i + 1..].contains(seed));
+        assert_ne!(&world, seed);
+    }
+    assert_ne!(world.derive("trees"), Seed::new(43).derive("trees"));
+    assert_ne!(world.derive("a").derive("b"), world.derive("b").derive("a"));
+    assert_eq!(world.tree(), SeedTree::new(42));
+}
+
+#[test]
+fn derived_seeds_are_pinned() {
+    // Derived seeds are part of the stable interface so changing them breaks the worlds of users.
+    let seed = Seed::new(0).derive("trees");
+    assert_eq!(seed, Seed::new(0).derive("trees"));
+    assert_eq!(seed.value(), 9888918008084418600);
+}
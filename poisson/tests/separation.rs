@@ -0,0 +1,51 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, RadiusError, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn separation_is_twice_disk_radius() {
+    let separated = Builder::<_, Vect>::with_separation(0.1, Type::Normal);
+    let disks = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal);
+    assert_eq!(separated, disks);
+    assert_eq!(0.1, separated.separation());
+    assert_eq!(0.05, separated.disk_radius());
+    let samples = separated
+        .build(SmallRng::seed_from_u64(4), algorithm::Bridson)
+        .generate();
+    for (i, a) in samples.iter().enumerate() {
+        for b in &samples[i + 1..] {
+            assert!((a - b).norm() >= 0.1);
+        }
+    }
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_radius_is_disk_radius() {
+    assert_eq!(
+        Builder::<_, Vect>::with_disk_radius(0.05, Type::Perioditic),
+        Builder::<_, Vect>::with_radius(0.05, Type::Perioditic)
+    );
+}
+
+#[test]
+fn invalid_values_are_errors() {
+    let separation = Builder::<_, Vect>::try_with_separation(0., Type::Normal);
+    assert_eq!(Some(RadiusError::NonPositiveSeparation(0.)), separation.err());
+    let radius = Builder::<_, Vect>::try_with_disk_radius(-0.1, Type::Normal);
+    assert_eq!(Some(RadiusError::NonPositiveRadius(-0.1)), radius.err());
+    let infinite = Builder::<_, Vect>::try_with_separation(f64::INFINITY, Type::Normal);
+    assert_eq!(Some(RadiusError::NotFinite(f64::INFINITY)), infinite.err());
+    assert!(Builder::<_, Vect>::try_with_disk_radius(f64::NAN, Type::Normal).is_err());
+    assert!(Builder::<_, Vect>::try_with_separation(0.1, Type::Normal).is_ok());
+}
+
+#[test]
+#[should_panic(expected = "Separation distance should be larger than 0")]
+fn non_positive_separation_panics() {
+    Builder::<_, Vect>::with_separation(-1., Type::Normal);
+}
@@ -0,0 +1,610 @@
+#![cfg(feature = "full")]
+use poisson::shape::{Ball, Shape};
+use poisson::{algorithm, Builder, Conflict, MergePolicy, PointSet, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+#[test]
+fn retain_within_keeps_only_inside_and_metadata() {
+    let set = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(7), algorithm::Ebeida)
+        .generate_set();
+    let ball = Ball {
+        center: Vect::new(0.5, 0.5),
+        radius: 0.3,
+    };
+    let filtered = set.clone().retain_within(&ball);
+    assert!(!filtered.is_empty());
+    assert!(filtered.len() < set.len());
+    assert!(filtered.iter().all(|p| ball.contains(p)));
+    assert_eq!(set.radius(), filtered.radius());
+    assert_eq!(set.poisson_type(), filtered.poisson_type());
+}
+
+#[test]
+fn retain_far_from_boundary_drops_samples_near_edges() {
+    let set = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(7), algorithm::Ebeida)
+        .generate_set()
+        .retain_far_from_boundary(0.1);
+    assert!(!set.is_empty());
+    assert!(set
+        .iter()
+        .all(|p| p.x >= 0.1 && p.x <= 0.9 && p.y >= 0.1 && p.y <= 0.9));
+}
+
+#[test]
+fn refilling_fills_only_the_region_and_stays_valid() {
+    let inner = |p: &Vect| (p - Vect::new(0.5, 0.5)).norm() < 0.3;
+    for seed in 0..20 {
+        let set = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal)
+            .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+            .generate_set();
+        let radius = set.radius();
+        let filtered = set.retain_far_from_boundary(0.25);
+        let before = filtered.len();
+        let refilled = filtered.refill_within(&inner, &mut SmallRng::seed_from_u64(seed), 1000);
+        assert!(refilled.len() > before);
+        assert!(refilled.points()[before..].iter().all(inner));
+        helper::assert_legal_poisson(&refilled.into_points(), radius, algorithm::DartThrowing);
+    }
+}
+
+#[test]
+fn tiling_perioditic_stays_valid() {
+    let set = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate_set();
+    let (tiled, extent) = set.tile(&[2, 3]);
+    assert_eq!(set.len() * 6, tiled.len());
+    assert_eq!(Vect::new(2., 3.), extent);
+    assert!(tiled
+        .iter()
+        .all(|p| 0. <= p.x && p.x < 2. && 0. <= p.y && p.y < 3.));
+    helper::assert_legal_poisson(&tiled, set.radius(), algorithm::Ebeida);
+}
+
+#[test]
+#[should_panic]
+fn tiling_normal_fails() {
+    Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate_set()
+        .tile(&[2, 2]);
+}
+
+#[test]
+fn diff_reports_added_removed_and_moved() {
+    let before = PointSet::new(
+        vec![
+            Vect::new(0.1, 0.1),
+            Vect::new(0.5, 0.5),
+            Vect::new(0.9, 0.9),
+        ],
+        0.05,
+        Type::Normal,
+    );
+    let after = PointSet::new(
+        vec![
+            Vect::new(0.5, 0.5),
+            Vect::new(0.11, 0.1),
+            Vect::new(0.3, 0.7),
+        ],
+        0.05,
+        Type::Normal,
+    );
+    let diff = before.diff(&after, 0.02);
+    assert_eq!(vec![Vect::new(0.3, 0.7)], diff.added);
+    assert_eq!(vec![Vect::new(0.9, 0.9)], diff.removed);
+    assert_eq!(
+        vec![(Vect::new(0.1, 0.1), Vect::new(0.11, 0.1))],
+        diff.moved
+    );
+    assert!(before.diff(&before, 0.02).is_empty());
+}
+
+#[test]
+fn regeneration_keeps_prior_samples() {
+    let builder = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal);
+    let prior = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .generate_set();
+
+    // Every sample stays legal when the radius shrinks.
+    let smaller = Builder::<_, Vect>::with_disk_radius(prior.radius() * 0.95, Type::Normal)
+        .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+        .regenerate_from(&prior);
+    assert!(prior.diff(&smaller, 0.).removed.is_empty());
+
+    for &scale in &[0.95, 1.05] {
+        let radius = prior.radius() * scale;
+        let set = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+            .build(SmallRng::seed_from_u64(4), algorithm::Bridson)
+            .regenerate_from(&prior);
+        let diff = prior.diff(&set, 0.);
+        assert!(diff.removed.len() < prior.len() / 2);
+        helper::test_poisson(
+            set.into_iter(),
+            radius,
+            Type::Normal,
+            algorithm::Bridson,
+            false,
+        );
+    }
+}
+
+#[test]
+fn merge_resolves_conflicts_with_policy() {
+    let generate = |seed| {
+        Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+            .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+            .generate_set()
+    };
+    let (first, second) = (generate(1), generate(2));
+    for &policy in &[
+        MergePolicy::KeepFirst,
+        MergePolicy::KeepFarthest,
+        MergePolicy::DropBoth,
+    ] {
+        let merged = first.clone().merge(second.clone(), policy);
+        assert_eq!(Some("Ebeida"), merged.algorithm());
+        if policy != MergePolicy::DropBoth {
+            assert!(merged.len() >= first.len() * 9 / 10);
+        }
+        helper::assert_legal_poisson(&merged.points().to_vec(), 0.03, algorithm::Ebeida);
+        if policy == MergePolicy::KeepFirst {
+            assert!(first.iter().all(|p| merged.points().contains(p)));
+        }
+    }
+    let keep = first.clone().merge(second.clone(), MergePolicy::KeepFirst);
+    let drop = first.clone().merge(second.clone(), MergePolicy::DropBoth);
+    assert!(drop.len() < keep.len());
+    assert!(drop.iter().all(|p| keep.points().contains(p)));
+}
+
+#[test]
+fn merging_disjoint_sets_keeps_everything() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate_set();
+    let (left, right): (Vec<Vect>, Vec<Vect>) = set.iter().partition(|p| p.x < 0.5);
+    let merged = PointSet::new(left, 0.05, Type::Normal).merge(
+        PointSet::new(right, 0.05, Type::Normal),
+        MergePolicy::DropBoth,
+    );
+    assert_eq!(set.len(), merged.len());
+}
+
+#[test]
+fn quantizing_snaps_to_lattice_and_stays_valid() {
+    let step = 0.01;
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let set = Builder::<_, Vect>::with_disk_radius(0.03, ptype)
+            .build(SmallRng::seed_from_u64(8), algorithm::Bridson)
+            .generate_set();
+        let quantized = set.clone().quantize(step);
+        assert!(quantized.len() > set.len() * 9 / 10);
+        assert_eq!(set.radius(), quantized.radius());
+        assert_eq!(set.algorithm(), quantized.algorithm());
+        for p in &quantized {
+            for x in [p.x, p.y] {
+                assert!((0. ..1.).contains(&x));
+                assert!((x / step - (x / step).round()).abs() < 1e-9);
+            }
+        }
+        helper::assert_legal_poisson(&quantized.into_points(), 0.03, algorithm::Bridson);
+    }
+}
+
+#[test]
+fn coarse_quantizing_drops_samples() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+        .build(SmallRng::seed_from_u64(9), algorithm::Ebeida)
+        .generate_set();
+    let quantized = set.clone().quantize(0.05);
+    assert!(quantized.len() < set.len());
+    helper::assert_legal_poisson(&quantized.into_points(), 0.03, algorithm::Ebeida);
+}
+
+#[test]
+fn packing_fraction_clips_disks_at_boundary() {
+    use std::f64::consts::PI;
+    let area = PI * 0.1 * 0.1;
+    let empty = PointSet::<f64, Vect>::new(vec![], 0.1, Type::Normal);
+    assert_eq!(0., empty.packing_fraction());
+    let center = PointSet::new(vec![Vect::new(0.5, 0.5)], 0.1, Type::Normal);
+    assert!((center.packing_fraction() - area).abs() < 1e-12);
+    let corner = PointSet::new(vec![Vect::new(0., 0.)], 0.1, Type::Normal);
+    assert!((corner.packing_fraction() - area / 4.).abs() < area * 1e-3);
+    let edge = PointSet::new(vec![Vect::new(0.5, 0.95)], 0.1, Type::Normal);
+    let segment = 0.01 * (0.5f64).acos() - 0.05 * (0.01f64 - 0.0025).sqrt();
+    assert!((edge.packing_fraction() - (area - segment)).abs() < area * 1e-3);
+    let wrapped = PointSet::new(vec![Vect::new(0., 0.)], 0.1, Type::Perioditic);
+    assert!((wrapped.packing_fraction() - area).abs() < 1e-12);
+    let ball = PointSet::new(vec![na::Vector3::new(0.5, 0.5, 0.)], 0.1, Type::Normal);
+    let volume = 4. / 3. * PI * 0.001;
+    assert!((ball.packing_fraction() - volume / 2.).abs() < volume * 1e-3);
+}
+
+#[test]
+fn packing_fraction_of_generated_distributions() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.02, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(10), algorithm::Ebeida)
+        .generate_set();
+    let expected = set.len() as f64 * std::f64::consts::PI * 0.02 * 0.02;
+    assert!((set.packing_fraction() - expected).abs() < 1e-9);
+    let fraction = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal)
+        .build(SmallRng::seed_from_u64(10), algorithm::Ebeida)
+        .generate_set()
+        .packing_fraction();
+    assert!(0.4 < fraction && fraction < 0.91, "{}", fraction);
+}
+
+#[test]
+fn coverage_radius_of_handmade_sets() {
+    let empty = PointSet::<f64, Vect>::new(vec![], 0.1, Type::Normal);
+    assert!(empty.coverage_radius().is_infinite());
+    let center = PointSet::new(vec![Vect::new(0.5, 0.5)], 0.1, Type::Normal);
+    assert!((center.coverage_radius() - 0.5f64.sqrt()).abs() < 1e-6);
+    // The farthest position is the opposite corner, far away from the cells around the sample.
+    let corner = PointSet::new(vec![Vect::new(0.1, 0.1)], 0.01, Type::Normal);
+    assert!((corner.coverage_radius() - 0.9 * 2f64.sqrt()).abs() < 1e-7);
+    let wrapped = PointSet::new(vec![Vect::new(0.1, 0.1)], 0.01, Type::Perioditic);
+    assert!((wrapped.coverage_radius() - 0.5f64.sqrt()).abs() < 1e-7);
+    let pair = PointSet::new(
+        vec![na::Vector3::new(0., 0., 0.), na::Vector3::new(1., 1., 1.)],
+        0.1,
+        Type::Normal,
+    );
+    assert!((pair.coverage_radius() - 1.25f64.sqrt()).abs() < 1e-6);
+}
+
+#[test]
+fn coverage_radius_matches_dense_search() {
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let set = Builder::<_, Vect>::with_disk_radius(0.05, ptype)
+            .build(SmallRng::seed_from_u64(12), algorithm::Ebeida)
+            .generate_set();
+        let coverage = set.coverage_radius();
+        let steps = 200;
+        let mut farthest = 0f64;
+        for i in 0..steps * steps {
+            let p = Vect::new((i % steps) as f64, (i / steps) as f64) / steps as f64;
+            let closest = set
+                .iter()
+                .map(|q| {
+                    let mut d = q - p;
+                    if ptype == Type::Perioditic {
+                        d = d.map(|c| c.abs().min(1. - c.abs()));
+                    }
+                    d.norm()
+                })
+                .fold(f64::MAX, f64::min);
+            farthest = farthest.max(closest);
+        }
+        // Dense search misses the farthest position by at most half of the diagonal between its positions.
+        let miss = 2f64.sqrt() / (2 * steps) as f64;
+        assert!(farthest <= coverage + 1e-6 && coverage <= farthest + miss);
+        assert!(0.05 < coverage && coverage < 0.15, "{}", coverage);
+    }
+}
+
+#[test]
+fn morton_order_visits_quadrants_in_z_order() {
+    let set = PointSet::new(
+        vec![
+            Vect::new(0.75, 0.75),
+            Vect::new(0.25, 0.25),
+            Vect::new(0.25, 0.75),
+            Vect::new(0.75, 0.25),
+        ],
+        0.1,
+        Type::Normal,
+    );
+    let order = set.iter_morton().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            Vect::new(0.25, 0.25),
+            Vect::new(0.75, 0.25),
+            Vect::new(0.25, 0.75),
+            Vect::new(0.75, 0.75),
+        ],
+        order
+    );
+}
+
+#[test]
+fn morton_order_keeps_consecutive_samples_close() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.01, Type::Normal)
+        .build(SmallRng::seed_from_u64(11), algorithm::DartThrowing)
+        .generate_set();
+    let length = |points: Vec<&Vect>| points.windows(2).map(|w| (w[0] - w[1]).norm()).sum::<f64>();
+    let morton = set.iter_morton().collect::<Vec<_>>();
+    assert_eq!(set.len(), morton.len());
+    let mut sorted = morton.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+    let mut original = set.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    original.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    assert_eq!(original, sorted);
+    assert!(length(morton) * 5. < length(set.iter().collect()));
+}
+
+#[test]
+fn subsampling_gaps_keeps_disks_apart() {
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let trees = Builder::<_, Vect>::with_disk_radius(0.05, ptype)
+            .build(SmallRng::seed_from_u64(12), algorithm::Bridson)
+            .generate_set();
+        let rocks = trees.subsample_gaps(0.01, &mut SmallRng::seed_from_u64(13), 1000);
+        assert!(rocks.len() > trees.len());
+        assert_eq!(0.01, rocks.radius());
+        assert_eq!(trees.domain(), rocks.domain());
+        assert!(poisson::verify::verify_separation(rocks.points(), 0.01, ptype).is_ok());
+        for rock in &rocks {
+            for tree in &trees {
+                let mut d = rock - tree;
+                if ptype == Type::Perioditic {
+                    d = d.map(|c| c - c.round());
+                }
+                assert!(d.norm() >= 0.06);
+            }
+        }
+    }
+}
+
+#[test]
+fn filtering_legal_matches_separation_check() {
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let set = Builder::<_, Vect>::with_disk_radius(0.05, ptype)
+            .build(SmallRng::seed_from_u64(14), algorithm::Bridson)
+            .generate_set()
+            .retain_within(&Ball {
+                center: Vect::new(0.5, 0.5),
+                radius: 0.3,
+            });
+        let mut rng = SmallRng::seed_from_u64(15);
+        let mut positions = (0..2000)
+            .map(|_| Vect::new(rng.random(), rng.random()))
+            .collect::<Vec<_>>();
+        positions.push(Vect::new(1.5, 0.5));
+        let legal = set.filter_legal(&positions);
+        assert_eq!(positions.len(), legal.len());
+        assert!(!legal[positions.len() - 1]);
+        assert!(legal.iter().any(|&l| l));
+        for (p, &l) in positions.iter().zip(&legal).take(2000) {
+            let expected = set.iter().all(|q| {
+                let mut d = p - q;
+                if ptype == Type::Perioditic {
+                    d = d.map(|c| c - c.round());
+                }
+                d.norm() >= 0.1
+            });
+            assert_eq!(expected, l);
+        }
+    }
+}
+
+#[test]
+fn thinning_by_importance_follows_importance() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.01, Type::Normal)
+        .build(SmallRng::seed_from_u64(16), algorithm::Bridson)
+        .generate_set();
+    let mut rng = SmallRng::seed_from_u64(17);
+    assert_eq!(set, set.clone().thin_by_importance(|_| 2., &mut rng));
+    assert!(set.clone().thin_by_importance(|_| 0., &mut rng).is_empty());
+    let half = set.clone().thin_by_importance(|_| 0.5, &mut rng);
+    assert!(half.iter().all(|p| set.iter().any(|q| p == q)));
+    assert!((half.len() as f64 - set.len() as f64 / 2.).abs() < 0.02 * set.len() as f64);
+    let ramp = set.clone().thin_by_importance(|p| p.x, &mut rng);
+    let left = ramp.iter().filter(|p| p.x < 0.5).count();
+    assert!(2 * left < ramp.len() - left);
+    assert_eq!(set.radius(), ramp.radius());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_iteration_visits_every_point() {
+    use rayon::prelude::*;
+    let set = Builder::<_, Vect>::with_samples(200, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+        .generate_set();
+    let sum: f64 = set.par_iter().map(|p| p.x + p.y).sum();
+    let expected: f64 = set.iter().map(|p| p.x + p.y).sum();
+    assert!((sum - expected).abs() < 1e-9);
+    let mut owned: Vec<Vect> = set.clone().into_par_iter().collect();
+    assert_eq!(owned.len(), set.len());
+    owned.retain(|p| !set.iter().any(|q| q == p));
+    assert!(owned.is_empty());
+}
+
+#[test]
+fn sorting_by_axis_orders_coordinates() {
+    let set = PointSet::new(
+        vec![Vect::new(0.7, 0.1), Vect::new(0.2, 0.9), Vect::new(0.4, 0.5)],
+        0.05,
+        Type::Normal,
+    );
+    let by_x = set.sorted_by_axis(0).map(|p| p.x).collect::<Vec<_>>();
+    assert_eq!(vec![0.2, 0.4, 0.7], by_x);
+    let by_y = set.sorted_by_axis(1).map(|p| p.y).collect::<Vec<_>>();
+    assert_eq!(vec![0.1, 0.5, 0.9], by_y);
+}
+
+#[test]
+fn sorting_by_distance_wraps_perioditic_domain() {
+    let points = vec![Vect::new(0.5, 0.5), Vect::new(0.95, 0.1), Vect::new(0.2, 0.1)];
+    let origin = Vect::new(0.05, 0.1);
+    let open = PointSet::new(points.clone(), 0.05, Type::Normal);
+    let order = open.sorted_by_distance(&origin).cloned().collect::<Vec<_>>();
+    assert_eq!(vec![points[2], points[0], points[1]], order);
+    let perioditic = PointSet::new(points.clone(), 0.05, Type::Perioditic);
+    let order = perioditic.sorted_by_distance(&origin).cloned().collect::<Vec<_>>();
+    assert_eq!(vec![points[1], points[2], points[0]], order);
+}
+
+#[test]
+fn nearest_wraps_perioditic_domain() {
+    let points = vec![Vect::new(0.5, 0.5), Vect::new(0.95, 0.5)];
+    let position = Vect::new(0.02, 0.5);
+    let open = PointSet::new(points.clone(), 0.01, Type::Normal);
+    assert_eq!(0, open.nearest(&position).unwrap().0);
+    let perioditic = PointSet::new(points, 0.01, Type::Perioditic);
+    let (index, distance) = perioditic.nearest(&position).unwrap();
+    assert_eq!(1, index);
+    assert!((distance - 0.07).abs() < 1e-9);
+    assert!((perioditic.distance(&position, &Vect::new(0.95, 0.5)) - 0.07).abs() < 1e-9);
+    assert!(PointSet::<f64, Vect>::new(vec![], 0.01, Type::Normal).nearest(&position).is_none());
+}
+
+#[test]
+fn nearest_distances_match_brute_force() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.03, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(8), algorithm::Bridson)
+        .generate_set();
+    let distances = set.nearest_distances();
+    for (i, p) in set.iter().enumerate() {
+        let expected = set
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, q)| set.distance(p, q))
+            .fold(f64::INFINITY, f64::min);
+        assert_eq!(expected, distances[i]);
+        assert!(distances[i] >= 2. * set.radius());
+    }
+}
+
+#[test]
+fn radial_distribution_is_empty_inside_diameter_and_flat_far_away() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.015, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(9), algorithm::Ebeida)
+        .generate_set();
+    let rdf = set.radial_distribution(0.5, 50);
+    assert!(rdf[..3].iter().all(|&g| g == 0.));
+    let far = &rdf[30..];
+    let mean = far.iter().sum::<f64>() / far.len() as f64;
+    assert!((mean - 1.).abs() < 0.05, "{}", mean);
+}
+
+#[test]
+fn approx_eq_ignores_order_and_noise() {
+    let set = Builder::<_, Vect>::with_samples(200, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(9), algorithm::Ebeida)
+        .generate_set();
+    let mut points = set.points().to_vec();
+    points.reverse();
+    let mut rng = SmallRng::seed_from_u64(10);
+    for p in &mut points {
+        *p += Vect::new(rng.random_range(-1e-9..1e-9), rng.random_range(-1e-9..1e-9));
+    }
+    let noisy = PointSet::new(points, set.radius(), set.domain());
+    assert_ne!(set, noisy);
+    assert!(set.approx_eq(&noisy, 1e-6));
+    assert!(!set.approx_eq(&noisy, 0.));
+    let fewer = PointSet::new(set.points()[1..].to_vec(), set.radius(), set.domain());
+    assert!(!set.approx_eq(&fewer, 1e-6));
+    let larger = PointSet::new(set.points().to_vec(), 2. * set.radius(), set.domain());
+    assert!(!set.approx_eq(&larger, 1e-6));
+}
+
+#[test]
+fn content_hash_ignores_order_and_small_noise() {
+    let set = PointSet::new(
+        vec![Vect::new(0.1, 0.2), Vect::new(0.5, 0.5), Vect::new(0.9, 0.3)],
+        0.05,
+        Type::Normal,
+    );
+    let shuffled = PointSet::new(
+        vec![Vect::new(0.9, 0.3), Vect::new(0.1, 0.2 + 1e-12), Vect::new(0.5, 0.5)],
+        0.05,
+        Type::Normal,
+    );
+    assert_eq!(set.content_hash(), shuffled.content_hash());
+    let moved = PointSet::new(
+        vec![Vect::new(0.1, 0.2), Vect::new(0.5, 0.51), Vect::new(0.9, 0.3)],
+        0.05,
+        Type::Normal,
+    );
+    assert_ne!(set.content_hash(), moved.content_hash());
+    let swapped = PointSet::new(
+        vec![Vect::new(0.2, 0.1), Vect::new(0.5, 0.5), Vect::new(0.9, 0.3)],
+        0.05,
+        Type::Normal,
+    );
+    assert_ne!(set.content_hash(), swapped.content_hash());
+    let larger = PointSet::new(set.points().to_vec(), 0.06, Type::Normal);
+    assert_ne!(set.content_hash(), larger.content_hash());
+    let fewer = PointSet::new(set.points()[1..].to_vec(), 0.05, Type::Normal);
+    assert_ne!(set.content_hash(), fewer.content_hash());
+}
+
+#[test]
+fn symmetry_variants_are_legal_transforms() {
+    let set = Builder::<_, Vect>::with_disk_radius(0.04, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(11), algorithm::Ebeida)
+        .generate_set();
+    let variants = set.symmetry_variants().expect("Variants of legal set should be legal.");
+    assert_eq!(8, variants.len());
+    assert_eq!(set, variants[0]);
+    for (i, a) in variants.iter().enumerate() {
+        assert_eq!(set.len(), a.len());
+        assert!(a.iter().all(|p| (0..2).all(|n| 0. <= p[n] && p[n] < 1.)));
+        assert!(variants[i + 1..].iter().all(|b| !a.approx_eq(b, 1e-9)));
+    }
+    // Half turn mirrors both axes and the mirrored quarter turn swaps them.
+    let p = set.points()[0];
+    let q = variants[2].points()[0];
+    assert!((q - Vect::new(1. - p.x, 1. - p.y)).norm() < 1e-12);
+    let q = variants[5].points()[0];
+    assert!((q - Vect::new(p.y, p.x)).norm() < 1e-12);
+    let illegal = PointSet::new(
+        vec![Vect::new(0.01, 0.5), Vect::new(0.99, 0.5)],
+        0.05,
+        Type::Perioditic,
+    );
+    assert!(illegal.symmetry_variants().is_err());
+}
+
+#[test]
+fn try_insert_keeps_set_legal() {
+    let radius = 0.03;
+    let mut set = Builder::<_, Vect>::with_disk_radius(radius, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(12), algorithm::Bridson)
+        .generate_set();
+    let original = set.clone();
+    let mut rng = SmallRng::seed_from_u64(13);
+    let mut inserted = 0;
+    for _ in 0..2000 {
+        let p = Vect::new(rng.random(), rng.random());
+        let legal = set.filter_legal(&[p])[0];
+        match set.try_insert(p) {
+            Ok(()) => {
+                assert!(legal);
+                inserted += 1;
+            }
+            Err(Conflict::Sample { sample, distance }) => {
+                assert!(!legal);
+                assert!(distance < 2. * radius);
+                assert_eq!(distance, set.distance(&sample, &p));
+            }
+            Err(Conflict::OutsideDomain) => unreachable!(),
+        }
+    }
+    assert!(inserted > 0);
+    assert_eq!(original.len() + inserted, set.len());
+    assert_eq!(original.points(), &set.points()[..original.len()]);
+    assert!(poisson::verify::verify_separation(set.points(), radius, Type::Perioditic).is_ok());
+    assert_eq!(Err(Conflict::OutsideDomain), set.try_insert(Vect::new(1.5, 0.5)));
+    // Sets compare equal regardless of whether the grid was built.
+    let mut copy = PointSet::new(set.points().to_vec(), radius, Type::Perioditic);
+    if let Some(algorithm) = set.algorithm() {
+        copy = copy.with_algorithm(algorithm);
+    }
+    assert_eq!(copy, set);
+}
@@ -0,0 +1,289 @@
+#![cfg(feature = "full")]
+use poisson::shape::{
+    Annulus, Ball, Cuboid, Difference, Ellipse, Intersection, Parametric, Polygon, RoundedRectangle,
+    Sdf, Shape, Union, VoxelSdf,
+};
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+fn shapes() -> (
+    Annulus<f64, Vect>,
+    Ellipse<Vect>,
+    RoundedRectangle<f64, Vect>,
+) {
+    (
+        Annulus {
+            center: Vect::new(0.5, 0.5),
+            inner: 0.2,
+            outer: 0.45,
+        },
+        Ellipse {
+            center: Vect::new(0.5, 0.4),
+            radii: Vect::new(0.45, 0.25),
+        },
+        RoundedRectangle {
+            min: Vect::new(0.1, 0.2),
+            max: Vect::new(0.9, 0.7),
+            corner: 0.1,
+        },
+    )
+}
+
+fn check<S>(shape: S)
+where
+    S: Parametric<f64, Vect> + Clone + Send + Sync + 'static,
+{
+    let radius = 0.02;
+    let mut rng = SmallRng::seed_from_u64(9);
+    let points = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .with_shape(shape.clone())
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert!(points.iter().all(|p| shape.contains(p)));
+    helper::assert_legal_poisson(&points, radius, algorithm::Ebeida);
+    // Ebeida's algorithm is nearly maximal so nearly every position inside the shape is covered by some sample.
+    let (mut inside, mut uncovered) = (0, 0);
+    for _ in 0..4000 {
+        let p = Vect::new(rng.random(), rng.random());
+        if shape.contains(&p) {
+            inside += 1;
+            if points.iter().all(|q| (q - p).norm() >= 2. * radius) {
+                uncovered += 1;
+            }
+        }
+    }
+    assert!(
+        uncovered * 100 < inside,
+        "{} of {} uncovered",
+        uncovered,
+        inside
+    );
+    let builder =
+        Builder::<_, Vect>::with_disk_radius(radius, Type::Perioditic).with_shape(shape.clone());
+    let samples = [
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+            .generate(),
+        builder
+            .clone()
+            .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing)
+            .generate(),
+        builder
+            .build(SmallRng::seed_from_u64(2), algorithm::Hashed)
+            .generate(),
+    ];
+    for points in samples {
+        assert!(!points.is_empty());
+        assert!(points.iter().all(|p| shape.contains(p)));
+    }
+}
+
+#[test]
+fn shapes_are_sampled_maximally() {
+    let (annulus, ellipse, rectangle) = shapes();
+    check(annulus);
+    check(ellipse);
+    check(rectangle);
+}
+
+fn check_outline<S>(shape: S)
+where
+    S: Parametric<f64, Vect>,
+{
+    let steps = 4000;
+    let outline = (0..steps)
+        .map(|i| shape.outline(i as f64 / steps as f64))
+        .collect::<Vec<_>>();
+    for p in &outline {
+        assert!(shape.distance(p) < 1e-9);
+    }
+    let mut rng = SmallRng::seed_from_u64(3);
+    for _ in 0..200 {
+        let p = Vect::new(rng.random(), rng.random());
+        let closest = outline
+            .iter()
+            .map(|q| (q - p).norm())
+            .fold(f64::INFINITY, f64::min);
+        if shape.contains(&p) {
+            assert_eq!(0., shape.distance(&p));
+        } else {
+            assert!(shape.distance(&p) <= closest + 1e-9);
+        }
+    }
+}
+
+#[test]
+fn distance_bounds_distance_to_outline() {
+    let (annulus, ellipse, rectangle) = shapes();
+    check_outline(annulus);
+    check_outline(ellipse);
+    check_outline(rectangle.clone());
+    let perimeter = (0..4000)
+        .map(|i| {
+            let (a, b) = (
+                rectangle.outline(i as f64 / 4000.),
+                rectangle.outline((i + 1) as f64 / 4000.),
+            );
+            (b - a).norm()
+        })
+        .sum::<f64>();
+    assert!((perimeter - rectangle.perimeter()).abs() < 1e-3);
+}
+
+#[test]
+fn outline_is_sampled_first() {
+    let (annulus, _, _) = shapes();
+    let radius = 0.02;
+    let points = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .with_shape(annulus.clone())
+        .with_boundary_sampling(true)
+        .build(SmallRng::seed_from_u64(5), algorithm::Ebeida)
+        .generate();
+    helper::assert_legal_poisson(&points, radius, algorithm::Ebeida);
+    let on_outline = |p: &Vect| {
+        let d = (p - annulus.center).norm();
+        (d - annulus.inner).abs() < 1e-9 || (d - annulus.outer).abs() < 1e-9
+    };
+    let outline = points.iter().take_while(|p| on_outline(p)).count();
+    // Circles of radius r fit about 2πr / 2r samples along them.
+    let expected = std::f64::consts::PI * (annulus.inner + annulus.outer) / radius;
+    assert!(
+        outline as f64 > 0.8 * expected,
+        "{} < {}",
+        outline,
+        expected
+    );
+    assert!(outline < points.len());
+}
+
+#[test]
+fn dual_sampling_splits_outline_and_interior() {
+    let (annulus, _, _) = shapes();
+    let radius = 0.02;
+    let generator = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal)
+        .with_shape(annulus.clone())
+        .with_start_points(&[Vect::new(0.5, 0.75)], true)
+        .build(SmallRng::seed_from_u64(6), algorithm::Bridson);
+    let dual = generator.generate_dual();
+    let on_outline = |p: &Vect| {
+        let d = (p - annulus.center).norm();
+        (d - annulus.inner).abs() < 1e-9 || (d - annulus.outer).abs() < 1e-9
+    };
+    assert!(!dual.boundary.is_empty());
+    assert!(dual.boundary.iter().all(on_outline));
+    assert!(dual.interior.contains(&Vect::new(0.5, 0.75)));
+    assert!(dual.interior.iter().all(|p| !on_outline(p)));
+    let mut all = dual.boundary.clone();
+    all.extend(dual.interior);
+    helper::assert_legal_poisson(&all, radius, algorithm::Bridson);
+}
+
+/// Map minus a round lake and a triangular bay plus an island in the round lake.
+fn map() -> impl Parametric<f64, Vect> + Clone + Send + Sync + 'static {
+    let land = Cuboid {
+        min: Vect::new(0.05, 0.05),
+        max: Vect::new(0.95, 0.95),
+    };
+    let lake = Ball {
+        center: Vect::new(0.35, 0.6),
+        radius: 0.25,
+    };
+    let triangle = Polygon {
+        vertices: vec![Vect::new(0.6, 0.), Vect::new(0.9, 0.), Vect::new(0.75, 0.4)],
+    };
+    let island = Ball {
+        center: Vect::new(0.35, 0.6),
+        radius: 0.1,
+    };
+    Union(Difference(land, Union(lake, triangle)), island)
+}
+
+#[test]
+fn compositions_are_sampled_maximally() {
+    check(map());
+    let circle = Sdf(|p: &Vect| (p - Vect::new(0.5, 0.5)).norm() - 0.4);
+    let top = Cuboid {
+        min: Vect::new(0., 0.3),
+        max: Vect::new(1., 1.),
+    };
+    check(Intersection(circle, top));
+}
+
+#[test]
+fn compositions_bound_distance_and_depth_by_outline() {
+    let shape = map();
+    let steps = 8000;
+    let outline = (0..steps)
+        .map(|i| i as f64 / steps as f64)
+        .filter(|&t| shape.on_outline(t))
+        .map(|t| shape.outline(t))
+        .collect::<Vec<_>>();
+    assert!(outline.len() < steps);
+    for p in &outline {
+        assert!(shape.distance(p) < 1e-9 && shape.depth(p) < 1e-9);
+    }
+    let mut rng = SmallRng::seed_from_u64(4);
+    for _ in 0..500 {
+        let p = Vect::new(rng.random(), rng.random());
+        let closest = outline
+            .iter()
+            .map(|q| (q - p).norm())
+            .fold(f64::INFINITY, f64::min);
+        if shape.contains(&p) {
+            assert_eq!(0., shape.distance(&p));
+            assert!(shape.depth(&p) <= closest + 1e-9);
+        } else {
+            assert_eq!(0., shape.depth(&p));
+            assert!(shape.distance(&p) <= closest + 1e-9);
+        }
+    }
+    // Boundary sampling keeps only the parts of the outlines on the boundary of the composition.
+    let points = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal)
+        .with_shape(shape.clone())
+        .with_boundary_sampling(true)
+        .build(SmallRng::seed_from_u64(5), algorithm::Ebeida)
+        .generate();
+    assert!(points.iter().all(|p| shape.contains(p) || shape.distance(p) < 1e-9));
+    assert!(points.iter().filter(|p| shape.depth(p) < 1e-9).count() > 100);
+}
+
+#[test]
+fn voxels_restrict_samples_inside_open_space() {
+    type Vect3 = na::Vector3<f64>;
+    let side = 24;
+    let cell = 1. / side as f64;
+    let center = Vect3::new(0.5, 0.5, 0.5);
+    let values = (0..side * side * side)
+        .map(|i| {
+            let (x, y, z) = (i % side, i / side % side, i / side / side);
+            let voxel = Vect3::new(x as f64, y as f64, z as f64);
+            (voxel.add_scalar(0.5) * cell - center).norm() - 0.4
+        })
+        .collect();
+    let space = VoxelSdf::new(values, [side; 3], cell);
+    let voxel = Vect3::new(3.5, 7.5, 11.5) * cell;
+    assert!((space.signed_distance(&voxel) - ((voxel - center).norm() - 0.4)).abs() < 1e-12);
+    let outside = Vect3::new(-0.5, 0.5, 0.5);
+    assert!(space.signed_distance(&outside) >= 0.5);
+    let radius = 0.05;
+    let samples = Builder::<_, Vect3>::with_disk_radius(radius, Type::Normal)
+        .with_shape(space)
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+        .generate();
+    // Trilinear interpolation of the distance to a sphere errs by a fraction of a voxel.
+    assert!(samples.iter().all(|s| (s - center).norm() <= 0.4 + cell / 4.));
+    let mut rng = SmallRng::seed_from_u64(3);
+    for _ in 0..1000 {
+        let p = Vect3::new(rng.random(), rng.random(), rng.random());
+        if (p - center).norm() < 0.4 - cell {
+            assert!(samples.iter().any(|s| (s - p).norm() < 2. * radius));
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "full")]
+#[test]
+fn sampling_2d_keeps_disks_apart() {
+    let samples = poisson::sample_2d(0.05, 1);
+    assert!(samples.len() > 50);
+    assert_ne!(samples, poisson::sample_2d(0.05, 2));
+    for (i, a) in samples.iter().enumerate() {
+        assert!(a.iter().all(|c| (0. ..1.).contains(c)));
+        for b in &samples[i + 1..] {
+            let d = (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2);
+            assert!(d >= 0.1f64.powi(2));
+        }
+    }
+}
+
+#[test]
+fn sampling_3d_keeps_disks_apart() {
+    let samples = poisson::sample_3d(0.1, 3);
+    assert!(samples.len() > 50);
+    for (i, a) in samples.iter().enumerate() {
+        assert!(a.iter().all(|c| (0. ..1.).contains(c)));
+        for b in &samples[i + 1..] {
+            let d = a.iter().zip(b).map(|(p, q)| (p - q).powi(2)).sum::<f64>();
+            assert!(d >= 0.2f64.powi(2));
+        }
+    }
+}
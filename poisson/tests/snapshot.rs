@@ -0,0 +1,36 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn cloned_iterator_continues_from_the_same_state() {
+    let mut iter = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(5), algorithm::Bridson)
+        .into_iter();
+    let head = iter.by_ref().take(20).collect::<Vec<_>>();
+    let fork = iter.clone();
+    let rest = iter.collect::<Vec<_>>();
+    assert_eq!(rest, fork.collect::<Vec<_>>());
+    let whole = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal)
+        .build(SmallRng::seed_from_u64(5), algorithm::Bridson)
+        .generate();
+    assert_eq!(whole, [head, rest].concat());
+}
+
+#[test]
+fn debug_output_describes_generation() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.1, Type::Normal)
+        .build(SmallRng::seed_from_u64(5), algorithm::Ebeida);
+    let debug = format!("{:?}", generator);
+    assert!(debug.contains("radius: 0.1"));
+    assert!(debug.contains("dimension: 2"));
+    let mut iter = generator.into_iter();
+    iter.next();
+    let debug = format!("{:?}", iter);
+    assert!(debug.contains("emitted: 1"));
+    assert!(debug.contains("finished: false"));
+}
@@ -0,0 +1,63 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect2 = na::Vector2<f64>;
+pub type Vect3 = na::Vector3<f64>;
+
+mod helper;
+
+#[test]
+fn large_radii_generate_valid_distributions() {
+    for &radius in &[0.3, 0.4, 0.6, 0.9] {
+        // Tiling perioditic distribution to check it makes a sample conflict with its own images above 0.5.
+        let ptypes = if radius < 0.5 {
+            vec![Type::Normal, Type::Perioditic]
+        } else {
+            vec![Type::Normal]
+        };
+        for &ptype in &ptypes {
+            for seed in 0..20 {
+                let builder = Builder::<_, Vect2>::with_disk_radius(radius, ptype);
+                let iter = builder
+                    .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+                    .into_iter();
+                let (_, upper) = iter.size_hint();
+                let points = iter.collect::<Vec<_>>();
+                assert!(!points.is_empty());
+                assert!(upper.is_some_and(|u| points.len() <= u));
+                helper::test_poisson(points.into_iter(), radius, ptype, algorithm::Sparse, false);
+            }
+        }
+    }
+}
+
+#[test]
+fn huge_radius_fits_one_sample() {
+    let points = Builder::<_, Vect3>::with_disk_radius(2., Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+        .generate();
+    assert_eq!(1, points.len());
+}
+
+#[test]
+fn sparse_works_with_small_radius() {
+    let points = Builder::<_, Vect2>::with_disk_radius(0.05, Type::Normal)
+        .build(SmallRng::seed_from_u64(3), algorithm::Sparse)
+        .generate();
+    assert!(points.len() > 50);
+    helper::assert_legal_poisson(&points, 0.05, algorithm::Sparse);
+}
+
+#[test]
+fn start_points_restrict_large_radius() {
+    let start = Vect2::new(0.5, 0.5);
+    let points = Builder::<_, Vect2>::with_disk_radius(0.4, Type::Normal)
+        .with_start_points(&[start], true)
+        .build(SmallRng::seed_from_u64(4), algorithm::Ebeida)
+        .generate();
+    assert_eq!(start, points[0]);
+    helper::assert_legal_poisson(&points, 0.4, algorithm::Sparse);
+}
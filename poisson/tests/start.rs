@@ -0,0 +1,197 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, PrefillPolicy, Type, Wrap};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+mod helper;
+
+#[test]
+fn emitted_start_points_come_first() {
+    let start = [Vect::new(0.5, 0.5), Vect::new(0.1, 0.1)];
+    let samples = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal)
+        .with_start_points(&start, true)
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+        .generate();
+    assert_eq!(&start[..], &samples[..2]);
+}
+
+#[test]
+fn excluded_start_points_are_not_emitted() {
+    let start = [Vect::new(0.5, 0.5)];
+    for seed in 0..20 {
+        let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal)
+            .with_start_points(&start, false);
+        let radius = builder.radius();
+        let samples = builder
+            .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+            .generate();
+        assert!(!samples.contains(&start[0]));
+        assert!(samples.iter().all(|s| (s - start[0]).norm() > 2. * radius));
+    }
+}
+
+#[test]
+fn generation_from_start_points_is_valid() {
+    let start = [Vect::new(0.5, 0.5), Vect::new(0.1, 0.9)];
+    for &ptype in &[Type::Normal, Type::Perioditic] {
+        for seed in 0..50 {
+            let builder =
+                Builder::<_, Vect>::with_samples(100, 0.8, ptype).with_start_points(&start, true);
+            let radius = builder.radius();
+            let samples = builder
+                .clone()
+                .build(SmallRng::seed_from_u64(seed), algorithm::Bridson)
+                .generate();
+            helper::test_poisson(
+                samples.into_iter(),
+                radius,
+                ptype,
+                algorithm::Bridson,
+                false,
+            );
+            let samples = builder
+                .build(SmallRng::seed_from_u64(seed), algorithm::Ebeida)
+                .generate();
+            helper::test_poisson(samples.into_iter(), radius, ptype, algorithm::Ebeida, false);
+        }
+    }
+}
+
+fn conflicting_builder() -> Builder<f64, Vect> {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic);
+    let r = builder.radius();
+    let start = [
+        Vect::new(0.5, 0.5),
+        Vect::new(0.5 + r, 0.5),
+        Vect::new(r / 2., 0.1),
+        Vect::new(1. - r / 2., 0.1),
+    ];
+    builder.with_start_points(&start, true)
+}
+
+#[test]
+fn conflicting_start_points_are_reported() {
+    assert_eq!(vec![1, 3], conflicting_builder().conflicting_start_points());
+}
+
+#[test]
+fn conflicting_start_points_are_kept_by_default() {
+    let builder = conflicting_builder();
+    let samples = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    assert_eq!(builder.start_points(), &samples[..4]);
+}
+
+#[test]
+fn conflicting_start_points_can_be_rejected() {
+    let builder = conflicting_builder().with_prefill_policy(PrefillPolicy::RejectConflicting);
+    let start = [builder.start_points()[0], builder.start_points()[2]];
+    let samples = builder
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    assert_eq!(&start[..], &samples[..2]);
+    assert!(!samples.contains(&conflicting_builder().start_points()[1]));
+}
+
+#[test]
+#[should_panic]
+fn conflicting_start_points_can_panic() {
+    conflicting_builder()
+        .with_prefill_policy(PrefillPolicy::Panic)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson);
+}
+
+#[test]
+fn start_points_in_neighbouring_tiles_are_wrapped() {
+    let start = [Vect::new(1.25, -0.5), Vect::new(0.5, 0.5)];
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic);
+    let radius = builder.radius();
+    let samples = builder
+        .with_start_points(&start, true)
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    assert_eq!(&[Vect::new(0.25, 0.5), start[1]], &samples[..2]);
+    helper::test_poisson(samples.into_iter(), radius, Type::Perioditic, algorithm::Ebeida, false);
+}
+
+#[test]
+fn restricted_samples_are_wrapped() {
+    for wrap in [Wrap::Strict, Wrap::Loose] {
+        let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic).with_wrap(wrap);
+        let radius = builder.radius();
+        let mut iter = builder
+            .build(SmallRng::seed_from_u64(2), algorithm::Bridson)
+            .into_iter();
+        iter.restrict(Vect::new(-0.75, 0.5));
+        assert!(!iter.stays_legal(Vect::new(0.25 + radius, 0.5)));
+        assert!(iter.all(|s| (s - Vect::new(0.25, 0.5)).norm() >= 2. * radius));
+    }
+}
+
+#[test]
+fn loose_wrap_wraps_far_points() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic)
+        .with_start_points(&[Vect::new(3.5, -2.75)], true)
+        .with_wrap(Wrap::Loose);
+    assert_eq!(Wrap::Loose, builder.wrap());
+    let samples = builder
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    assert_eq!(Vect::new(0.5, 0.25), samples[0]);
+}
+
+#[test]
+#[should_panic]
+fn strict_wrap_panics_on_far_points() {
+    Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic)
+        .with_start_points(&[Vect::new(2.5, 0.5)], true)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson);
+}
+
+#[test]
+fn open_axes_are_not_wrapped() {
+    let start = [Vect::new(2.5, 0.5)];
+    let samples = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal)
+        .with_start_points(&start, true)
+        .build(SmallRng::seed_from_u64(3), algorithm::Bridson)
+        .generate();
+    assert_eq!(start[0], samples[0]);
+}
+
+#[test]
+fn tiles_match_borders_of_neighbours() {
+    let radius = 0.03;
+    let builder = Builder::<_, Vect>::with_disk_radius(radius, Type::Normal);
+    let left = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+        .generate();
+    let below = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida)
+        .generate();
+    let start = [Vect::new(0.5, 0.5), Vect::new(0.001, 0.5)];
+    let tile = builder
+        .with_start_points(&start, true)
+        .with_neighbour_tile(&[-1, 0], &left)
+        .with_neighbour_tile(&[0, -1], &below)
+        .with_prefill_policy(PrefillPolicy::RejectConflicting);
+    assert!(tile.start_points().len() < left.len() + below.len() + start.len());
+    let samples = tile.build(SmallRng::seed_from_u64(3), algorithm::Ebeida).generate();
+    assert_eq!(samples[0], start[0]);
+    assert!(samples.iter().all(|s| (0..2).all(|n| 0. <= s[n] && s[n] < 1.)));
+    let neighbours = left
+        .iter()
+        .map(|p| p - Vect::new(1., 0.))
+        .chain(below.iter().map(|p| p - Vect::new(0., 1.)));
+    let all = samples.iter().cloned().chain(neighbours).collect::<Vec<_>>();
+    assert!(!all.contains(&start[1]));
+    for (i, a) in samples.iter().enumerate() {
+        assert!(all[i + 1..].iter().all(|b| (a - b).norm() >= 2. * radius));
+    }
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, GenerationStats, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+fn check_stats(points: usize, stats: GenerationStats) {
+    assert_eq!(points, stats.accepted);
+    assert_eq!(stats.candidates, stats.accepted + stats.rejected);
+}
+
+#[test]
+fn stats_are_consistent() {
+    let builder = Builder::<_, Vect>::with_samples(100, 0.8, Type::Normal);
+    let rng = SmallRng::seed_from_u64(5);
+
+    let (set, stats) = builder
+        .clone()
+        .build(rng.clone(), algorithm::Ebeida)
+        .generate_with_stats();
+    check_stats(set.len(), stats);
+    assert!(stats.depth > 0);
+
+    let (set, stats) = builder
+        .clone()
+        .build(rng.clone(), algorithm::Bridson)
+        .generate_with_stats();
+    check_stats(set.len(), stats);
+    assert_eq!(0, stats.depth);
+
+    let (set, stats) = builder
+        .with_rejection_limit(50)
+        .build(rng, algorithm::DartThrowing)
+        .generate_with_stats();
+    check_stats(set.len(), stats);
+    assert!(stats.rejected >= 50);
+}
+
+#[test]
+fn stats_do_not_change_generation() {
+    let generator = Builder::<_, Vect>::with_samples(100, 0.8, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(5), algorithm::Ebeida);
+    let (set, _) = generator.generate_with_stats();
+    assert_eq!(generator.generate(), set.into_points());
+}
+
+fn check_progress<A>(ptype: Type, algo: A)
+where
+    A: algorithm::Creator<f64, Vect>,
+{
+    let start = [Vect::new(0.5, 0.5)];
+    let builder = Builder::<_, Vect>::with_disk_radius(0.01, ptype).with_start_points(&start, true);
+    let total = builder
+        .clone()
+        .build(SmallRng::seed_from_u64(2), algo)
+        .generate()
+        .len();
+    let mut iter = builder.build(SmallRng::seed_from_u64(2), algo).into_iter();
+    let estimate = iter.remaining_estimate();
+    assert!(estimate.abs_diff(total) * 10 < total, "{} vs {}", estimate, total);
+    let mut previous = estimate;
+    while let Some(_) = iter.next() {
+        let (lower, upper) = iter.size_hint();
+        let estimate = iter.remaining_estimate();
+        assert!(lower <= estimate && upper.is_none_or(|upper| estimate <= upper));
+        assert!(estimate <= previous);
+        previous = estimate;
+    }
+    assert_eq!(total, iter.emitted());
+    assert_eq!(0, iter.remaining_estimate());
+}
+
+#[test]
+fn remaining_estimate_tracks_progress() {
+    check_progress(Type::Normal, algorithm::Ebeida);
+    check_progress(Type::Perioditic, algorithm::Ebeida);
+    check_progress(Type::Normal, algorithm::Bridson);
+    check_progress(Type::Perioditic, algorithm::DartThrowing);
+}
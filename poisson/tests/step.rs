@@ -0,0 +1,47 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Budget, Builder, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+use std::time::Duration;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn stepping_generates_same_samples() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+        .build(SmallRng::seed_from_u64(1), algorithm::Bridson);
+    let mut iter = generator.clone().into_iter();
+    let mut stepped = vec![];
+    while !iter.is_finished() {
+        let points = iter.step(Budget::Points(10));
+        assert!(points.len() <= 10);
+        stepped.extend(points);
+    }
+    assert_eq!(generator.generate(), stepped);
+    assert!(iter.step(Budget::Points(10)).is_empty());
+}
+
+#[test]
+fn time_budget_bounds_step() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.03, Type::Perioditic)
+        .build(SmallRng::seed_from_u64(2), algorithm::Ebeida);
+    let mut iter = generator.clone().into_iter();
+    assert!(iter.step(Budget::Time(Duration::ZERO)).is_empty());
+    assert!(!iter.is_finished());
+    let points = iter.step(Budget::Time(Duration::from_secs(3600)));
+    assert!(iter.is_finished());
+    assert_eq!(generator.generate(), points);
+}
+
+#[test]
+fn stepping_stops_at_max_points() {
+    let mut iter = Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+        .with_max_points(25)
+        .build(SmallRng::seed_from_u64(3), algorithm::Ebeida)
+        .into_iter();
+    assert_eq!(20, iter.step(Budget::Points(20)).len());
+    assert_eq!(5, iter.step(Budget::Points(20)).len());
+    assert!(iter.is_finished());
+}
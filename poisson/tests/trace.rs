@@ -0,0 +1,63 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Boundary, Builder, Domain, PointSet, Trace, TraceEvent, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn replay_reconstructs_generated_set() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.04, Type::Perioditic)
+        .with_start_points(&[Vect::new(0.5, 0.5)], true);
+    let generator = builder.build(SmallRng::seed_from_u64(9), algorithm::Ebeida);
+    let (set, trace) = generator.generate_with_trace();
+    assert_eq!(generator.generate(), set.points());
+    assert_eq!(TraceEvent::Start(Vect::new(0.5, 0.5)), trace.events[0]);
+    let replayed = PointSet::replay(&trace);
+    assert_eq!(set.points(), replayed.points());
+    assert_eq!(set.radius(), replayed.radius());
+    assert_eq!(set.domain(), replayed.domain());
+}
+
+#[test]
+fn trace_counts_match_stats() {
+    let generator = Builder::<_, Vect>::with_disk_radius(0.03, Type::Normal)
+        .build(SmallRng::seed_from_u64(2), algorithm::DartThrowing);
+    let (_, stats) = generator.generate_with_stats();
+    let (_, trace) = generator.generate_with_trace();
+    assert_eq!(stats.candidates, trace.candidates());
+    assert_eq!(stats.accepted, trace.accepted());
+    assert_eq!(stats.rejected, trace.rejected());
+    assert_eq!(Some("DartThrowing"), trace.algorithm.as_deref());
+}
+
+#[test]
+fn iterator_records_only_when_asked() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.05, Type::Normal);
+    let rng = SmallRng::seed_from_u64(1);
+    assert!(builder.clone().build(rng.clone(), algorithm::Bridson).into_iter().trace().is_none());
+    let mut iter = builder.record_trace().build(rng, algorithm::Bridson).into_iter();
+    let first = iter.by_ref().take(3).collect::<Vec<_>>();
+    let trace = iter.trace().unwrap();
+    assert_eq!(first, PointSet::replay(&trace).into_points());
+}
+
+#[test]
+fn text_round_trips_exactly() {
+    let domain = Domain::open()
+        .with_boundary(0, Boundary::Perioditic)
+        .with_extent(1, 0.5);
+    let generator = Builder::<_, na::Vector2<f32>>::with_disk_radius(0.03, domain)
+        .build(SmallRng::seed_from_u64(4), algorithm::Bridson);
+    let (set, trace) = generator.generate_with_trace();
+    let parsed: Trace<f32, na::Vector2<f32>> = trace.to_string().parse().unwrap();
+    assert_eq!(trace, parsed);
+    assert_eq!(set.points(), PointSet::replay(&parsed).points());
+    assert!("radius=0.1 dimension=3 boundaries=oo extents=1.0,1.0\n"
+        .parse::<Trace<f64, Vect>>()
+        .is_err());
+    assert!("radius=0.1 dimension=2 boundaries=oo extents=1.0,1.0\nmaybe 0.1 0.2\n"
+        .parse::<Trace<f64, Vect>>()
+        .is_err());
+}
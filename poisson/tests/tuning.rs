@@ -0,0 +1,38 @@
+#![cfg(feature = "full")]
+use poisson::{algorithm, Builder, Tuning, Type};
+
+use rand::{rngs::SmallRng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn tuning_is_applied_to_builder() {
+    let tuning: Tuning = "# Written by poisson-tune\nalgorithm = \"bridson\"\ngrid_factor = 0.7\nbridson_k = 12\n"
+        .parse()
+        .unwrap();
+    let builder = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal).with_tuning(&tuning);
+    assert_eq!(0.7, builder.cell_scale());
+    assert_eq!(12, builder.attempts());
+}
+
+#[test]
+fn invalid_tuning_is_rejected() {
+    assert!("bridson_k = many".parse::<Tuning>().is_err());
+    assert!("speed = 11".parse::<Tuning>().is_err());
+    assert!("algorithm = bridson".parse::<Tuning>().is_err());
+}
+
+#[test]
+fn fewer_attempts_leave_more_gaps() {
+    let builder = Builder::<_, Vect>::with_disk_radius(0.02, Type::Normal);
+    let count = |attempts| {
+        builder
+            .clone()
+            .with_attempts(attempts)
+            .build(SmallRng::seed_from_u64(6), algorithm::Bridson)
+            .generate()
+            .len()
+    };
+    assert!(count(2) < count(60));
+}
@@ -1,3 +1,4 @@
+#![cfg(feature = "full")]
 use poisson::Type;
 
 use rand::{rngs::SmallRng, Rng, SeedableRng};
@@ -15,6 +16,7 @@ use crate::helper::When::*;
 mod helper;
 
 #[test]
+#[allow(clippy::redundant_pattern_matching)]
 fn multiple_too_close_invalid() {
     let samples = 101; // TODO: 100 freezes forever.
     let relative_radius = 0.8;
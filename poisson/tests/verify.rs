@@ -0,0 +1,74 @@
+#![cfg(feature = "full")]
+use poisson::verify::{verify_separation, violations, Violation};
+use poisson::{algorithm, Boundary, Builder, Domain, Type};
+
+use rand::{rngs::SmallRng, Rng, SeedableRng};
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector2<f64>;
+
+#[test]
+fn generated_distributions_are_separated() {
+    for ptype in [Type::Normal, Type::Perioditic] {
+        let points = Builder::<_, Vect>::with_disk_radius(0.02, ptype)
+            .build(SmallRng::seed_from_u64(1), algorithm::Ebeida)
+            .generate();
+        assert_eq!(Ok(()), verify_separation(&points, 0.02, ptype));
+        let points = Builder::<_, na::Vector4<f64>>::with_disk_radius(0.1, ptype)
+            .build(SmallRng::seed_from_u64(1), algorithm::Bridson)
+            .generate();
+        assert_eq!(Ok(()), verify_separation(&points, 0.1, ptype));
+    }
+}
+
+#[test]
+fn separation_wraps_around_perioditic_axes() {
+    let points = vec![Vect::new(0.01, 0.5), Vect::new(0.99, 0.5)];
+    assert_eq!(Ok(()), verify_separation(&points, 0.02, Type::Normal));
+    let violation = verify_separation(&points, 0.02, Type::Perioditic).unwrap_err();
+    assert_eq!((0, 1), (violation.first, violation.second));
+    assert!((violation.distance - 0.02).abs() < 1e-9);
+    let cylinder = Domain::open().with_boundary(1, Boundary::Perioditic);
+    assert_eq!(Ok(()), verify_separation(&points, 0.02, cylinder));
+    let cylinder = Domain::open().with_boundary(0, Boundary::Perioditic);
+    assert!(verify_separation(&points, 0.02, cylinder).is_err());
+}
+
+#[test]
+fn separation_wraps_across_a_short_last_cell() {
+    // Cells are 0.04 wide so the last cell along y is only 0.03 wide and the samples are two cells apart.
+    let strip = Domain::perioditic().with_extent(1, 0.23);
+    let points = vec![Vect::new(0.5, 0.001), Vect::new(0.5, 0.199)];
+    let violation = verify_separation(&points, 0.02, strip).unwrap_err();
+    assert!((violation.distance - 0.032).abs() < 1e-9);
+}
+
+#[test]
+fn violations_match_brute_force() {
+    let mut rng = SmallRng::seed_from_u64(4);
+    for ptype in [Type::Normal, Type::Perioditic] {
+        // Some of the points are outside of the domain.
+        let points = (0..300)
+            .map(|_| Vect::new(rng.random_range(-0.1..1.1), rng.random_range(0.0..1.0)))
+            .collect::<Vec<_>>();
+        let radius = 0.01;
+        let mut expected = vec![];
+        for i in 0..points.len() {
+            for j in i + 1..points.len() {
+                let mut diff = points[j] - points[i];
+                if ptype == Type::Perioditic {
+                    diff = diff.map(|d| d - d.round());
+                }
+                if diff.norm() < 2. * radius {
+                    expected.push((i, j));
+                }
+            }
+        }
+        assert!(!expected.is_empty());
+        let found = violations(&points, radius, ptype)
+            .into_iter()
+            .map(|Violation { first, second, .. }| (first, second))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, found);
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "full")]
+use poisson::VolumeSampler;
+
+extern crate nalgebra as na;
+pub type Vect = na::Vector3<f64>;
+
+#[test]
+fn samples_are_legal_across_chunks() {
+    let mut sampler = VolumeSampler::<f64, Vect>::new(1., 6., 3);
+    let samples = sampler.points_in_aabb(Vect::new(-6., -6., -6.), Vect::new(6., 6., 6.));
+    assert!(!samples.is_empty());
+    for (i, a) in samples.iter().enumerate() {
+        for b in &samples[i + 1..] {
+            assert!((b - a).norm() >= 2.);
+        }
+    }
+}
+
+#[test]
+fn samples_do_not_depend_on_query_order() {
+    let (min, max) = (Vect::new(2., -3., 5.), Vect::new(9., 4., 8.));
+    let mut fresh = VolumeSampler::<f64, Vect>::new(1., 6., 3);
+    let expected = fresh.points_in_aabb(min, max);
+    let mut sampler = VolumeSampler::<f64, Vect>::new(1., 6., 3);
+    sampler.points_in_aabb(Vect::new(20., 20., 20.), Vect::new(30., 30., 30.));
+    sampler.points_in_aabb(Vect::new(-10., -10., -10.), Vect::new(3., 3., 6.));
+    assert_eq!(expected, sampler.points_in_aabb(min, max));
+    sampler.clear();
+    assert_eq!(expected, sampler.points_in_aabb(min, max));
+}
+
+#[test]
+fn different_seeds_give_different_samples() {
+    let (min, max) = (Vect::new(0., 0., 0.), Vect::new(6., 6., 6.));
+    let a = VolumeSampler::<f64, Vect>::new(1., 6., 3).points_in_aabb(min, max);
+    let b = VolumeSampler::<f64, Vect>::new(1., 6., 4).points_in_aabb(min, max);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn faces_of_chunks_are_as_dense_as_their_interiors() {
+    let mut sampler = VolumeSampler::<f64, na::Vector2<f64>>::new(0.5, 8., 11);
+    let samples = sampler.points_in_aabb(na::Vector2::new(-16., -16.), na::Vector2::new(16., 16.));
+    for (i, a) in samples.iter().enumerate() {
+        for b in &samples[i + 1..] {
+            assert!((b - a).norm() >= 1.);
+        }
+    }
+    // Every probe away from the edges of the box is covered by the disk of a sample, including on the faces.
+    let mut probes = 0;
+    let mut uncovered = 0;
+    for x in -56..56 {
+        for y in -56..56 {
+            let probe = na::Vector2::new(x as f64 / 4., y as f64 / 4.);
+            probes += 1;
+            if samples.iter().all(|s| (s - probe).norm() >= 1.) {
+                uncovered += 1;
+            }
+        }
+    }
+    assert!(uncovered * 100 < probes, "{} of {} probes uncovered", uncovered, probes);
+}